@@ -175,6 +175,8 @@ fn testnet_genesis(
                 .cloned()
                 .map(|k| (k, ENDOWMENT))
                 .collect(),
+            reserved_balances: vec![],
+            locks: vec![],
         }),
         pallet_indices: Some(IndicesConfig {
             indices: vec![],
@@ -0,0 +1,64 @@
+// Copyright (C) 2019-2021 Calcu Network Technologies Ltd.
+// This file is part of Calcu.
+
+//! Runtime API definition for the balances pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+use frame_support::traits::{LockIdentifier, WithdrawReasons};
+use sp_runtime::{DispatchError, traits::NumberFor};
+pub use balances::{BalanceLock, AccountData, SimulatedTransfer, BalanceSummary};
+
+sp_api::decl_runtime_apis! {
+	/// The helper API to query balances.
+	pub trait BalancesApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// The balance `account` can send right now while staying alive.
+		fn transferable_balance(account: AccountId) -> Balance;
+		/// The lock with the given `id` on `account`, if there is one.
+		fn lock_by_id(account: AccountId, id: LockIdentifier) -> Option<BalanceLock<Balance, NumberFor<Block>>>;
+		/// The free balance of `account` not frozen by any non-fee lock.
+		fn usable_balance(account: AccountId) -> Balance;
+		/// The free balance of `account` not frozen by any fee lock.
+		fn usable_balance_for_fees(account: AccountId) -> Balance;
+		/// The free, reserved and frozen balances of `account`.
+		fn account_data(account: AccountId) -> AccountData<Balance>;
+		/// The free balance of `account` frozen for fees and for anything else, in that order.
+		fn frozen_balance_breakdown(account: AccountId) -> (Balance, Balance);
+		/// Whether `value` of the free balance of `account` can be reserved.
+		fn can_reserve(account: AccountId, value: Balance) -> bool;
+		/// Whether `value` can be withdrawn from `account` for `reasons`, allowing it to be reaped.
+		fn can_withdraw(account: AccountId, value: Balance, reasons: WithdrawReasons) -> bool;
+		/// The total issuance at the start of `era`, if it is still within the kept history.
+		fn issuance_at_era(era: u32) -> Option<Balance>;
+		/// The total issuance less the balances of the non-circulating system accounts.
+		fn circulating_supply() -> Balance;
+		/// The providers, consumers and sufficients reference counts of `account`.
+		fn account_refs(account: AccountId) -> (u32, u32, u32);
+		/// Whether `account` would be reaped once its balance is gone.
+		fn is_reapable(account: AccountId) -> bool;
+		/// What transferring `value` from `from` to `to` would do to both accounts.
+		fn simulate_transfer(from: AccountId, to: AccountId, value: Balance) -> SimulatedTransfer<Balance>;
+		/// The block at which the balance of `account` last changed, if it exists.
+		fn last_active(account: AccountId) -> Option<NumberFor<Block>>;
+		/// The part of the balance under the vesting schedule of `account` which is vested by now.
+		fn vested_balance(account: AccountId) -> Balance;
+		/// The free, reserved, frozen, transferable and total balance of `account`.
+		fn balance_summary(account: AccountId) -> BalanceSummary<Balance>;
+		/// What a `transfer` of `value` from `from` to `to` would return, without making it.
+		fn dry_run_transfer(from: AccountId, to: AccountId, value: Balance) -> Result<(), DispatchError>;
+		/// Up to `limit` accounts with a total balance above `threshold` after `start_key`, and
+		/// the key to continue from if there may be more.
+		fn accounts_above(
+			threshold: Balance,
+			start_key: Option<AccountId>,
+			limit: u32,
+		) -> (Vec<(AccountId, Balance)>, Option<AccountId>);
+		/// The opaque tag of `account`, e.g. its KYC tier, if it has one.
+		fn account_tag(account: AccountId) -> Option<Vec<u8>>;
+	}
+}
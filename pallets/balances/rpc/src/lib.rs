@@ -0,0 +1,262 @@
+// Copyright (C) 2019-2021 Calcu Network Technologies Ltd.
+// This file is part of Calcu.
+
+//! RPC interface for the balances pallet.
+
+use std::sync::Arc;
+use std::fmt::Display;
+use std::str::FromStr;
+use codec::Codec;
+use sp_blockchain::HeaderBackend;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, Saturating}};
+use sp_api::{ProvideRuntimeApi, ApiExt};
+use serde::{Serialize, Deserialize};
+pub use balances_rpc_runtime_api::BalancesApi as BalancesRuntimeApi;
+
+#[rpc]
+pub trait BalancesApi<BlockHash, AccountId> {
+	#[rpc(name = "balances_transferableBalance")]
+	fn transferable_balance(&self, account: AccountId, at: Option<BlockHash>) -> Result<String>;
+
+	#[rpc(name = "balances_usableBalance")]
+	fn usable_balance(&self, account: AccountId, at: Option<BlockHash>) -> Result<String>;
+
+	#[rpc(name = "balances_usableBalanceForFees")]
+	fn usable_balance_for_fees(&self, account: AccountId, at: Option<BlockHash>) -> Result<String>;
+
+	#[rpc(name = "balances_totalBalance")]
+	fn total_balance(&self, account: AccountId, at: Option<BlockHash>) -> Result<String>;
+
+	#[rpc(name = "balances_freeBalance")]
+	fn free_balance(&self, account: AccountId, at: Option<BlockHash>) -> Result<String>;
+
+	#[rpc(name = "balances_frozenBalanceBreakdown")]
+	fn frozen_balance_breakdown(&self, account: AccountId, at: Option<BlockHash>) -> Result<(String, String)>;
+
+	#[rpc(name = "balances_balanceSummary")]
+	fn balance_summary(&self, account: AccountId, at: Option<BlockHash>) -> Result<BalanceSummary>;
+
+	#[rpc(name = "balances_accountsAbove")]
+	fn accounts_above(
+		&self,
+		threshold: String,
+		start_key: Option<AccountId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> Result<AccountsAbove<AccountId>>;
+}
+
+/// The `BalanceSummary` of the runtime API, with the balances as decimal strings.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceSummary {
+	pub free: String,
+	pub reserved: String,
+	pub frozen: String,
+	pub transferable: String,
+	pub total: String,
+}
+
+/// A page of the accounts above a threshold, with their total balances as decimal strings, and
+/// the `start_key` of the next page if there may be one.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsAbove<AccountId> {
+	pub accounts: Vec<(AccountId, String)>,
+	pub next_key: Option<AccountId>,
+}
+
+/// A struct that implements the [`BalancesApi`].
+pub struct Balances<C, B, Balance> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(B, Balance)>,
+}
+
+impl<C, B, Balance> Balances<C, B, Balance> {
+	/// Create new `Balances` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The requested block is unknown to the node.
+	UnknownBlock,
+	/// A balance given isn't a decimal number of the runtime's balance type.
+	InvalidBalance,
+	/// The state of the requested block was pruned, or is not yet imported.
+	StateUnavailable,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::UnknownBlock => 2,
+			Error::InvalidBalance => 3,
+			Error::StateUnavailable => 4,
+		}
+	}
+}
+
+impl<C, Block, Balance> Balances<C, Block, Balance> where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+{
+	/// Resolve `at`, defaulting to the best block, and make sure the block is known and its state
+	/// still available. Only archive nodes keep the state of all blocks, other nodes only that of
+	/// the latest ones.
+	fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> Result<BlockId<Block>> {
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		let at = BlockId::hash(hash);
+		match self.client.header(at) {
+			Ok(Some(_)) => {},
+			_ => return Err(RpcError {
+				code: ErrorCode::ServerError(Error::UnknownBlock.into()),
+				message: "Unknown block.".into(),
+				data: Some(format!("{:?}", hash).into()),
+			}),
+		}
+		// the runtime version is read from the state of the block, so this fails once it is pruned.
+		self.client.runtime_api().has_api::<dyn sp_api::Core<Block>>(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::StateUnavailable.into()),
+			message: "The state of the block is unavailable. Old blocks are only kept on archive nodes.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		Ok(at)
+	}
+}
+
+impl<C, Block, AccountId, Balance> BalancesApi<<Block as BlockT>::Hash, AccountId> for Balances<C, Block, Balance>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: BalancesRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec,
+	Balance: Codec + Display + FromStr + Saturating + Send + Sync + 'static,
+{
+	/*
+		$ curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d   '{
+		  "jsonrpc":"2.0",
+		  "id":1,
+		  "method":"balances_transferableBalance",
+		  "params": ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+		}'
+		{"jsonrpc":"2.0","result":"1000000000000","id":1}
+	 */
+	fn transferable_balance(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<String> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at)?;
+		api.transferable_balance(&at, account).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query transferable balance.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(|balance| format!("{}", balance))
+	}
+
+	fn usable_balance(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<String> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at)?;
+		api.usable_balance(&at, account).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query usable balance.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(|balance| format!("{}", balance))
+	}
+
+	fn usable_balance_for_fees(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<String> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at)?;
+		api.usable_balance_for_fees(&at, account).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query usable balance for fees.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(|balance| format!("{}", balance))
+	}
+
+	fn total_balance(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<String> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at)?;
+		api.account_data(&at, account).map_err(account_data_error)
+			.map(|data| format!("{}", data.free.saturating_add(data.reserved)))
+	}
+
+	fn free_balance(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<String> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at)?;
+		api.account_data(&at, account).map_err(account_data_error)
+			.map(|data| format!("{}", data.free))
+	}
+
+	fn frozen_balance_breakdown(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<(String, String)> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at)?;
+		api.frozen_balance_breakdown(&at, account).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query frozen balance breakdown.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(|(fee, misc)| (format!("{}", fee), format!("{}", misc)))
+	}
+
+	fn balance_summary(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<BalanceSummary> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at)?;
+		api.balance_summary(&at, account).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query balance summary.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(|summary| BalanceSummary {
+			free: format!("{}", summary.free),
+			reserved: format!("{}", summary.reserved),
+			frozen: format!("{}", summary.frozen),
+			transferable: format!("{}", summary.transferable),
+			total: format!("{}", summary.total),
+		})
+	}
+
+	fn accounts_above(
+		&self,
+		threshold: String,
+		start_key: Option<AccountId>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<AccountsAbove<AccountId>> {
+		let threshold = threshold.parse::<Balance>().map_err(|_| RpcError {
+			code: ErrorCode::ServerError(Error::InvalidBalance.into()),
+			message: "The threshold isn't a valid balance.".into(),
+			data: Some(threshold.clone().into()),
+		})?;
+		let api = self.client.runtime_api();
+		let at = self.block_id(at)?;
+		api.accounts_above(&at, threshold, start_key, limit).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query accounts above the threshold.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(|(accounts, next_key)| AccountsAbove {
+			accounts: accounts.into_iter()
+				.map(|(who, total)| (who, format!("{}", total)))
+				.collect(),
+			next_key,
+		})
+	}
+}
+
+fn account_data_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query account data.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+#[cfg(test)]
+mod tests;
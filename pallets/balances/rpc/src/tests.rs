@@ -0,0 +1,170 @@
+// Copyright (C) 2019-2021 Calcu Network Technologies Ltd.
+// This file is part of Calcu.
+
+//! Tests of the RPC against a chain whose runtime API is mocked.
+
+use super::*;
+use std::collections::HashMap;
+use sp_api::{ApiRef, ApiError, NativeOrEncoded};
+use sp_blockchain::{BlockStatus, Info};
+use sp_runtime::{
+	DispatchError, OpaqueExtrinsic, generic::Header as GenericHeader,
+	traits::{BlakeTwo256, Header as HeaderT, NumberFor},
+};
+use frame_support::traits::WithdrawReasons;
+use balances_rpc_runtime_api::{AccountData, BalanceLock, SimulatedTransfer, BalanceSummary};
+
+type Header = GenericHeader<u64, BlakeTwo256>;
+type Block = sp_runtime::generic::Block<Header, OpaqueExtrinsic>;
+type Hash = <Block as BlockT>::Hash;
+
+/// The runtime API of the chain, knowing the free balance of the queried account at each block.
+#[derive(Clone)]
+struct TestApi {
+	free: HashMap<Hash, u64>,
+}
+
+sp_api::mock_impl_runtime_apis! {
+	impl BalancesRuntimeApi<Block, u64, u64> for TestApi {
+		fn transferable_balance(_account: u64) -> u64 { unimplemented!() }
+		fn lock_by_id(_account: u64, _id: [u8; 8]) -> Option<BalanceLock<u64, u64>> { unimplemented!() }
+		fn usable_balance(_account: u64) -> u64 { unimplemented!() }
+		fn usable_balance_for_fees(_account: u64) -> u64 { unimplemented!() }
+
+		#[advanced]
+		fn account_data(
+			&self,
+			at: &BlockId<Block>,
+			_account: u64,
+		) -> std::result::Result<NativeOrEncoded<AccountData<u64>>, ApiError> {
+			let free = match at {
+				BlockId::Hash(hash) => self.free[hash],
+				BlockId::Number(_) => unreachable!("the RPC always queries by hash"),
+			};
+			Ok(NativeOrEncoded::Native(AccountData { free, reserved: 5, ..Default::default() }))
+		}
+
+		fn frozen_balance_breakdown(_account: u64) -> (u64, u64) { unimplemented!() }
+		fn can_reserve(_account: u64, _value: u64) -> bool { unimplemented!() }
+		fn can_withdraw(_account: u64, _value: u64, _reasons: WithdrawReasons) -> bool { unimplemented!() }
+		fn issuance_at_era(_era: u32) -> Option<u64> { unimplemented!() }
+		fn circulating_supply() -> u64 { unimplemented!() }
+		fn account_refs(_account: u64) -> (u32, u32, u32) { unimplemented!() }
+		fn is_reapable(_account: u64) -> bool { unimplemented!() }
+		fn simulate_transfer(_from: u64, _to: u64, _value: u64) -> SimulatedTransfer<u64> { unimplemented!() }
+		fn last_active(_account: u64) -> Option<u64> { unimplemented!() }
+		fn vested_balance(_account: u64) -> u64 { unimplemented!() }
+		fn balance_summary(_account: u64) -> BalanceSummary<u64> { unimplemented!() }
+		fn dry_run_transfer(_from: u64, _to: u64, _value: u64) -> std::result::Result<(), DispatchError> {
+			unimplemented!()
+		}
+		fn accounts_above(
+			_threshold: u64,
+			_start_key: Option<u64>,
+			_limit: u32,
+		) -> (Vec<(u64, u64)>, Option<u64>) {
+			unimplemented!()
+		}
+		fn account_tag(_account: u64) -> Option<Vec<u8>> { unimplemented!() }
+	}
+}
+
+/// A chain of blocks, the last of which is the best one.
+struct TestClient {
+	headers: Vec<Header>,
+	api: TestApi,
+}
+
+impl TestClient {
+	/// A chain with a block for each of the free balances, as of that block, of the account queried.
+	fn new(free: &[u64]) -> Self {
+		let mut headers: Vec<Header> = Vec::new();
+		let mut balances = HashMap::new();
+		for (number, free) in free.iter().enumerate() {
+			let parent_hash = headers.last().map(|h| h.hash()).unwrap_or_default();
+			let header = Header::new(
+				number as u64,
+				Default::default(),
+				Default::default(),
+				parent_hash,
+				Default::default(),
+			);
+			balances.insert(header.hash(), *free);
+			headers.push(header);
+		}
+		TestClient { headers, api: TestApi { free: balances } }
+	}
+}
+
+impl ProvideRuntimeApi<Block> for TestClient {
+	type Api = TestApi;
+
+	fn runtime_api<'a>(&'a self) -> ApiRef<'a, Self::Api> {
+		self.api.clone().into()
+	}
+}
+
+impl HeaderBackend<Block> for TestClient {
+	fn header(&self, id: BlockId<Block>) -> sp_blockchain::Result<Option<Header>> {
+		Ok(match id {
+			BlockId::Hash(hash) => self.headers.iter().find(|h| h.hash() == hash).cloned(),
+			BlockId::Number(number) => self.headers.get(number as usize).cloned(),
+		})
+	}
+
+	fn info(&self) -> Info<Block> {
+		let best = self.headers.last().expect("the chain has a genesis block; qed");
+		Info {
+			best_hash: best.hash(),
+			best_number: *best.number(),
+			genesis_hash: self.headers[0].hash(),
+			finalized_hash: best.hash(),
+			finalized_number: *best.number(),
+			number_leaves: 1,
+		}
+	}
+
+	fn status(&self, id: BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
+		Ok(if self.header(id)?.is_some() { BlockStatus::InChain } else { BlockStatus::Unknown })
+	}
+
+	fn number(&self, hash: Hash) -> sp_blockchain::Result<Option<NumberFor<Block>>> {
+		Ok(self.header(BlockId::Hash(hash))?.map(|h| *h.number()))
+	}
+
+	fn hash(&self, number: NumberFor<Block>) -> sp_blockchain::Result<Option<Hash>> {
+		Ok(self.headers.get(number as usize).map(|h| h.hash()))
+	}
+}
+
+#[test]
+fn balances_are_queried_at_historical_blocks() {
+	let client = Arc::new(TestClient::new(&[100, 70, 40]));
+	let rpc = Balances::<_, Block, u64>::new(client.clone());
+	let at = |number| client.hash(number).unwrap();
+
+	assert_eq!(rpc.free_balance(1u64, at(0)).unwrap(), "100");
+	assert_eq!(rpc.free_balance(1u64, at(1)).unwrap(), "70");
+	assert_eq!(rpc.total_balance(1u64, at(0)).unwrap(), "105");
+	assert_eq!(rpc.total_balance(1u64, at(1)).unwrap(), "75");
+
+	// the best block by default.
+	assert_eq!(rpc.free_balance(1u64, None).unwrap(), "40");
+}
+
+#[test]
+fn unknown_blocks_are_rejected_by_every_query() {
+	let client = Arc::new(TestClient::new(&[100]));
+	let rpc = Balances::<_, Block, u64>::new(client);
+	let unknown = Some(Hash::repeat_byte(1));
+	let unknown_block = ErrorCode::ServerError(Error::UnknownBlock.into());
+
+	assert_eq!(rpc.free_balance(1u64, unknown).unwrap_err().code, unknown_block);
+	assert_eq!(rpc.total_balance(1u64, unknown).unwrap_err().code, unknown_block);
+	assert_eq!(rpc.transferable_balance(1u64, unknown).unwrap_err().code, unknown_block);
+	assert_eq!(rpc.usable_balance(1u64, unknown).unwrap_err().code, unknown_block);
+	assert_eq!(rpc.usable_balance_for_fees(1u64, unknown).unwrap_err().code, unknown_block);
+	assert_eq!(rpc.frozen_balance_breakdown(1u64, unknown).unwrap_err().code, unknown_block);
+	assert_eq!(rpc.balance_summary(1u64, unknown).unwrap_err().code, unknown_block);
+	assert_eq!(rpc.accounts_above("0".into(), None, 10, unknown).unwrap_err().code, unknown_block);
+}
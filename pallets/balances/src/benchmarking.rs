@@ -8,8 +8,9 @@
 use super::*;
 
 use frame_system::RawOrigin;
+use frame_support::traits::OnInitialize;
 use frame_benchmarking::{benchmarks, account, whitelisted_caller, impl_benchmark_test_suite};
-use sp_runtime::traits::Bounded;
+use sp_runtime::traits::{Bounded, TrailingZeroInput};
 
 use crate::Module as Balances;
 
@@ -17,6 +18,16 @@ const SEED: u32 = 0;
 // existential deposit multiplier
 const ED_MULTIPLIER: u32 = 10;
 
+// The first block after the current one which isn't a `BurnInterval` boundary, if there is one.
+fn block_without_burn<T: Config>() -> T::BlockNumber {
+	let interval = T::BurnInterval::get();
+	let mut n = frame_system::Pallet::<T>::block_number() + One::one();
+	if interval > One::one() && (n % interval).is_zero() {
+		n += One::one();
+	}
+	n
+}
+
 
 benchmarks! {
 	// Benchmark `transfer` extrinsic with the worst possible conditions:
@@ -42,7 +53,6 @@ benchmarks! {
 
 	// Benchmark `transfer` with the best possible condition:
 	// * Both accounts exist and will continue to exist.
-	#[extra]
 	transfer_best_case {
 		let caller = whitelisted_caller();
 		let recipient: T::AccountId = account("recipient", 0, SEED);
@@ -128,6 +138,931 @@ benchmarks! {
 		assert_eq!(Balances::<T>::free_balance(&source), Zero::zero());
 		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
 	}
+
+	// Benchmark `transfer_all` with the worst possible condition:
+	// * The recipient account is created
+	// * The sender is killed
+	transfer_all {
+		let caller = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+
+		// Give some multiple of the existential deposit
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+	}: _(RawOrigin::Signed(caller.clone()), recipient_lookup, false)
+	verify {
+		assert!(Balances::<T>::free_balance(&caller).is_zero());
+		assert_eq!(Balances::<T>::free_balance(&recipient), balance);
+	}
+
+	// Benchmark `transfer_all` with `keep_alive`:
+	// * The recipient account is created
+	// * The sender survives with the existential deposit
+	#[extra]
+	transfer_all_keep_alive {
+		let caller = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+
+		// Give some multiple of the existential deposit
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+	}: transfer_all(RawOrigin::Signed(caller.clone()), recipient_lookup, true)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&caller), existential_deposit);
+		assert_eq!(Balances::<T>::free_balance(&recipient), balance - existential_deposit);
+	}
+
+	// Benchmark `transfer_batch` where every leg creates its recipient account.
+	transfer_batch {
+		let b in 1 .. T::MaxBatchSize::get();
+
+		let caller = whitelisted_caller();
+		// Give the sender account max funds, thus no leg will kill the account.
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, T::Balance::max_value());
+		let existential_deposit = T::ExistentialDeposit::get();
+		let transfer_amount = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+
+		let mut recipients = Vec::new();
+		for i in 0 .. b {
+			let recipient: T::AccountId = account("recipient", i, SEED);
+			recipients.push((T::Lookup::unlookup(recipient), transfer_amount));
+		}
+	}: _(RawOrigin::Signed(caller.clone()), recipients)
+	verify {
+		let recipient: T::AccountId = account("recipient", b - 1, SEED);
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+	}
+
+	// Benchmark `transfer_with_memo` with the recipient account being created and a memo of
+	// length `m`.
+	transfer_with_memo {
+		let m in 0 .. T::MaxMemoLength::get();
+
+		let caller = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+
+		// Give the sender account max funds, thus a transfer will not kill account.
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, T::Balance::max_value());
+		let existential_deposit = T::ExistentialDeposit::get();
+		let transfer_amount = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let memo = vec![0u8; m as usize];
+	}: _(RawOrigin::Signed(caller.clone()), recipient_lookup, transfer_amount, memo)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+	}
+
+	// Benchmark `set_lock` adding a new lock to an account which already has `l - 1` locks.
+	set_lock {
+		let l in 1 .. T::MaxLocks::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+
+		let lock_id = |i: u32| {
+			let mut id: LockIdentifier = [0u8; 8];
+			id[..4].copy_from_slice(&i.to_le_bytes());
+			id
+		};
+		for i in 1 .. l {
+			<Balances<T> as LockableCurrency<_>>::set_lock(lock_id(i), &caller, existential_deposit, WithdrawReasons::all());
+		}
+	}: {
+		<Balances<T> as LockableCurrency<_>>::set_lock(lock_id(0), &caller, balance, WithdrawReasons::all());
+	}
+	verify {
+		assert_eq!(Balances::<T>::locks(&caller).len() as u32, l);
+		assert_eq!(Balances::<T>::lock_by_id(&caller, lock_id(0)).map(|lock| lock.amount), Some(balance));
+	}
+
+	// Benchmark `force_unreserve` releasing the whole reserved balance of an account.
+	force_unreserve {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+
+		// Give some multiple of the existential deposit and reserve all of it.
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&user, balance);
+		<Balances<T> as ReservableCurrency<_>>::reserve(&user, balance)?;
+		assert_eq!(Balances::<T>::reserved_balance(&user), balance);
+		assert!(Balances::<T>::free_balance(&user).is_zero());
+	}: _(RawOrigin::Root, user_lookup, balance)
+	verify {
+		assert!(Balances::<T>::reserved_balance(&user).is_zero());
+		assert_eq!(Balances::<T>::free_balance(&user), balance);
+	}
+
+	// Benchmark `burn` with the worst possible condition:
+	// * The caller is reaped
+	burn {
+		let caller = whitelisted_caller();
+
+		// Give some multiple of the existential deposit
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let issuance = Balances::<T>::total_issuance();
+	}: _(RawOrigin::Signed(caller.clone()), balance, false)
+	verify {
+		assert!(Balances::<T>::free_balance(&caller).is_zero());
+		assert_eq!(Balances::<T>::total_issuance(), issuance - balance);
+	}
+
+	// Benchmark `burn` with `keep_alive`:
+	// * The caller survives with the existential deposit
+	#[extra]
+	burn_keep_alive {
+		let caller = whitelisted_caller();
+
+		// Give some multiple of the existential deposit
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let amount = balance - existential_deposit;
+	}: burn(RawOrigin::Signed(caller.clone()), amount, true)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&caller), existential_deposit);
+	}
+
+	// Benchmark `set_balance_batch` where every entry creates its account.
+	set_balance_batch {
+		let e in 1 .. T::MaxBatchSize::get();
+
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance_amount = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+
+		let mut entries = Vec::new();
+		for i in 0 .. e {
+			let user: T::AccountId = account("user", i, SEED);
+			entries.push((T::Lookup::unlookup(user), balance_amount, balance_amount));
+		}
+	}: _(RawOrigin::Root, entries)
+	verify {
+		let user: T::AccountId = account("user", e - 1, SEED);
+		assert_eq!(Balances::<T>::free_balance(&user), balance_amount);
+		assert_eq!(Balances::<T>::reserved_balance(&user), balance_amount);
+	}
+
+	// Benchmark `prune_expired_locks` where all `l` locks of the account have expired.
+	prune_expired_locks {
+		let l in 1 .. T::MaxLocks::get();
+
+		let caller = whitelisted_caller();
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&user, balance);
+
+		let until = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		for i in 0 .. l {
+			let mut id: LockIdentifier = [0u8; 8];
+			id[..4].copy_from_slice(&i.to_le_bytes());
+			Balances::<T>::try_set_lock_until(id, &user, balance, WithdrawReasons::all(), until)?;
+		}
+		frame_system::Pallet::<T>::set_block_number(until);
+	}: _(RawOrigin::Signed(caller), user_lookup)
+	verify {
+		assert!(Balances::<T>::locks(&user).is_empty());
+	}
+
+	// Benchmark `repatriate_reserved_split` where the beneficiary account is created and both
+	// portions are fully covered.
+	repatriate_reserved_split {
+		let slashed: T::AccountId = account("slashed", 0, SEED);
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&slashed, balance);
+		<Balances<T> as ReservableCurrency<_>>::reserve(&slashed, balance - existential_deposit)?;
+	}: {
+		let shortfall = Balances::<T>::repatriate_reserved_split(
+			&slashed,
+			&beneficiary,
+			existential_deposit,
+			existential_deposit,
+		);
+		assert!(shortfall.is_zero());
+	}
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&beneficiary), existential_deposit);
+		assert_eq!(Balances::<T>::reserved_balance(&beneficiary), existential_deposit);
+	}
+
+	// Benchmark `set_existential_deposit` coming from `SetEDOrigin`.
+	set_existential_deposit {
+		let origin = T::SetEDOrigin::successful_origin();
+		let new = T::ExistentialDeposit::get().saturating_mul(ED_MULTIPLIER.into());
+	}: {
+		Balances::<T>::set_existential_deposit(origin, new)?;
+	}
+	verify {
+		assert_eq!(Balances::<T>::existential_deposit(), new);
+	}
+
+	// Benchmark `ensure_reserved` reserving the difference up to the target.
+	ensure_reserved {
+		let user: T::AccountId = account("user", 0, SEED);
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&user, balance);
+		<Balances<T> as ReservableCurrency<_>>::reserve(&user, existential_deposit)?;
+		let target = balance - existential_deposit;
+	}: {
+		Balances::<T>::ensure_reserved(&user, target)?;
+	}
+	verify {
+		assert_eq!(Balances::<T>::reserved_balance(&user), target);
+	}
+
+	// Benchmark `transfer_if_exists` to a recipient which already exists.
+	transfer_if_exists {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&recipient, existential_deposit);
+		let transfer_amount = existential_deposit.saturating_mul((ED_MULTIPLIER - 1).into());
+	}: _(RawOrigin::Signed(caller.clone()), recipient_lookup, transfer_amount)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), balance);
+	}
+
+	// Benchmark merging an account holding both free and reserved balance. The signature check
+	// is left out, as signatures of a generic account can't be produced here.
+	merge_accounts {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let other: T::AccountId = account("other", 0, SEED);
+
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&other, balance);
+		<Balances<T> as ReservableCurrency<_>>::reserve(&other, existential_deposit)?;
+	}: {
+		Balances::<T>::do_merge_accounts(&caller, &other)?;
+	}
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&caller), balance + balance - existential_deposit);
+		assert_eq!(Balances::<T>::reserved_balance(&caller), existential_deposit);
+		assert!(Balances::<T>::total_balance(&other).is_zero());
+		assert_eq!(Balances::<T>::merge_nonce(&other), 1);
+	}
+
+	// Benchmark the `MinTransfer` check the signed transfer calls make on their own, to confirm
+	// it is negligible next to their weight.
+	ensure_min_transfer {
+		let value = T::MinTransfer::get();
+	}: {
+		Balances::<T>::ensure_min_transfer(value)?;
+	}
+
+	// Benchmark `reserved_transfer` creating the recipient, the worst case.
+	reserved_transfer {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let transfer_amount = existential_deposit.saturating_mul((ED_MULTIPLIER - 1).into());
+	}: _(RawOrigin::Signed(caller.clone()), recipient_lookup, transfer_amount)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), Zero::zero());
+		assert_eq!(Balances::<T>::reserved_balance(&recipient), transfer_amount);
+	}
+
+	set_fee_from_reserved {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()), true)
+	verify {
+		assert!(Balances::<T>::fee_from_reserved(&caller));
+	}
+
+	// Benchmark `transfer_bounded` leaving exactly `min_remaining`.
+	transfer_bounded {
+		let caller = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let transfer_amount = existential_deposit;
+		let min_remaining = balance - transfer_amount;
+	}: _(RawOrigin::Signed(caller.clone()), recipient_lookup, transfer_amount, min_remaining)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&caller), min_remaining);
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+	}
+
+	// Benchmark `transfer_keep_alive` from an account with `l` locks. The frozen amount is cached
+	// in its `AccountData`, so the weight should not depend on `l`.
+	transfer_with_locks {
+		let l in 0 .. T::MaxLocks::get();
+
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+
+		for i in 0 .. l {
+			let mut id: LockIdentifier = [0u8; 8];
+			id[..4].copy_from_slice(&i.to_le_bytes());
+			<Balances<T> as LockableCurrency<_>>::set_lock(id, &caller, existential_deposit, WithdrawReasons::all());
+		}
+		let transfer_amount = existential_deposit.saturating_mul((ED_MULTIPLIER - 2).into());
+	}: transfer_keep_alive(RawOrigin::Signed(caller.clone()), recipient_lookup, transfer_amount)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+		assert_eq!(Balances::<T>::locks(&caller).len() as u32, l);
+	}
+
+	// Benchmark `reap_account` on an account left below a raised existential deposit.
+	reap_account {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller = whitelisted_caller();
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&user, existential_deposit);
+		let new_existential_deposit = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		Balances::<T>::set_existential_deposit(T::SetEDOrigin::successful_origin(), new_existential_deposit)?;
+	}: _(RawOrigin::Signed(caller), user_lookup)
+	verify {
+		assert_eq!(Balances::<T>::total_balance(&user), Zero::zero());
+	}
+
+	// Benchmark `compact_reserves` purging `z` zero named reserves next to a non-zero one.
+	compact_reserves {
+		let z in 0 .. T::MaxReserves::get().saturating_sub(1);
+
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		<Balances<T> as ReservableCurrency<_>>::reserve(&caller, existential_deposit)?;
+
+		let mut reserves = (0 ..= z).map(|i| ReserveData {
+			id: T::ReserveIdentifier::decode(&mut TrailingZeroInput::new(&i.encode()))
+				.expect("an identifier decodes from any input padded with zeros; qed"),
+			amount: if i < z { Zero::zero() } else { existential_deposit },
+		}).collect::<Vec<_>>();
+		reserves.sort_by_key(|data| data.id);
+		Reserves::<T>::insert(&caller, reserves);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Balances::<T>::reserves(&caller).len(), 1);
+	}
+
+	freeze {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+		let origin = T::FreezeOrigin::successful_origin();
+	}: {
+		Balances::<T>::freeze(origin, user_lookup)?;
+	}
+	verify {
+		assert!(Balances::<T>::is_frozen(&user));
+	}
+
+	thaw {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+		FrozenAccounts::<T>::insert(&user, true);
+		let origin = T::FreezeOrigin::successful_origin();
+	}: {
+		Balances::<T>::thaw(origin, user_lookup)?;
+	}
+	verify {
+		assert!(!Balances::<T>::is_frozen(&user));
+	}
+
+	// Benchmark `sponsored_transfer` creating the recipient, without the signature check.
+	sponsored_transfer {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let sponsor: T::AccountId = account("sponsor", 0, SEED);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let refund = T::SponsoredFeeRefund::get();
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&sponsor, balance.saturating_add(refund));
+		<Balances<T> as ReservableCurrency<_>>::reserve(&sponsor, refund)?;
+		let transfer_amount = existential_deposit.saturating_mul((ED_MULTIPLIER - 1).into());
+	}: {
+		Balances::<T>::do_sponsored_transfer(&sponsor, &caller, &recipient, transfer_amount)?;
+	}
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+		assert_eq!(Balances::<T>::reserved_balance(&sponsor), Zero::zero());
+	}
+
+
+	force_set_lock {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+		let balance = T::ExistentialDeposit::get().saturating_mul(1000u32.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&user, balance);
+		let origin = T::ForceLockOrigin::successful_origin();
+	}: {
+		Balances::<T>::force_set_lock(origin, user_lookup, [0u8; 8], balance, WithdrawReasons::all())?;
+	}
+	verify {
+		assert_eq!(Balances::<T>::locks(&user).len(), 1);
+	}
+
+	force_remove_lock {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+		let balance = T::ExistentialDeposit::get().saturating_mul(1000u32.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&user, balance);
+		<Balances<T> as LockableCurrency<_>>::set_lock([0u8; 8], &user, balance, WithdrawReasons::all());
+		let origin = T::ForceLockOrigin::successful_origin();
+	}: {
+		Balances::<T>::force_remove_lock(origin, user_lookup, [0u8; 8])?;
+	}
+	verify {
+		assert!(Balances::<T>::locks(&user).is_empty());
+	}
+
+
+	// Same as `transfer_best_case`, but a few blocks later so that `LastActive` changes for both
+	// accounts, to compare against it.
+	transfer_tracking_activity {
+		let caller = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, T::Balance::max_value());
+		let existential_deposit = T::ExistentialDeposit::get();
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&recipient, existential_deposit);
+		let transfer_amount = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let now = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		frame_system::Pallet::<T>::set_block_number(now);
+	}: transfer(RawOrigin::Signed(caller.clone()), recipient_lookup, transfer_amount)
+	verify {
+		assert_eq!(Balances::<T>::last_active(&caller), Some(now));
+		assert_eq!(Balances::<T>::last_active(&recipient), Some(now));
+	}
+
+
+	// Benchmark `repatriate_reserved` in the worst case, moving into the reserved balance of the
+	// beneficiary everything `slashed` has, which reaps it.
+	repatriate_reserved {
+		let slashed: T::AccountId = account("slashed", 0, SEED);
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&slashed, balance);
+		<Balances<T> as ReservableCurrency<_>>::reserve(&slashed, balance)?;
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&beneficiary, existential_deposit);
+	}: {
+		<Balances<T> as ReservableCurrency<_>>::repatriate_reserved(&slashed, &beneficiary, balance, Status::Reserved)?;
+	}
+	verify {
+		assert!(Balances::<T>::total_balance(&slashed).is_zero());
+		assert_eq!(Balances::<T>::reserved_balance(&beneficiary), balance);
+	}
+
+	// Benchmark `repatriate_reserved` into the free balance of the beneficiary.
+	repatriate_reserved_free {
+		let slashed: T::AccountId = account("slashed", 0, SEED);
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&slashed, balance);
+		<Balances<T> as ReservableCurrency<_>>::reserve(&slashed, balance)?;
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&beneficiary, existential_deposit);
+	}: {
+		<Balances<T> as ReservableCurrency<_>>::repatriate_reserved(&slashed, &beneficiary, balance, Status::Free)?;
+	}
+	verify {
+		assert!(Balances::<T>::total_balance(&slashed).is_zero());
+		assert_eq!(Balances::<T>::free_balance(&beneficiary), existential_deposit + balance);
+	}
+
+
+	// Benchmark `adjust_balance` coming from ROOT account, raising both balances.
+	adjust_balance {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance_amount = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&user, balance_amount);
+	}: _(RawOrigin::Root, user_lookup, 1_000, 1_000)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&user), balance_amount + 1_000u32.into());
+		assert_eq!(Balances::<T>::reserved_balance(&user), 1_000u32.into());
+	}
+
+
+	// Benchmark `transfer_claimable` where the recipient is created.
+	transfer_claimable {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let transfer_amount = existential_deposit.saturating_mul((ED_MULTIPLIER - 1).into());
+	}: _(RawOrigin::Signed(caller.clone()), recipient_lookup, transfer_amount)
+	verify {
+		assert_eq!(Balances::<T>::reserved_balance(&recipient), transfer_amount);
+		assert_eq!(Balances::<T>::claimable(&recipient), transfer_amount);
+	}
+
+	claim {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let transfer_amount = existential_deposit.saturating_mul((ED_MULTIPLIER - 1).into());
+		Balances::<T>::transfer_claimable(RawOrigin::Signed(caller).into(), recipient_lookup, transfer_amount)?;
+	}: _(RawOrigin::Signed(recipient.clone()))
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+		assert!(Balances::<T>::claimable(&recipient).is_zero());
+	}
+
+
+	// Benchmark `force_reserve` reserving all but the existential deposit of an account.
+	force_reserve {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&user, balance);
+		let amount = balance - existential_deposit;
+	}: _(RawOrigin::Root, user_lookup, amount)
+	verify {
+		assert_eq!(Balances::<T>::reserved_balance(&user), amount);
+	}
+
+
+	// Benchmark `transfer_to_subaccount` creating the sub-account of the longest seed.
+	transfer_to_subaccount {
+		let s in 0 .. T::MaxSubAccountSeedLength::get();
+
+		let caller = whitelisted_caller();
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, T::Balance::max_value());
+		let existential_deposit = T::ExistentialDeposit::get();
+		let transfer_amount = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let sub = vec![1u8; s as usize];
+		let recipient = Balances::<T>::sub_account(*b"py/bench", &sub);
+	}: _(RawOrigin::Signed(caller.clone()), *b"py/bench", sub, transfer_amount)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+	}
+
+
+	pause_transfers {
+		let origin = T::EmergencyOrigin::successful_origin();
+	}: {
+		Balances::<T>::pause_transfers(origin)?;
+	}
+	verify {
+		assert!(Balances::<T>::transfers_paused());
+	}
+
+	resume_transfers {
+		TransfersPaused::<T>::put(true);
+		let origin = T::EmergencyOrigin::successful_origin();
+	}: {
+		Balances::<T>::resume_transfers(origin)?;
+	}
+	verify {
+		assert!(!Balances::<T>::transfers_paused());
+	}
+
+
+	// Benchmark `mint` creating the recipient.
+	mint {
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+		let existential_deposit = T::ExistentialDeposit::get();
+		let amount = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let origin = T::MintOrigin::successful_origin();
+	}: {
+		Balances::<T>::mint(origin, recipient_lookup, amount)?;
+	}
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), amount);
+	}
+
+
+	// Benchmark `vested_transfer` creating the recipient.
+	vested_transfer {
+		let caller = whitelisted_caller();
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+		let transfer_amount = existential_deposit.saturating_mul((ED_MULTIPLIER - 1).into()) + 1u32.into();
+		let schedule = VestingSchedule {
+			locked: transfer_amount,
+			per_block: One::one(),
+			starting_block: 10u32.into(),
+			cliff: 10u32.into(),
+		};
+	}: _(RawOrigin::Signed(caller), recipient_lookup, schedule)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+		assert_eq!(Balances::<T>::vesting(&recipient), Some(schedule));
+	}
+
+	// Benchmark `claim_vested` shrinking the lock without removing it.
+	claim_vested {
+		let caller = whitelisted_caller();
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let schedule = VestingSchedule {
+			locked: balance,
+			per_block: One::one(),
+			starting_block: Zero::zero(),
+			cliff: Zero::zero(),
+		};
+		<Balances<T> as LockableCurrency<_>>::set_lock(VESTING_ID, &caller, balance, WithdrawReasons::all());
+		Vesting::<T>::insert(&caller, schedule);
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Balances::<T>::locks(&caller)[0].amount, balance - One::one());
+	}
+
+
+	// Benchmark `on_initialize` in a block with no expiring locks and no dust to burn.
+	on_initialize_idle {
+		let now = block_without_burn::<T>();
+	}: {
+		Balances::<T>::on_initialize(now);
+	}
+
+	// Benchmark `on_initialize` in a block where `a` accounts each have `MaxLocks` expiring locks.
+	on_initialize_expiring {
+		let a in 1 .. 100;
+
+		let now = block_without_burn::<T>();
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let mut users = Vec::new();
+		for u in 0 .. a {
+			let user: T::AccountId = account("user", u, SEED);
+			let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&user, balance);
+			for i in 0 .. T::MaxLocks::get() {
+				let mut id: LockIdentifier = [0u8; 8];
+				id[..4].copy_from_slice(&i.to_le_bytes());
+				Balances::<T>::try_set_lock_until(id, &user, balance, WithdrawReasons::all(), now)?;
+			}
+			users.push(user);
+		}
+		frame_system::Pallet::<T>::set_block_number(now);
+	}: {
+		Balances::<T>::on_initialize(now);
+	}
+	verify {
+		assert!(users.iter().all(|user| Balances::<T>::locks(user).is_empty()));
+	}
+
+	// Benchmark the dust burn of `on_initialize` at a `BurnInterval` boundary.
+	on_initialize_burn {
+		let burn_account = T::BurnAccount::get();
+		let existential_deposit = T::ExistentialDeposit::get();
+		let dust = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&burn_account, dust);
+		PendingDust::<T>::put(dust);
+	}: {
+		Balances::<T>::burn_pending_dust();
+	}
+	verify {
+		assert!(Balances::<T>::pending_dust().is_zero());
+	}
+
+
+	// Benchmark `release_expired_reserves` where all `r` named reserves of the account expired.
+	release_expired_reserves {
+		let r in 1 .. T::MaxReserves::get();
+
+		let caller = whitelisted_caller();
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&user, balance.saturating_mul(r.into()));
+
+		let until = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		for i in 0 .. r {
+			let id = T::ReserveIdentifier::decode(&mut TrailingZeroInput::new(&i.encode()))
+				.expect("an identifier decodes from any input padded with zeros; qed");
+			Balances::<T>::reserve_with_expiry(&id, &user, balance, until)?;
+		}
+		frame_system::Pallet::<T>::set_block_number(until);
+	}: _(RawOrigin::Signed(caller), user_lookup)
+	verify {
+		assert!(Balances::<T>::reserves(&user).is_empty());
+		assert!(Balances::<T>::reserve_expiries(&user).is_empty());
+	}
+
+
+	// Benchmark `transfer` creating the recipient and paying the `AccountCreationFee` for it.
+	transfer_paying_creation_fee {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let fee = T::AccountCreationFee::get();
+		let caller = whitelisted_caller();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into()).saturating_add(fee);
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+		let transfer_amount = existential_deposit.saturating_mul((ED_MULTIPLIER - 1).into()) + 1u32.into();
+	}: transfer(RawOrigin::Signed(caller.clone()), recipient_lookup, transfer_amount)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+	}
+
+
+	// Benchmark `force_transfer_batch` where each of `b` moves comes from its own source account
+	// and creates its destination.
+	force_transfer_batch {
+		let b in 1 .. T::MaxBatchSize::get();
+
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into())
+			.saturating_add(T::AccountCreationFee::get());
+		let transfer_amount = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let mut moves = Vec::new();
+		for i in 0 .. b {
+			let source: T::AccountId = account("source", i, SEED);
+			let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&source, balance);
+			let recipient: T::AccountId = account("recipient", i, SEED);
+			moves.push((T::Lookup::unlookup(source), T::Lookup::unlookup(recipient), transfer_amount));
+		}
+	}: _(RawOrigin::Root, moves)
+	verify {
+		let recipient: T::AccountId = account("recipient", b - 1, SEED);
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+	}
+
+
+
+	// Benchmark `reserve` followed by reading the reserved balance, for comparison with
+	// `reserve_returning`.
+	reserve_then_read {
+		let caller: T::AccountId = whitelisted_caller();
+		let balance = T::ExistentialDeposit::get().saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let value = T::ExistentialDeposit::get();
+	}: {
+		<Balances<T> as ReservableCurrency<_>>::reserve(&caller, value)?;
+		let _reserved = <Balances<T> as ReservableCurrency<_>>::reserved_balance(&caller);
+	}
+	verify {
+		assert_eq!(Balances::<T>::reserved_balance(&caller), value);
+	}
+
+
+	// Benchmark `reserve_returning`, which saves the read of `reserve_then_read`.
+	reserve_returning {
+		let caller: T::AccountId = whitelisted_caller();
+		let balance = T::ExistentialDeposit::get().saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let value = T::ExistentialDeposit::get();
+	}: {
+		let _reserved = Balances::<T>::reserve_returning(&caller, value)?;
+	}
+	verify {
+		assert_eq!(Balances::<T>::reserved_balance(&caller), value);
+	}
+
+
+
+	// Benchmark `set_inflation_per_block`.
+	set_inflation_per_block {
+		let origin = T::InflationOrigin::successful_origin();
+		let amount = T::ExistentialDeposit::get().saturating_mul(ED_MULTIPLIER.into());
+	}: {
+		Balances::<T>::set_inflation_per_block(origin, amount)?;
+	}
+	verify {
+		assert_eq!(Balances::<T>::inflation_per_block(), amount);
+	}
+
+
+	// Benchmark the inflation mint of `on_initialize` creating `InflationAccount`.
+	on_initialize_inflation {
+		let pot = T::InflationAccount::get();
+		let amount = T::ExistentialDeposit::get().saturating_mul(ED_MULTIPLIER.into());
+		InflationPerBlock::<T>::put(amount);
+	}: {
+		Balances::<T>::mint_inflation();
+	}
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&pot), amount);
+	}
+
+
+
+	// Benchmark `transfer_valid_until` included at its last valid block, creating the recipient
+	// and paying the `AccountCreationFee` for it.
+	transfer_valid_until {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let fee = T::AccountCreationFee::get();
+		let caller = whitelisted_caller();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into()).saturating_add(fee);
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+		let transfer_amount = existential_deposit.saturating_mul((ED_MULTIPLIER - 1).into()) + 1u32.into();
+		let valid_until = frame_system::Pallet::<T>::block_number();
+	}: _(RawOrigin::Signed(caller.clone()), recipient_lookup, transfer_amount, valid_until)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&recipient), transfer_amount);
+	}
+
+	// Benchmark `emit_balance_checkpoint` of another account, paying the `CheckpointFee`.
+	emit_balance_checkpoint {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let fee = T::CheckpointFee::get();
+		let caller = whitelisted_caller();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into()).saturating_add(fee);
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+
+		let who: T::AccountId = account("who", 0, SEED);
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&who, balance);
+		let who_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(who.clone());
+	}: _(RawOrigin::Signed(caller.clone()), who_lookup)
+	verify {
+		assert_eq!(Balances::<T>::free_balance(&caller), balance - fee);
+	}
+
+	// Benchmark `force_adjust_total_issuance` raising the total issuance.
+	force_adjust_total_issuance {
+		let issuance = Balances::<T>::total_issuance();
+		let delta = T::ExistentialDeposit::get();
+	}: _(RawOrigin::Root, AdjustmentDirection::Increase, delta)
+	verify {
+		assert_eq!(Balances::<T>::total_issuance(), issuance + delta);
+	}
+
+	set_account_tag {
+		let t in 0 .. T::MaxTagLength::get();
+
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+		let origin = T::TagOrigin::successful_origin();
+		let tag = vec![0u8; t as usize];
+	}: {
+		Balances::<T>::set_account_tag(origin, user_lookup, tag.clone())?;
+	}
+	verify {
+		assert_eq!(Balances::<T>::account_tag(&user), Some(tag));
+	}
+
+	clear_account_tag {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+		let origin = T::TagOrigin::successful_origin();
+		AccountTags::<T>::insert(&user, vec![0u8; T::MaxTagLength::get() as usize]);
+	}: {
+		Balances::<T>::clear_account_tag(origin, user_lookup)?;
+	}
+	verify {
+		assert_eq!(Balances::<T>::account_tag(&user), None);
+	}
+
+	// Benchmark `transfer_fraction` of the whole balance, killing the sender and creating the
+	// recipient.
+	transfer_fraction {
+		let caller = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+
+		let existential_deposit = T::ExistentialDeposit::get();
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T> as Currency<_>>::make_free_balance_be(&caller, balance);
+	}: _(RawOrigin::Signed(caller.clone()), recipient_lookup, Perbill::one(), false)
+	verify {
+		assert!(Balances::<T>::free_balance(&caller).is_zero());
+		assert_eq!(Balances::<T>::free_balance(&recipient), balance);
+	}
 }
 
 impl_benchmark_test_suite!(
@@ -60,8 +60,16 @@
 //! fungible assets system.
 //! - [`ReservableCurrency`](frame_support::traits::ReservableCurrency):
 //! Functions for dealing with assets that can be reserved from an account.
+//! - [`NamedReservableCurrency`](primitives::traits::NamedReservableCurrency):
+//! Functions for dealing with reserves that are tagged with an identifier.
 //! - [`LockableCurrency`](frame_support::traits::LockableCurrency): Functions for
 //! dealing with accounts that allow liquidity restrictions.
+//! - [`fungible::Inspect`](primitives::traits::fungible::Inspect) and
+//! [`fungible::Mutate`](primitives::traits::fungible::Mutate): The `fungible` view of the
+//! same balances, for pallets written against the newer token traits.
+//! - [`fungible::InspectHold`](primitives::traits::fungible::InspectHold) and
+//! [`fungible::MutateHold`](primitives::traits::fungible::MutateHold): Holds for a reason, backed
+//! by the named reserves.
 //! - [`Imbalance`](frame_support::traits::Imbalance): Functions for handling
 //! imbalances between total issuance in the system and account balances. Must be used when a function
 //! creates new funds (e.g. a reward) or destroys some funds (e.g. a system fee).
@@ -72,6 +80,64 @@
 //!
 //! - `transfer` - Transfer some liquid free balance to another account.
 //! - `set_balance` - Set the balances of a given account. The origin of this call must be root.
+//! - `adjust_balance` - Add to or subtract from the balances of a given account. The origin of
+//!   this call must be root.
+//! - `set_balance_batch` - Set the balances of several accounts. The origin of this call must be root.
+//! - `force_adjust_total_issuance` - Correct a total issuance which drifted from the balances. The
+//!   origin of this call must be root.
+//! - `transfer_all` - Transfer the whole transferable balance to another account.
+//! - `transfer_fraction` - Transfer a fraction of the transferable balance, as of inclusion, to
+//!   another account.
+//! - `transfer_batch` - Transfer some liquid free balance to several accounts at once.
+//! - `force_transfer_batch` - Make several transfers between any accounts, each failing on its
+//!   own. The origin of this call must be root.
+//! - `transfer_with_memo` - Transfer some liquid free balance along with an opaque memo.
+//! - `force_unreserve` - Release some reserved balance of an account. The origin of this call must be root.
+//! - `burn` - Destroy some of the caller's free balance.
+//! - `prune_expired_locks` - Remove the expired locks of an account.
+//! - `release_expired_reserves` - Unreserve the expired named reserves of an account.
+//! - `set_existential_deposit` - Change the existential deposit. The origin of this call must be
+//!   `SetEDOrigin`.
+//! - `transfer_if_exists` - Transfer some liquid free balance to an account, only if it already exists.
+//! - `merge_accounts` - Move the balances and locks of another account controlled by the caller into its own.
+//! - `reserved_transfer` - Transfer some liquid free balance into the reserved balance of another account.
+//! - `set_fee_from_reserved` - Allow or forbid the caller's fees to be paid from its reserved balance.
+//! - `transfer_bounded` - Transfer some liquid free balance, keeping at least a given free balance.
+//! - `transfer_valid_until` - Transfer some liquid free balance, unless included after a given
+//!   block.
+//! - `reap_account` - Reap an account left below the existential deposit, free of charge.
+//! - `compact_reserves` - Remove the caller's named reserves which are down to zero.
+//! - `freeze` - Stop an account from moving any of its balance. The origin of this call must be
+//!   `FreezeOrigin`.
+//! - `thaw` - Undo `freeze`. The origin of this call must be `FreezeOrigin`.
+//! - `sponsored_transfer` - Transfer some liquid free balance, with a fixed fee refund from a sponsor.
+//! - `transfer_asset` - Transfer some of an asset, of which only the native currency is supported
+//!   so far.
+//! - `force_set_lock` - Set a lock on an account, bypassing the pallet owning it. The origin of this
+//!   call must be `ForceLockOrigin`.
+//! - `force_remove_lock` - Remove a lock from an account, bypassing the pallet owning it. The origin
+//!   of this call must be `ForceLockOrigin`.
+//! - `transfer_claimable` - Transfer some liquid free balance, which the recipient must `claim`
+//!   before it can spend it.
+//! - `claim` - Make the balance transferred to the caller with `transfer_claimable` spendable.
+//! - `transfer_to_subaccount` - Transfer some liquid free balance to a sub-account of a pallet,
+//!   derived on chain.
+//! - `force_reserve` - Reserve some free balance of an account regardless of `ReserveQuota`. The
+//!   origin of this call must be root.
+//! - `pause_transfers` - Halt the transfers of all signed origins. The origin of this call must be
+//!   `EmergencyOrigin`.
+//! - `resume_transfers` - Undo `pause_transfers`. The origin of this call must be `EmergencyOrigin`.
+//! - `mint` - Create some balance for an account. The origin of this call must be `MintOrigin`.
+//! - `set_inflation_per_block` - Set the balance minted to `InflationAccount` every block. The
+//!   origin of this call must be `InflationOrigin`.
+//! - `vested_transfer` - Transfer some balance which is released to the recipient over time.
+//! - `claim_vested` - Unlock the part of a vested transfer which is released by now.
+//! - `emit_balance_checkpoint` - Record the balances of an account in an event, for off-chain
+//!   mirrors to reconcile against.
+//! - `set_account_tag` - Attach an opaque tag to an account, e.g. its KYC tier. The origin of this
+//!   call must be `TagOrigin`.
+//! - `clear_account_tag` - Remove the tag of an account. The origin of this call must be
+//!   `TagOrigin`.
 //!
 //! ## Usage
 //!
@@ -129,7 +195,8 @@
 //!
 //! ## Assumptions
 //!
-//! * Total issued balanced of all accounts should be less than `Config::Balance::max_value()`.
+//! * Total issued balanced of all accounts should be less than `Config::Balance::max_value()`,
+//!   and minting may not take it beyond `Config::MaxIssuance`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -142,24 +209,29 @@ mod benchmarking;
 pub mod weights;
 
 use sp_std::prelude::*;
-use sp_std::{cmp, result, mem, fmt::Debug, ops::BitOr};
-use codec::{Codec, Encode, Decode};
+use sp_std::{cmp, result, mem, fmt::Debug, ops::BitOr, marker::PhantomData, convert::TryFrom};
+use codec::{Codec, Encode, Decode, DecodeAll};
 use frame_support::{
-	ensure,
+	ensure, transactional, weights::Weight, storage::StoragePrefixedMap,
 	traits::{
-		Currency, OnUnbalanced, TryDrop, StoredMap,
-		WithdrawReasons, LockIdentifier, LockableCurrency, ExistenceRequirement,
+		Currency, OnUnbalanced, TryDrop, StoredMap, Contains, OnNewAccount, OnKilledAccount, EnsureOrigin,
+		HandleLifetime, WithdrawReasons, LockIdentifier, LockableCurrency, ExistenceRequirement,
 		Imbalance, SignedImbalance, ReservableCurrency, Get, ExistenceRequirement::KeepAlive,
-		ExistenceRequirement::AllowDeath, BalanceStatus as Status,
+		ExistenceRequirement::AllowDeath, BalanceStatus as Status, FindAuthor,
 	}
 };
 #[cfg(feature = "std")]
 use frame_support::traits::GenesisBuild;
 use sp_runtime::{
-	RuntimeDebug, DispatchResult, DispatchError,
+	RuntimeDebug, DispatchResult, DispatchError, Perbill, PerThing, ModuleId,
 	traits::{
-		Zero, AtLeast32BitUnsigned, StaticLookup, CheckedAdd, CheckedSub,
-		MaybeSerializeDeserialize, Saturating, Bounded, StoredMapError,
+		Zero, One, AtLeast32BitUnsigned, StaticLookup, CheckedAdd, CheckedSub,
+		MaybeSerializeDeserialize, Saturating, Bounded, StoredMapError, Verify, IdentifyAccount,
+		SignedExtension, DispatchInfoOf, SaturatedConversion, AccountIdConversion,
+	},
+	transaction_validity::{
+		TransactionValidity, TransactionValidityError, ValidTransaction, InvalidTransaction,
+		TransactionPriority,
 	},
 };
 use frame_system as system;
@@ -169,7 +241,11 @@ pub use weights::WeightInfo;
 pub use pallet::*;
 
 // Calcu primitives
-use primitives::traits::UsableCurrency;
+use primitives::EraIndex;
+use primitives::traits::{
+	UsableCurrency, NamedReservableCurrency, ReservedFirstSlash, OnEraBoundary, OnReservedChanged,
+	fungible::{self, DepositConsequence, WithdrawConsequence},
+};
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -184,24 +260,205 @@ pub mod pallet {
 		MaybeSerializeDeserialize + Debug;
 
 		/// Handler for the unbalanced reduction when removing a dust account.
+		///
+		/// Total issuance only drops if the handler lets the imbalance go; a handler that
+		/// resolves it into another account (e.g. a treasury) leaves issuance untouched.
 		type DustRemoval: OnUnbalanced<NegativeImbalance<Self, I>>;
 
+		/// Accounts which are never reaped, even when their total balance drops below the
+		/// existential deposit. Their sub-ED balance is kept instead of being swept as dust.
+		type DustRemovalWhitelist: Contains<Self::AccountId>;
+
+		/// The account collecting dust swept by [`SweepDust`], or by [`RouteDust`] set to sweep,
+		/// when that is the `DustRemoval`.
+		type BurnAccount: Get<Self::AccountId>;
+
+		/// The number of blocks between two burns of the dust collected in `BurnAccount`.
+		/// Zero disables the burn.
+		#[pallet::constant]
+		type BurnInterval: Get<Self::BlockNumber>;
+
+		/// Decides whether a signed transfer of some value by an account, e.g. by `transfer`,
+		/// `transfer_all` or each leg of `transfer_batch`, may go ahead. `force_transfer` is not
+		/// subject to it.
+		type TransferFilter: Contains<(Self::AccountId, Self::Balance)>;
+
+		/// Decides whether an account may transfer to another with the signed transfers,
+		/// given as `(from, to)`, e.g. to allow or deny certain recipients. Root's `force_transfer`
+		/// and `force_transfer_batch` are not subject to it.
+		type DestinationFilter: Contains<(Self::AccountId, Self::AccountId)>;
+
+		/// Accounts which only exist to pay fees, e.g. oracle submitters. They are held to
+		/// `FeeOnlyMinimum` rather than the existential deposit.
+		type FeeOnlyAccounts: Contains<Self::AccountId>;
+
+		/// The reduced existential deposit of the `FeeOnlyAccounts`. It never raises theirs above
+		/// the existential deposit.
+		#[pallet::constant]
+		type FeeOnlyMinimum: Get<Self::Balance>;
+
+		/// The assets `transfer_asset` accepts.
+		type SupportedAssets: Contains<AssetId>;
+
+		/// The smallest value a signed transfer, e.g. by `transfer`, `transfer_all` or each leg of
+		/// `transfer_batch`, may send. `force_transfer` is exempt. Zero allows any value.
+		#[pallet::constant]
+		type MinTransfer: Get<Self::Balance>;
+
+		/// The number of eras `IssuanceHistory` keeps the total issuance for.
+		#[pallet::constant]
+		type IssuanceHistoryDepth: Get<u32>;
+
+		/// The total issuance which minting may not go beyond, e.g. [`UncappedIssuance`] to only
+		/// stop it from overflowing. It holds every mint, i.e. `issue`, `deposit_creating`,
+		/// `deposit_into_existing`, `mint_into`, `mint` and the inflation, while resolving an
+		/// existing imbalance with `resolve_creating` or `resolve_into_existing` is never refused.
+		#[pallet::constant]
+		type MaxIssuance: Get<Self::Balance>;
+
+		/// Accounts holding balance which doesn't circulate, e.g. the treasury, and which is
+		/// therefore left out of `circulating_supply`.
+		type SystemAccounts: Get<Vec<Self::AccountId>>;
+
+		/// The signature by which the account merged away by `merge_accounts` consents to it.
+		type OffchainSignature: Verify<Signer = Self::SigningPublicKey> + Parameter;
+
+		/// The public key an `OffchainSignature` is checked against.
+		type SigningPublicKey: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// The locks `merge_accounts` may move to the merged account. Any other lock, e.g. one
+		/// tracked per account by its owning pallet, prevents the merge.
+		type MergeableLocks: Contains<LockIdentifier>;
+
+		/// The fixed amount the sponsor of a `sponsored_transfer` pays back to the sender from its
+		/// reserved balance, towards the transaction fee. It isn't the fee actually charged, which
+		/// depends on e.g. the length of the call and the fee multiplier, so it should be set to
+		/// cover a typical one.
+		#[pallet::constant]
+		type SponsoredFeeRefund: Get<Self::Balance>;
+
+		/// The fee charged to the sender of a transfer which creates its recipient, by any signed
+		/// call or `force_transfer`, on top of the value transferred. Zero disables it.
+		#[pallet::constant]
+		type AccountCreationFee: Get<Self::Balance>;
+
+		/// Handler for the `AccountCreationFee`s charged, e.g. the treasury.
+		type CreationFeeDestination: OnUnbalanced<NegativeImbalance<Self, I>>;
+
+		/// The fee charged to the caller of `emit_balance_checkpoint`, on top of the transaction
+		/// fee, so that checkpoints can't be spammed cheaply. Zero disables it.
+		#[pallet::constant]
+		type CheckpointFee: Get<Self::Balance>;
+
+		/// Handler for the `CheckpointFee`s charged, e.g. the treasury.
+		type CheckpointFeeDestination: OnUnbalanced<NegativeImbalance<Self, I>>;
+
+		/// The part of the free balance which a lock freezing both fees and other withdrawals may
+		/// not take, so that the account can still pay for a transaction. Zero disables it.
+		#[pallet::constant]
+		type FeeReserve: Get<Self::Balance>;
+
+		/// Handler for the tips of `ChargeTransactionTip` which are paid to the block author.
+		type TipToAuthor: OnUnbalanced<NegativeImbalance<Self, I>>;
+
+		/// How the part split off an imbalance by a ratio is rounded, e.g. by `FeeSplit`,
+		/// `SlashSplit` and `slash_pro_rata`.
+		type Rounding: Get<RoundingMode>;
+
+		/// How overlapping locks for the same reason combine into the frozen balance.
+		type LockAggregation: Get<LockAggregation>;
+
+		/// The part of its total balance an account may reserve. Only `reserve` and `reserve_named`
+		/// are bound by it, not root nor transfers into the reserved balance.
+		#[pallet::constant]
+		type ReserveQuota: Get<Perbill>;
+
 		/// The overarching event type.
 		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// The minimum amount required to keep an account open.
+		/// The minimum amount required to keep an account open at genesis. It can be changed
+		/// afterwards with `set_existential_deposit`.
 		#[pallet::constant]
 		type ExistentialDeposit: Get<Self::Balance>;
 
+		/// The origin which may change the existential deposit.
+		type SetEDOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may freeze and thaw accounts.
+		type FreezeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may pause and resume the transfers of all accounts.
+		type EmergencyOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may mint new balance with `mint`.
+		type MintOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may change the `InflationPerBlock` with `set_inflation_per_block`.
+		type InflationOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The account the `InflationPerBlock` is minted to, e.g. the treasury.
+		type InflationAccount: Get<Self::AccountId>;
+
+		/// The origin which may set and remove locks with `force_set_lock` and
+		/// `force_remove_lock`.
+		type ForceLockOrigin: EnsureOrigin<Self::Origin>;
+
 		/// The means of storing the balances of an account.
 		type AccountStore: StoredMap<Self::AccountId, AccountData<Self::Balance>>;
 
+		/// Handler for when an account holding a balance is created.
+		type OnNewAccount: OnNewAccount<Self::AccountId>;
+
+		/// Handler for when an account holding a balance is reaped.
+		type OnKilledAccount: OnKilledAccount<Self::AccountId>;
+
+		/// Handler for when the reserved balance of an account changes, e.g. by a slash.
+		type OnReservedChanged: OnReservedChanged<Self::AccountId, Self::Balance>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
-		/// The maximum number of locks that should exist on an account.
-		/// Not strictly enforced, but used for weight estimation.
+		/// The maximum number of locks that can exist on an account.
+		#[pallet::constant]
 		type MaxLocks: Get<u32>;
+
+		/// The maximum number of entries in a single `transfer_batch` or `set_balance_batch` call.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+
+		/// The maximum number of named reserves that can exist on an account.
+		#[pallet::constant]
+		type MaxReserves: Get<u32>;
+
+		/// The id type for named reserves.
+		type ReserveIdentifier: Parameter + Member + Ord + Copy;
+
+		/// The maximum length in bytes of the memo attached by `transfer_with_memo`.
+		#[pallet::constant]
+		type MaxMemoLength: Get<u32>;
+
+		/// The maximum length in bytes of the seed of the sub-account `transfer_to_subaccount`
+		/// sends to.
+		#[pallet::constant]
+		type MaxSubAccountSeedLength: Get<u32>;
+
+		/// The origin which may set and clear the tags of accounts.
+		type TagOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum length in bytes of the tag of an account.
+		#[pallet::constant]
+		type MaxTagLength: Get<u32>;
+
+		/// The weight each block reserves in `on_initialize` for pruning stale locks in
+		/// `on_finalize`. It should stay well within the normal dispatch class' share of the
+		/// block. Zero disables the pruning.
+		#[pallet::constant]
+		type LockPruneWeight: Get<Weight>;
+
+		/// The named reserve which holds the balance transferred with `transfer_claimable` until
+		/// its recipient claims it.
+		#[pallet::constant]
+		type ClaimableReserveId: Get<Self::ReserveIdentifier>;
 	}
 
 	#[pallet::pallet]
@@ -210,6 +467,73 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			// Most blocks have no expiring locks, so they are not charged for removing the entry.
+			let mut weight = if LockExpiries::<T, I>::contains_key(now) {
+				let expiring = LockExpiries::<T, I>::take(now);
+				let count = expiring.len() as u32;
+				for who in expiring {
+					Self::do_prune_expired_locks(&who, &now);
+				}
+				T::WeightInfo::on_initialize_expiring(count)
+			} else {
+				T::WeightInfo::on_initialize_idle()
+			};
+
+			let interval = T::BurnInterval::get();
+			if !interval.is_zero() && (now % interval).is_zero() {
+				Self::burn_pending_dust();
+				weight = weight.saturating_add(T::WeightInfo::on_initialize_burn());
+			}
+			// reserved for `on_finalize`, which can't add to the block weight itself.
+			weight.saturating_add(Self::mint_inflation()).saturating_add(T::LockPruneWeight::get())
+		}
+
+		fn on_finalize(now: BlockNumberFor<T>) {
+			// Substitute for an `on_idle` hook: spend the weight reserved in `on_initialize` on
+			// pruning locks.
+			Self::on_idle(now, T::LockPruneWeight::get());
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
+			if StorageVersion::<T, I>::get() == Releases::V2_0_0 {
+				StorageVersion::<T, I>::put(Releases::V3_0_0);
+				weight = weight.saturating_add(migrations::migrate_to_named_reserves::<T, I>());
+			}
+			let version = StorageVersion::<T, I>::get();
+			if version == Releases::V3_0_0 || version == Releases::V4_0_0 {
+				StorageVersion::<T, I>::put(Releases::V5_0_0);
+				// Locks must be in their current layout before they can be truncated.
+				weight = weight.saturating_add(migrations::migrate_to_expiring_locks::<T, I>());
+				if version == Releases::V3_0_0 {
+					weight = weight.saturating_add(migrations::truncate_excess_locks::<T, I>());
+				}
+			}
+			if StorageVersion::<T, I>::get() == Releases::V5_0_0 {
+				StorageVersion::<T, I>::put(Releases::V6_0_0);
+				weight = weight.saturating_add(migrations::migrate_single_locks::<T, I>());
+			}
+			if StorageVersion::<T, I>::get() == Releases::V6_0_0 {
+				StorageVersion::<T, I>::put(Releases::V7_0_0);
+				weight = weight.saturating_add(migrations::initialize_last_active::<T, I>());
+			}
+			if StorageVersion::<T, I>::get() == Releases::V7_0_0 {
+				StorageVersion::<T, I>::put(Releases::V8_0_0);
+				weight = weight.saturating_add(migrations::dedup_locks::<T, I>());
+			}
+			weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migrations::pre_upgrade::<T, I>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::post_upgrade::<T, I>()
+		}
 	}
 
 	#[pallet::call]
@@ -240,17 +564,33 @@ pub mod pallet {
 		/// - Base Weight: 73.64 µs, worst case scenario (account created, account removed)
 		/// - DB Weight: 1 Read and 1 Write to destination account
 		/// - Origin account is already in memory, so no DB operations for them.
+		/// - Refunded down to `transfer_best_case` when no account is created or removed, and to
+		///   `transfer` when no `AccountCreationFee` is charged.
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::transfer())]
+		#[pallet::weight(T::WeightInfo::transfer_paying_creation_fee())]
+		#[transactional]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
 			#[pallet::compact] value: T::Balance,
 		) -> DispatchResultWithPostInfo {
 			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			Self::ensure_transfer_allowed(&transactor, value)?;
+			Self::ensure_min_transfer(value)?;
 			let dest = T::Lookup::lookup(dest)?;
-			<Self as Currency<_>>::transfer(&transactor, &dest, value, ExistenceRequirement::AllowDeath)?;
-			Ok(().into())
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			let dest_existed = !Self::account(&dest).total().is_zero();
+			let fee_charged =
+				Self::transfer_charging_creation_fee(&transactor, &dest, value, AllowDeath)?;
+			let transactor_survived = !Self::account(&transactor).total().is_zero();
+			if dest_existed && transactor_survived {
+				Ok(Some(T::WeightInfo::transfer_best_case()).into())
+			} else if !fee_charged {
+				Ok(Some(T::WeightInfo::transfer()).into())
+			} else {
+				Ok(().into())
+			}
 		}
 
 		/// Set the balances of a given account.
@@ -260,6 +600,9 @@ pub mod pallet {
 		/// If the new free or reserved balance is below the existential deposit,
 		/// it will reset the account nonce (`frame_system::AccountNonce`).
 		///
+		/// Wiping out an account which still has locks, or consumers while this pallet is its only
+		/// provider, fails with `CannotReap`. Either way `ReapFailed` says what kept it alive.
+		///
 		/// The dispatch origin for this call is `root`.
 		///
 		/// # <weight>
@@ -283,31 +626,129 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
 			let who = T::Lookup::lookup(who)?;
-			let existential_deposit = T::ExistentialDeposit::get();
+			Self::do_set_balance(who, new_free, new_reserved)?;
+			Ok(().into())
+		}
 
-			let wipeout = new_free + new_reserved < existential_deposit;
-			let new_free = if wipeout { Zero::zero() } else { new_free };
-			let new_reserved = if wipeout { Zero::zero() } else { new_reserved };
+		/// Add `free_delta` to the free balance of `who` and `reserved_delta` to its reserved
+		/// balance, either of which may be negative, adjusting the total issuance by the net change.
+		///
+		/// The resulting balances are then set as in [`set_balance`], emitting `BalanceSet` with
+		/// them. Taking either balance below zero fails with `AdjustmentUnderflow`.
+		///
+		/// The dispatch origin for this call is `root`.
+		///
+		/// [`set_balance`]: struct.Pallet.html#method.set_balance
+		/// # <weight>
+		/// - Same as `set_balance`.
+		/// - DB Weight: 1 Read, 1 Write to `who`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::adjust_balance())]
+		pub fn adjust_balance(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			free_delta: i128,
+			reserved_delta: i128,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let account = Self::account(&who);
+			let new_free = Self::apply_delta(account.free, free_delta)?;
+			let new_reserved = Self::apply_delta(account.reserved, reserved_delta)?;
+			Self::do_set_balance(who, new_free, new_reserved)?;
+			Ok(().into())
+		}
 
-			let (free, reserved) = Self::mutate_account(&who, |account| {
-				if new_free > account.free {
-					mem::drop(PositiveImbalance::<T, I>::new(new_free - account.free));
-				} else if new_free < account.free {
-					mem::drop(NegativeImbalance::<T, I>::new(account.free - new_free));
-				}
+		/// Raise or lower `TotalIssuance` by `delta` without touching any balance, to repair a
+		/// total issuance which has drifted from the sum of the balances.
+		///
+		/// This is meant for governance-approved reconciliation only: it emits `Issued` or
+		/// `Rescinded` like any other change of the total issuance, plus `TotalIssuanceForced`,
+		/// and logs a warning, so that a repair can't go unnoticed and mask a bug still at work.
+		/// Lowering it below zero fails with `AdjustmentUnderflow`.
+		///
+		/// The dispatch origin for this call is `root`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Read, 1 Write to `TotalIssuance`
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::force_adjust_total_issuance())]
+		pub fn force_adjust_total_issuance(
+			origin: OriginFor<T>,
+			direction: AdjustmentDirection,
+			#[pallet::compact] delta: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let old = TotalIssuance::<T, I>::get();
+			let new = match direction {
+				AdjustmentDirection::Increase => old.checked_add(&delta).ok_or(Error::<T, I>::Overflow)?,
+				AdjustmentDirection::Decrease =>
+					old.checked_sub(&delta).ok_or(Error::<T, I>::AdjustmentUnderflow)?,
+			};
+			frame_support::debug::warn!(
+				"Warning: The total issuance was forced from {:?} to {:?}.",
+				old,
+				new,
+			);
+			Self::set_total_issuance(new);
+			Self::deposit_event(Event::TotalIssuanceForced(old, new));
+			Ok(().into())
+		}
 
-				if new_reserved > account.reserved {
-					mem::drop(PositiveImbalance::<T, I>::new(new_reserved - account.reserved));
-				} else if new_reserved < account.reserved {
-					mem::drop(NegativeImbalance::<T, I>::new(account.reserved - new_reserved));
+		/// Set the free and reserved balances of many accounts at once.
+		///
+		/// Each entry is applied as in [`set_balance`], emitting its own `BalanceSet` event, and
+		/// `TotalIssuance` is adjusted by the net delta across all entries. The call is atomic:
+		/// if any entry would overflow the total issuance then the whole batch is reverted.
+		///
+		/// The dispatch origin for this call is `root`.
+		///
+		/// [`set_balance`]: struct.Pallet.html#method.set_balance
+		/// # <weight>
+		/// - O(E) where E is the number of entries, bounded by `MaxBatchSize`.
+		/// - DB Weight: 1 Read and 1 Write to `TotalIssuance` and to each account.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::set_balance_batch(entries.len() as u32))]
+		#[transactional]
+		pub fn set_balance_batch(
+			origin: OriginFor<T>,
+			entries: Vec<(<T::Lookup as StaticLookup>::Source, T::Balance, T::Balance)>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(entries.len() as u32 <= T::MaxBatchSize::get(), Error::<T, I>::TooManyRecipients);
+			let mut issuance = TotalIssuance::<T, I>::get();
+
+			for (who, new_free, new_reserved) in entries {
+				let who = T::Lookup::lookup(who)?;
+				let wipeout = new_free.saturating_add(new_reserved) < Self::existential_deposit_for(&who);
+				let new_free = if wipeout { Zero::zero() } else { new_free };
+				let new_reserved = if wipeout { Zero::zero() } else { new_reserved };
+				if wipeout {
+					Self::ensure_can_reap(&who)?;
 				}
 
-				account.free = new_free;
-				account.reserved = new_reserved;
+				let (free, reserved) = Self::try_mutate_account(&who, |account, _| -> Result<_, DispatchError> {
+					let old = account.total();
+					let new = new_free.checked_add(&new_reserved).ok_or(Error::<T, I>::Overflow)?;
+					issuance = if new > old {
+						issuance.checked_add(&(new - old)).ok_or(Error::<T, I>::Overflow)?
+					} else {
+						Self::defensive_saturating_sub(issuance, old - new)
+					};
+
+					account.free = new_free;
+					account.reserved = new_reserved;
+
+					Ok((account.free, account.reserved))
+				})?;
+				Self::deposit_event(Event::BalanceSet(who.clone(), free, reserved));
+				if wipeout {
+					Self::note_unreaped(who);
+				}
+			}
 
-				(account.free, account.reserved)
-			})?;
-			Self::deposit_event(Event::BalanceSet(who, free, reserved));
+			Self::set_total_issuance(issuance);
 			Ok(().into())
 		}
 
@@ -316,8 +757,12 @@ pub mod pallet {
 		/// # <weight>
 		/// - Same as transfer, but additional read and write because the source account is
 		///   not assumed to be in the overlay.
+		/// - Refunded down to `force_transfer` when no `AccountCreationFee` is charged.
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::force_transfer())]
+		#[pallet::weight(
+			T::WeightInfo::force_transfer().max(T::WeightInfo::transfer_paying_creation_fee())
+		)]
+		#[transactional]
 		pub fn force_transfer(
 			origin: OriginFor<T>,
 			source: <T::Lookup as StaticLookup>::Source,
@@ -327,7 +772,56 @@ pub mod pallet {
 			ensure_root(origin)?;
 			let source = T::Lookup::lookup(source)?;
 			let dest = T::Lookup::lookup(dest)?;
-			<Self as Currency<_>>::transfer(&source, &dest, value, ExistenceRequirement::AllowDeath)?;
+			let fee_charged = Self::charge_creation_fee(&source, &dest, value, true)?;
+			// unlike a signed transfer, this may move funds off a frozen account.
+			Self::do_transfer(&source, &dest, value, ExistenceRequirement::AllowDeath)?;
+			if fee_charged {
+				Ok(().into())
+			} else {
+				Ok(Some(T::WeightInfo::force_transfer()).into())
+			}
+		}
+
+		/// Execute each of `moves` as a `force_transfer` from its source to its destination, e.g.
+		/// to restore the balances of a snapshot.
+		///
+		/// Unlike `transfer_batch`, a failing move is reverted on its own and the others still go
+		/// ahead. The `ForceTransferBatchCompleted` event tells which moves failed and why.
+		///
+		/// The dispatch origin for this call must be `root`.
+		///
+		/// # <weight>
+		/// - O(B) where B is the number of moves, bounded by `MaxBatchSize`.
+		/// - DB Weight: 2 Reads and 2 Writes to the source and destination of each move.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::force_transfer_batch(moves.len() as u32))]
+		pub fn force_transfer_batch(
+			origin: OriginFor<T>,
+			moves: Vec<(
+				<T::Lookup as StaticLookup>::Source,
+				<T::Lookup as StaticLookup>::Source,
+				T::Balance,
+			)>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(moves.len() as u32 <= T::MaxBatchSize::get(), Error::<T, I>::TooManyRecipients);
+			let mut successes = 0u32;
+			let mut failures = Vec::new();
+			for (index, (source, dest, value)) in moves.into_iter().enumerate() {
+				let result = frame_support::storage::with_transaction(|| {
+					let result = Self::do_force_transfer_move(source, dest, value);
+					if result.is_ok() {
+						frame_support::storage::TransactionOutcome::Commit(result)
+					} else {
+						frame_support::storage::TransactionOutcome::Rollback(result)
+					}
+				});
+				match result {
+					Ok(()) => successes += 1,
+					Err(e) => failures.push((index as u32, e)),
+				}
+			}
+			Self::deposit_event(Event::ForceTransferBatchCompleted(successes, failures));
 			Ok(().into())
 		}
 
@@ -342,408 +836,4239 @@ pub mod pallet {
 		/// - Base Weight: 51.4 µs
 		/// - DB Weight: 1 Read and 1 Write to dest (sender is in overlay already)
 		/// #</weight>
-		#[pallet::weight(T::WeightInfo::transfer_keep_alive())]
+		#[pallet::weight(
+			T::WeightInfo::transfer_keep_alive()
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
 		pub fn transfer_keep_alive(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
 			#[pallet::compact] value: T::Balance,
 		) -> DispatchResultWithPostInfo {
 			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			Self::ensure_transfer_allowed(&transactor, value)?;
+			Self::ensure_min_transfer(value)?;
 			let dest = T::Lookup::lookup(dest)?;
-			<Self as Currency<_>>::transfer(&transactor, &dest, value, KeepAlive)?;
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			Self::transfer_charging_creation_fee(&transactor, &dest, value, KeepAlive)?;
 			Ok(().into())
 		}
-	}
-
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	#[pallet::metadata(T::AccountId = "AccountId", T::Balance = "Balance")]
-	pub enum Event<T: Config<I>, I: 'static = ()> {
-		/// An account was created with some free balance. \[account, free_balance\]
-		Endowed(T::AccountId, T::Balance),
-		/// An account was removed whose balance was non-zero but below ExistentialDeposit,
-		/// resulting in an outright loss. \[account, balance\]
-		DustLost(T::AccountId, T::Balance),
-		/// Transfer succeeded. \[from, to, value\]
-		Transfer(T::AccountId, T::AccountId, T::Balance),
-		/// A balance was set by root. \[who, free, reserved\]
-		BalanceSet(T::AccountId, T::Balance, T::Balance),
-		/// Some amount was deposited (e.g. for transaction fees). \[who, deposit\]
-		Deposit(T::AccountId, T::Balance),
-		/// Some balance was reserved (moved from free to reserved). \[who, value\]
-		Reserved(T::AccountId, T::Balance),
-		/// Some balance was unreserved (moved from reserved to free). \[who, value\]
-		Unreserved(T::AccountId, T::Balance),
-		/// Some balance was moved from the reserve of the first account to the second account.
-		/// Final argument indicates the destination balance type.
-		/// \[from, to, balance, destination_status\]
-		ReserveRepatriated(T::AccountId, T::AccountId, T::Balance, Status),
-	}
 
-	/// Old name generated by `decl_event`.
-	#[deprecated(note = "use `Event` instead")]
-	pub type RawEvent<T, I = ()> = Event<T, I>;
+		/// Transfer the entire transferable balance from the caller account.
+		///
+		/// NOTE: This function only attempts to transfer _transferable_ balances. This means that
+		/// any locked, reserved, or existential deposits (when `keep_alive` is `true`), will not be
+		/// transferred by this function. To ensure that this function results in a killed account,
+		/// you might need to prepare the account by removing any reference counters, storage
+		/// deposits, etc...
+		///
+		/// The dispatch origin of this call must be Signed.
+		///
+		/// - `dest`: The recipient of the transfer.
+		/// - `keep_alive`: A boolean to determine if the `transfer_all` operation should send all
+		///   of the funds the account has, causing the sender account to be killed (false), or
+		///   transfer everything except at least the existential deposit, which will guarantee to
+		///   keep the sender account alive (true).
+		///
+		/// The `AccountCreationFee` is set aside from the transferable balance if the transfer
+		/// creates `dest`. Nothing is transferred, and no event emitted, if there is no
+		/// transferable balance.
+		/// # <weight>
+		/// - O(1). Just like transfer, but reading the user's transferable balance first.
+		/// - Refunded down to that read if there is nothing to transfer.
+		/// #</weight>
+		#[pallet::weight(
+			T::WeightInfo::transfer_all()
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
+		pub fn transfer_all(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			keep_alive: bool,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			let reducible_balance = Self::reducible_balance(&transactor, keep_alive)
+				.saturating_sub(Self::creation_fee(&transactor, &dest));
+			if reducible_balance.is_zero() {
+				return Ok(Some(T::DbWeight::get().reads(1)).into())
+			}
+			Self::ensure_transfer_allowed(&transactor, reducible_balance)?;
+			Self::ensure_min_transfer(reducible_balance)?;
+			let keep_alive = if keep_alive { KeepAlive } else { AllowDeath };
+			Self::transfer_charging_creation_fee(&transactor, &dest, reducible_balance, keep_alive)?;
+			Ok(().into())
+		}
 
-	#[pallet::error]
-	pub enum Error<T, I = ()> {
-		/// Vesting balance too high to send value
-		VestingBalance,
-		/// Account liquidity restrictions prevent withdrawal
-		LiquidityRestrictions,
-		/// Got an overflow after adding
-		Overflow,
-		/// Balance too low to send value
-		InsufficientBalance,
-		/// Value too low to create account due to existential deposit
-		ExistentialDeposit,
-		/// Transfer/payment would kill account
-		KeepAlive,
-		/// A vesting schedule already exists for this account
-		ExistingVestingSchedule,
-		/// Beneficiary account must pre-exist
-		DeadAccount,
-	}
+		/// Transfer `fraction` of the transferable balance of the caller, as it is when the call
+		/// is executed, e.g. for a "send half" button which doesn't race against the balance
+		/// changing before inclusion. The amount is rounded down, and emitted in
+		/// `FractionTransferred`.
+		///
+		/// The transferable balance is the one `transfer_all` would move with the same
+		/// `keep_alive`, so less the `AccountCreationFee` if the transfer creates `dest`. Nothing
+		/// is transferred, and no event emitted, if the amount is zero.
+		///
+		/// The dispatch origin of this call must be Signed.
+		///
+		/// # <weight>
+		/// - Same as `transfer_all`.
+		/// - Refunded down to reading the transferable balance if there is nothing to transfer.
+		/// #</weight>
+		#[pallet::weight(
+			T::WeightInfo::transfer_fraction()
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
+		pub fn transfer_fraction(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			fraction: Perbill,
+			keep_alive: bool,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			let transferable = Self::reducible_balance(&transactor, keep_alive)
+				.saturating_sub(Self::creation_fee(&transactor, &dest));
+			let value = fraction.mul_floor(transferable);
+			if value.is_zero() {
+				return Ok(Some(T::DbWeight::get().reads(1)).into())
+			}
+			Self::ensure_transfer_allowed(&transactor, value)?;
+			Self::ensure_min_transfer(value)?;
+			let liveness = if keep_alive { KeepAlive } else { AllowDeath };
+			Self::transfer_charging_creation_fee(&transactor, &dest, value, liveness)?;
+			Self::deposit_event(Event::FractionTransferred(transactor, dest, fraction, value));
+			Ok(().into())
+		}
 
-	/// The total units issued in the system.
-	#[pallet::storage]
-	#[pallet::getter(fn total_issuance)]
-	pub type TotalIssuance<T: Config<I>, I: 'static = ()> = StorageValue<_, T::Balance, ValueQuery>;
+		/// Transfer some liquid free balance to each of the given recipients.
+		///
+		/// Every leg is executed as a normal `transfer`, emitting its own `Transfer` event. The
+		/// call is atomic: if any single leg fails (insufficient balance, below existential
+		/// deposit, overflow, ...) then the whole batch is reverted.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// # <weight>
+		/// - O(B) where B is the number of recipients, bounded by `MaxBatchSize`.
+		/// - DB Weight: 1 Read and 1 Write to each destination account.
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::transfer_batch(recipients.len() as u32)
+				.saturating_add(
+					Pallet::<T, I>::creation_fee_weight().saturating_mul(recipients.len() as Weight)
+				)
+		)]
+		#[transactional]
+		pub fn transfer_batch(
+			origin: OriginFor<T>,
+			recipients: Vec<(<T::Lookup as StaticLookup>::Source, T::Balance)>,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			ensure!(recipients.len() as u32 <= T::MaxBatchSize::get(), Error::<T, I>::TooManyRecipients);
+			for (dest, value) in recipients {
+				Self::ensure_transfer_allowed(&transactor, value)?;
+				Self::ensure_min_transfer(value)?;
+				let dest = T::Lookup::lookup(dest)?;
+				Self::ensure_destination_allowed(&transactor, &dest)?;
+				Self::transfer_charging_creation_fee(&transactor, &dest, value, AllowDeath)?;
+			}
+			Ok(().into())
+		}
 
-	/// The balance of an account.
-	///
-	/// NOTE: This is only used in the case that this pallet is used to store balances.
-	#[pallet::storage]
-	pub type Account<T: Config<I>, I: 'static = ()> = StorageMap<
-		_,
-		Blake2_128Concat,
-		T::AccountId,
-		AccountData<T::Balance>,
-		ValueQuery
-	>;
+		/// Same as the [`transfer_keep_alive`] call, but with an opaque `memo` which is only
+		/// surfaced in the `TransferWithMemo` event and never stored on chain.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// [`transfer_keep_alive`]: struct.Pallet.html#method.transfer_keep_alive
+		/// # <weight>
+		/// - O(M) where M is the length of the memo, bounded by `MaxMemoLength`.
+		/// - DB Weight: 1 Read and 1 Write to dest (sender is in overlay already)
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::transfer_with_memo(memo.len() as u32)
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
+		pub fn transfer_with_memo(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] value: T::Balance,
+			memo: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			ensure!(memo.len() as u32 <= T::MaxMemoLength::get(), Error::<T, I>::MemoTooLong);
+			Self::ensure_transfer_allowed(&transactor, value)?;
+			Self::ensure_min_transfer(value)?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			Self::transfer_charging_creation_fee(&transactor, &dest, value, KeepAlive)?;
+			Self::deposit_event(Event::TransferWithMemo(transactor, dest, value, memo));
+			Ok(().into())
+		}
+
+		/// Same as the [`transfer`] call, but to the sub-account `sub` of the pallet whose
+		/// `ModuleId` is `pallet_id`, as `ModuleId::into_sub_account` derives it.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// [`transfer`]: struct.Pallet.html#method.transfer
+		/// # <weight>
+		/// - O(S) where S is the length of the seed, bounded by `MaxSubAccountSeedLength`.
+		/// - DB Weight: 1 Read and 1 Write to dest (sender is in overlay already)
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::transfer_to_subaccount(sub.len() as u32)
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
+		pub fn transfer_to_subaccount(
+			origin: OriginFor<T>,
+			pallet_id: [u8; 8],
+			sub: Vec<u8>,
+			#[pallet::compact] value: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			ensure!(
+				sub.len() as u32 <= T::MaxSubAccountSeedLength::get(),
+				Error::<T, I>::SubAccountSeedTooLong,
+			);
+			Self::ensure_transfer_allowed(&transactor, value)?;
+			Self::ensure_min_transfer(value)?;
+			let dest = Self::sub_account(pallet_id, &sub);
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			Self::transfer_charging_creation_fee(&transactor, &dest, value, AllowDeath)?;
+			Self::deposit_event(Event::SubAccountTransfer(transactor, dest, value));
+			Ok(().into())
+		}
+
+		/// Unreserve up to `amount` of the reserved balance of `who`, moving it back into their
+		/// free balance. Requesting more than is reserved unreserves everything, regardless of the
+		/// `ReserveFloor` of `who`.
+		///
+		/// The dispatch origin for this call is `root`. The `Unreserved` event carries the amount
+		/// actually unreserved.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Read and 1 Write to `who`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::force_unreserve())]
+		pub fn force_unreserve(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			// root isn't held to the `ReserveFloor` of `who`.
+			let _leftover = Self::do_unreserve(&who, amount, Zero::zero());
+			Ok(().into())
+		}
+
+		/// Destroy `amount` of the caller's free balance, reducing the total issuance by the same
+		/// amount.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// - `keep_alive`: If `true`, the call fails with `KeepAlive` rather than reap the caller.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Read and 1 Write to the caller (in the overlay already) and total issuance.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub fn burn(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: T::Balance,
+			keep_alive: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			let liveness = if keep_alive { KeepAlive } else { AllowDeath };
+			// dropping the imbalance is what reduces the total issuance.
+			drop(<Self as Currency<_>>::withdraw(&who, amount, WithdrawReasons::TRANSFER, liveness)?);
+			Self::deposit_event(Event::Burned(who, amount));
+			Ok(().into())
+		}
+
+		/// Set the existential deposit to `new`.
+		///
+		/// Accounts left below the new existential deposit are not reaped right away, which would
+		/// mean scanning every account, but only on their next mutation or by `reap_account`.
+		///
+		/// The dispatch origin for this call must be `SetEDOrigin`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Read and 1 Write to `ExistentialDeposit`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::set_existential_deposit())]
+		pub fn set_existential_deposit(
+			origin: OriginFor<T>,
+			#[pallet::compact] new: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			T::SetEDOrigin::ensure_origin(origin)?;
+			let old = ExistentialDeposit::<T, I>::get();
+			ExistentialDeposit::<T, I>::put(new);
+			Self::deposit_event(Event::ExistentialDepositChanged(old, new));
+			Ok(().into())
+		}
+
+		/// Remove the expired locks of `who`, releasing the balance they froze.
+		///
+		/// Expired locks are otherwise only pruned when the locks of `who` are next changed, or
+		/// at the initialization of the block they expire at.
+		///
+		/// The dispatch origin for this call must be `Signed`, by anyone.
+		///
+		/// # <weight>
+		/// - O(L) where L is the number of locks of `who`, bounded by `MaxLocks`.
+		/// - DB Weight: 1 Read and 1 Write to the locks and the account of `who`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::prune_expired_locks(T::MaxLocks::get()))]
+		pub fn prune_expired_locks(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::do_prune_expired_locks(&who, &system::Pallet::<T>::block_number());
+			Ok(().into())
+		}
+
+		/// Same as the [`transfer`] call, but only if `dest` already exists, i.e. has a non-zero
+		/// total balance. This never creates `dest`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// [`transfer`]: struct.Pallet.html#method.transfer
+		/// # <weight>
+		/// - Same as `transfer`, without the cost of creating `dest`.
+		/// - DB Weight: 1 Read and 1 Write to dest (sender is in overlay already)
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::transfer_if_exists())]
+		pub fn transfer_if_exists(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] value: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			Self::ensure_transfer_allowed(&transactor, value)?;
+			Self::ensure_min_transfer(value)?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			ensure!(!Self::total_balance(&dest).is_zero(), Error::<T, I>::RecipientDoesNotExist);
+			<Self as Currency<_>>::transfer(&transactor, &dest, value, ExistenceRequirement::AllowDeath)?;
+			Ok(().into())
+		}
+
+		/// Merge the account `other` into the caller's: its free and reserved balances and its
+		/// `MergeableLocks` are moved over, and `other` is reaped.
+		///
+		/// `other_signature` must be the signature by `other` of `merge_accounts_payload`.
+		///
+		/// The merge fails with `MergeNotPermitted` if `other` has named reserves, a lock which
+		/// isn't mergeable, or any other consumer (e.g. a staking bond) left once its locks are
+		/// moved, as these are tracked by other pallets against `other` itself.
+		///
+		/// The dispatch origin for this call must be `Signed` by the account merged into.
+		///
+		/// # <weight>
+		/// - O(L) where L is the number of locks of `other`, bounded by `MaxLocks`.
+		/// - DB Weight: 5 Reads and 5 Writes to the accounts and locks of both accounts and to
+		///   the `MergeNonce` of `other`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::merge_accounts())]
+		#[transactional]
+		pub fn merge_accounts(
+			origin: OriginFor<T>,
+			other: <T::Lookup as StaticLookup>::Source,
+			other_signature: T::OffchainSignature,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			let other = T::Lookup::lookup(other)?;
+			ensure!(who != other, Error::<T, I>::MergeNotPermitted);
+			let payload = Self::merge_accounts_payload(&who, &other);
+			ensure!(other_signature.verify(&payload[..], &other), Error::<T, I>::InvalidMergeSignature);
+			Self::do_merge_accounts(&who, &other)?;
+			Self::deposit_event(Event::AccountsMerged(other, who));
+			Ok(().into())
+		}
+
+		/// Transfer some liquid free balance of the caller straight into the reserved balance of
+		/// `dest`, e.g. to escrow it there until a settlement unreserves it.
+		///
+		/// `dest` is created if needed, in which case `value` must be at least the existential
+		/// deposit.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// # <weight>
+		/// - Same as `transfer`.
+		/// - DB Weight: 1 Read and 1 Write to dest (sender is in overlay already)
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::reserved_transfer()
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
+		#[transactional]
+		pub fn reserved_transfer(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] value: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			Self::ensure_transfer_allowed(&transactor, value)?;
+			Self::ensure_min_transfer(value)?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			Self::charge_creation_fee(&transactor, &dest, value, false)?;
+			Self::do_reserved_transfer(&transactor, &dest, value)?;
+			Self::deposit_event(Event::ReservedTransfer(transactor, dest, value));
+			Ok(().into())
+		}
+
+		/// Allow or forbid transaction fees of the caller to be paid from its reserved balance
+		/// when its usable free balance falls short.
+		///
+		/// Only reserved balance which isn't held in a named reserve can pay fees, so reserves
+		/// backing e.g. market obligations are always kept for them.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		///
+		/// # <weight>
+		/// - DB Weight: 1 Write to the flag of the caller.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::set_fee_from_reserved())]
+		pub fn set_fee_from_reserved(origin: OriginFor<T>, allow: bool) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			if allow {
+				FeeFromReserved::<T, I>::insert(&who, true);
+			} else {
+				FeeFromReserved::<T, I>::remove(&who);
+			}
+			Self::deposit_event(Event::FeeFromReservedSet(who, allow));
+			Ok(().into())
+		}
+
+		/// Same as the [`transfer_keep_alive`] call, but with a check that the free balance of the
+		/// origin account will be at least `min_remaining` afterwards, e.g. the minimum bond of a
+		/// nominator.
+		///
+		/// [`transfer_keep_alive`]: struct.Pallet.html#method.transfer_keep_alive
+		/// # <weight>
+		/// - Same as `transfer_keep_alive`.
+		/// - DB Weight: 1 Read and 1 Write to dest (sender is in overlay already)
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::transfer_bounded()
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
+		pub fn transfer_bounded(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] value: T::Balance,
+			#[pallet::compact] min_remaining: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			Self::ensure_transfer_allowed(&transactor, value)?;
+			Self::ensure_min_transfer(value)?;
+			let dest = T::Lookup::lookup(dest)?;
+			let spent = value.saturating_add(Self::creation_fee(&transactor, &dest));
+			let remaining = Self::free_balance(&transactor).checked_sub(&spent);
+			ensure!(
+				remaining.map_or(false, |r| r >= min_remaining),
+				Error::<T, I>::WouldGoBelowMinimum,
+			);
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			Self::transfer_charging_creation_fee(&transactor, &dest, value, KeepAlive)?;
+			Ok(().into())
+		}
+
+		/// Same as the [`transfer`] call, but failing with `TransferExpired` if it is included
+		/// after block `valid_until`, however long the transaction itself stays valid.
+		///
+		/// [`transfer`]: struct.Pallet.html#method.transfer
+		/// # <weight>
+		/// - Same as `transfer`, plus reading the block number.
+		/// - DB Weight: 1 Read and 1 Write to dest (sender is in overlay already)
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::transfer_valid_until()
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
+		pub fn transfer_valid_until(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] value: T::Balance,
+			valid_until: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				system::Pallet::<T>::block_number() <= valid_until,
+				Error::<T, I>::TransferExpired,
+			);
+			Self::transfer(origin, dest, value)
+		}
+
+		/// Reap an account whose total balance is below the existential deposit, e.g. because the
+		/// existential deposit was raised, sweeping its balance to `DustRemoval` as dust.
+		///
+		/// Fails with `AccountNotReapable` unless `who` exists with a balance below the
+		/// existential deposit, isn't in `DustRemovalWhitelist`, has no locks or named reserves,
+		/// and no other pallet provides for or depends on it. The caller pays no fee if it succeeds.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		///
+		/// # <weight>
+		/// - Independent of the arguments.
+		/// - DB Weight: 4 Reads (account, locks, reserves, existential deposit), 2 Writes (account,
+		///   total issuance)
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::reap_account())]
+		pub fn reap_account(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			let who = T::Lookup::lookup(who)?;
+			Self::do_reap_account(&who)?;
+			Ok(Pays::No.into())
+		}
+
+		/// Remove the caller's named reserves which are down to zero, e.g. after being slashed, so
+		/// they no longer count towards `MaxReserves`.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		///
+		/// # <weight>
+		/// - O(R) where R is the number of named reserves, bounded by `MaxReserves`.
+		/// - DB Weight: 1 Read and 1 Write to the reserves of the caller.
+		/// - Refunded down to the number of entries actually removed.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::compact_reserves(T::MaxReserves::get()))]
+		pub fn compact_reserves(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let purged = Self::do_compact_reserves(&who);
+			Ok(Some(T::WeightInfo::compact_reserves(purged)).into())
+		}
+
+		/// Unreserve the named reserves of `who` placed by `reserve_with_expiry` whose expiry has
+		/// passed, e.g. the collateral of an order which was never settled.
+		///
+		/// Expired reserves are otherwise only released when the named reserves of `who` are next
+		/// changed.
+		///
+		/// The dispatch origin for this call must be `Signed`, by anyone.
+		///
+		/// # <weight>
+		/// - O(R) where R is the number of named reserves, bounded by `MaxReserves`.
+		/// - DB Weight: 3 Reads and 3 Writes to the reserve expiries, the reserves and the account
+		///   of `who`.
+		/// - Refunded down to the number of reserves actually released.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::release_expired_reserves(T::MaxReserves::get()))]
+		pub fn release_expired_reserves(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let released = Self::do_release_expired_reserves(&who, &system::Pallet::<T>::block_number());
+			Ok(Some(T::WeightInfo::release_expired_reserves(released)).into())
+		}
+
+		/// Freeze `who`, so that it may no longer withdraw (transaction fees included), transfer
+		/// or reserve any balance until it is thawed. Its locks are left alone, and it may still
+		/// receive transfers and be moved from by `force_transfer`.
+		///
+		/// The dispatch origin for this call must be `FreezeOrigin`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Write to `FrozenAccounts`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::freeze())]
+		pub fn freeze(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			FrozenAccounts::<T, I>::insert(&who, true);
+			Self::deposit_event(Event::AccountFrozen(who));
+			Ok(().into())
+		}
+
+		/// Thaw `who`, undoing `freeze`.
+		///
+		/// The dispatch origin for this call must be `FreezeOrigin`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Write to `FrozenAccounts`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::thaw())]
+		pub fn thaw(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			FrozenAccounts::<T, I>::remove(&who);
+			Self::deposit_event(Event::AccountThawed(who));
+			Ok(().into())
+		}
+
+		/// Same as the [`transfer`] call, except that `sponsor` pays the sender the fixed
+		/// `SponsoredFeeRefund` towards its fee, from the anonymous part of its reserved balance.
+		///
+		/// `sponsor_signature` must be the signature by `sponsor` of `sponsored_transfer_payload`.
+		/// The whole call reverts with `SponsorInsufficient` if `sponsor` can't cover the refund.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// [`transfer`]: struct.Pallet.html#method.transfer
+		/// # <weight>
+		/// - Same as `transfer`, plus repatriating the refund from `sponsor`.
+		/// - DB Weight: 3 Reads and 3 Writes to the accounts of `sponsor` and dest and the
+		///   reserves of `sponsor`.
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::sponsored_transfer()
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
+		#[transactional]
+		pub fn sponsored_transfer(
+			origin: OriginFor<T>,
+			sponsor: <T::Lookup as StaticLookup>::Source,
+			sponsor_signature: T::OffchainSignature,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] value: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			let sponsor = T::Lookup::lookup(sponsor)?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			let payload = Self::sponsored_transfer_payload(&transactor, &dest, value);
+			ensure!(sponsor_signature.verify(&payload[..], &sponsor), Error::<T, I>::InvalidSponsorSignature);
+			Self::ensure_transfer_allowed(&transactor, value)?;
+			Self::ensure_min_transfer(value)?;
+			Self::do_sponsored_transfer(&sponsor, &transactor, &dest, value)?;
+			Ok(().into())
+		}
+
+		/// Transfer some `value` of the asset `asset_id` to another account.
+		///
+		/// `AssetId::Native` is transferred the same way as the [`transfer`] call. Any other asset,
+		/// or one which isn't in `SupportedAssets`, fails with `UnsupportedAsset` for now.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// [`transfer`]: struct.Pallet.html#method.transfer
+		/// # <weight>
+		/// - Same as `transfer`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::transfer_paying_creation_fee())]
+		pub fn transfer_asset(
+			origin: OriginFor<T>,
+			asset_id: AssetId,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] value: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			ensure!(T::SupportedAssets::contains(&asset_id), Error::<T, I>::UnsupportedAsset);
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			match asset_id {
+				AssetId::Native => {
+					Self::ensure_transfer_allowed(&transactor, value)?;
+					Self::ensure_min_transfer(value)?;
+					Self::transfer_charging_creation_fee(&transactor, &dest, value, AllowDeath)?;
+				},
+				AssetId::Token(_) => return Err(Error::<T, I>::UnsupportedAsset.into()),
+			}
+			Self::deposit_event(Event::AssetTransfer(asset_id, transactor, dest, value));
+			Ok(().into())
+		}
+
+		/// Set the lock `id` on `who` to `amount` for `reasons`, the same way as
+		/// `LockableCurrency::set_lock`.
+		///
+		/// This is an escape hatch for locks left behind by a faulty pallet. It bypasses the pallet
+		/// owning the lock, whose own accounting may then no longer match the lock, so it should
+		/// only be used once that pallet can no longer be relied upon to manage it.
+		///
+		/// The dispatch origin for this call must be `ForceLockOrigin`.
+		///
+		/// # <weight>
+		/// - O(L) where L is the number of locks of `who`, bounded by `MaxLocks`.
+		/// - DB Weight: 2 Reads and 2 Writes to the account and the locks of `who`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::force_set_lock())]
+		pub fn force_set_lock(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			id: LockIdentifier,
+			#[pallet::compact] amount: T::Balance,
+			reasons: WithdrawReasons,
+		) -> DispatchResultWithPostInfo {
+			T::ForceLockOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::try_set_lock(id, &who, amount, reasons)?;
+			Self::deposit_event(Event::LockForceSet(who, id, amount));
+			Ok(().into())
+		}
+
+		/// Remove the lock `id` from `who`, the same way as `LockableCurrency::remove_lock`.
+		///
+		/// Like `force_set_lock`, this bypasses the pallet owning the lock, which may then still
+		/// count on it being there.
+		///
+		/// The dispatch origin for this call must be `ForceLockOrigin`.
+		///
+		/// # <weight>
+		/// - O(L) where L is the number of locks of `who`, bounded by `MaxLocks`.
+		/// - DB Weight: 2 Reads and 2 Writes to the account and the locks of `who`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::force_remove_lock())]
+		pub fn force_remove_lock(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			id: LockIdentifier,
+		) -> DispatchResultWithPostInfo {
+			T::ForceLockOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			<Self as LockableCurrency<_>>::remove_lock(id, &who);
+			Self::deposit_event(Event::LockForceRemoved(who, id));
+			Ok(().into())
+		}
+
+		/// Transfer some liquid free balance into the reserved balance of `dest`, where it stays
+		/// until `dest` claims it with `claim`, proving it controls its key.
+		///
+		/// Follows the same rules as the [`transfer`] call, except that the recipient may not
+		/// spend the balance, fees included, before claiming it.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// [`transfer`]: struct.Pallet.html#method.transfer
+		/// # <weight>
+		/// - Same as `reserved_transfer`, plus the claimable balance of dest.
+		/// - DB Weight: 2 Reads and 2 Writes to dest and its claimable balance.
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::transfer_claimable()
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
+		#[transactional]
+		pub fn transfer_claimable(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] value: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			Self::ensure_transfer_allowed(&transactor, value)?;
+			Self::ensure_min_transfer(value)?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			ensure!(transactor != dest, Error::<T, I>::ClaimableToSelf);
+			Self::charge_creation_fee(&transactor, &dest, value, false)?;
+			let id = T::ClaimableReserveId::get();
+			Reserves::<T, I>::try_mutate(&dest, |reserves| -> DispatchResult {
+				match reserves.binary_search_by_key(&id, |data| data.id) {
+					Ok(index) => {
+						// this add can't overflow but just to be defensive.
+						reserves[index].amount = Self::defensive_saturating_add(reserves[index].amount, value);
+					},
+					Err(index) => {
+						ensure!((reserves.len() as u32) < T::MaxReserves::get(), Error::<T, I>::TooManyReserves);
+						reserves.insert(index, ReserveData { id, amount: value });
+					},
+				};
+				Self::do_reserved_transfer(&transactor, &dest, value)
+			})?;
+			Claimable::<T, I>::mutate(&dest, |claimable| *claimable = claimable.saturating_add(value));
+			Self::deposit_event(Event::TransferClaimable(transactor, dest, value));
+			Ok(().into())
+		}
+
+		/// Move all the balance transferred to the caller with `transfer_claimable` from its
+		/// `ClaimableReserveId` reserve into its free balance. Whatever can't be moved stays
+		/// claimable, as long as the reserve still holds it.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 2 Reads and 2 Writes to the account and the claimable balance of the caller.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::claim())]
+		pub fn claim(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			let claimable = Self::claimable(&who);
+			ensure!(!claimable.is_zero(), Error::<T, I>::NothingToClaim);
+			let id = T::ClaimableReserveId::get();
+			let claimed = claimable - Self::do_unreserve_named(&id, &who, claimable, Zero::zero());
+			let held = <Self as NamedReservableCurrency<_>>::reserved_balance_named(&id, &who);
+			let remaining = cmp::min(claimable - claimed, held);
+			if remaining.is_zero() {
+				Claimable::<T, I>::remove(&who);
+			} else {
+				Claimable::<T, I>::insert(&who, remaining);
+			}
+			Self::deposit_event(Event::Claimed(who, claimed));
+			Ok(().into())
+		}
+
+		/// Reserve `amount` of the free balance of `who`, even beyond `ReserveQuota`.
+		///
+		/// The dispatch origin for this call is `root`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Read and 1 Write to `who`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::force_reserve())]
+		pub fn force_reserve(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::do_reserve(&who, amount, false)?;
+			Ok(().into())
+		}
+
+		/// Halt every call by which a signed origin moves, reserves or destroys balance, e.g.
+		/// during an exploit. Queries and the calls of root keep working for recovery.
+		///
+		/// The dispatch origin for this call must be `EmergencyOrigin`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Write to `TransfersPaused`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::pause_transfers())]
+		pub fn pause_transfers(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::EmergencyOrigin::ensure_origin(origin)?;
+			TransfersPaused::<T, I>::put(true);
+			Self::deposit_event(Event::TransfersPaused);
+			Ok(().into())
+		}
+
+		/// Undo `pause_transfers`.
+		///
+		/// The dispatch origin for this call must be `EmergencyOrigin`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Write to `TransfersPaused`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::resume_transfers())]
+		pub fn resume_transfers(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::EmergencyOrigin::ensure_origin(origin)?;
+			TransfersPaused::<T, I>::kill();
+			Self::deposit_event(Event::TransfersResumed);
+			Ok(().into())
+		}
+
+		/// Mint `value` into the free balance of `dest`, increasing the total issuance by the same
+		/// amount, e.g. for a grant approved by governance.
+		///
+		/// `dest` is created if needed, in which case `value` must reach its existential deposit.
+		///
+		/// The dispatch origin for this call must be `MintOrigin`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 2 Reads and 2 Writes to `dest` and the total issuance.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::mint())]
+		pub fn mint(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] value: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			T::MintOrigin::ensure_origin(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			ensure!(
+				value >= Self::existential_deposit_for(&dest) || !Self::account(&dest).total().is_zero(),
+				Error::<T, I>::ExistentialDeposit,
+			);
+			ensure!(value <= Self::remaining_issuance_capacity(), Error::<T, I>::IssuanceOverflow);
+			// dropping the imbalance is what increases the total issuance.
+			let minted = <Self as Currency<_>>::deposit_creating(&dest, value);
+			ensure!(minted.peek() == value, Error::<T, I>::Overflow);
+			drop(minted);
+			Self::deposit_event(Event::Minted(dest, value));
+			Ok(().into())
+		}
+
+		/// Set the `InflationPerBlock` minted to `InflationAccount` to `amount`. Zero stops it.
+		///
+		/// The dispatch origin for this call must be `InflationOrigin`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Read and 1 Write to `InflationPerBlock`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::set_inflation_per_block())]
+		pub fn set_inflation_per_block(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			T::InflationOrigin::ensure_origin(origin)?;
+			let old = InflationPerBlock::<T, I>::get();
+			InflationPerBlock::<T, I>::put(amount);
+			Self::deposit_event(Event::InflationPerBlockChanged(old, amount));
+			Ok(().into())
+		}
+
+		/// Transfer `schedule.locked` to `dest` and lock it there under `VESTING_ID`. The lock is
+		/// kept whole until `schedule.cliff`, after which it falls by `schedule.per_block` for each
+		/// block since `schedule.starting_block` as `dest` calls `claim_vested`.
+		///
+		/// `dest` may not already have a vesting schedule, and the call reverts with `TooManyLocks`
+		/// if `dest` already has `MaxLocks` locks.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 3 Reads and 3 Writes to the sender, the account of `dest` and the locks
+		///   and vesting schedule of `dest`.
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::vested_transfer()
+				.saturating_add(Pallet::<T, I>::creation_fee_weight())
+		)]
+		#[transactional]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			schedule: VestingSchedule<T::Balance, T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let transactor = ensure_signed(origin)?;
+			Self::ensure_transfers_not_paused()?;
+			Self::ensure_transfer_allowed(&transactor, schedule.locked)?;
+			Self::ensure_min_transfer(schedule.locked)?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_destination_allowed(&transactor, &dest)?;
+			ensure!(!schedule.per_block.is_zero(), Error::<T, I>::InvalidVestingSchedule);
+			ensure!(!Vesting::<T, I>::contains_key(&dest), Error::<T, I>::ExistingVestingSchedule);
+			Self::transfer_charging_creation_fee(&transactor, &dest, schedule.locked, AllowDeath)?;
+			let locked = schedule.locked_at(system::Pallet::<T>::block_number());
+			Self::try_set_lock(VESTING_ID, &dest, locked, WithdrawReasons::all())?;
+			Vesting::<T, I>::insert(&dest, schedule);
+			Self::deposit_event(Event::VestedTransfer(transactor, dest, schedule.locked));
+			Ok(().into())
+		}
+
+		/// Shrink the vesting lock of the caller to what its schedule still locks, removing the
+		/// lock and the schedule once everything is vested.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 2 Reads and 2 Writes to the locks and vesting schedule of the caller.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::claim_vested())]
+		pub fn claim_vested(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let schedule = Vesting::<T, I>::get(&who).ok_or(Error::<T, I>::NotVesting)?;
+			let locked = schedule.locked_at(system::Pallet::<T>::block_number());
+			if locked.is_zero() {
+				<Self as LockableCurrency<_>>::remove_lock(VESTING_ID, &who);
+				Vesting::<T, I>::remove(&who);
+			} else {
+				Self::try_set_lock(VESTING_ID, &who, locked, WithdrawReasons::all())?;
+			}
+			Self::deposit_event(Event::VestingUpdated(who, locked));
+			Ok(().into())
+		}
+
+		/// Emit a `BalanceCheckpoint` with the current free, reserved and frozen balance of `who`,
+		/// giving off-chain mirrors of its balances a finalized reference to reconcile against.
+		///
+		/// The dispatch origin for this call must be `Signed`, and pays `CheckpointFee` to
+		/// `CheckpointFeeDestination`. The balances are read after the fee is paid.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 2 Reads and 1 Write to the caller, 1 Read of `who`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::emit_balance_checkpoint())]
+		pub fn emit_balance_checkpoint(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let caller = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let fee = T::CheckpointFee::get();
+			if !fee.is_zero() {
+				let fee = <Self as Currency<_>>::withdraw(&caller, fee, WithdrawReasons::FEE, KeepAlive)?;
+				T::CheckpointFeeDestination::on_unbalanced(fee);
+			}
+			let account = Self::account(&who);
+			Self::deposit_event(Event::BalanceCheckpoint(
+				who,
+				account.free,
+				account.reserved,
+				account.misc_frozen.max(account.fee_frozen),
+				system::Pallet::<T>::block_number(),
+			));
+			Ok(().into())
+		}
+
+		/// Tag `who` with `tag`, replacing its tag if it has one. The tag is opaque to this pallet
+		/// and enforces nothing, but other pallets and filters can read it, e.g. as a KYC tier.
+		///
+		/// The dispatch origin for this call must be `TagOrigin`.
+		///
+		/// # <weight>
+		/// - O(T) where T is the length of the tag, bounded by `MaxTagLength`.
+		/// - DB Weight: 1 Write to `AccountTags`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::set_account_tag(tag.len() as u32))]
+		pub fn set_account_tag(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			tag: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::TagOrigin::ensure_origin(origin)?;
+			ensure!(tag.len() as u32 <= T::MaxTagLength::get(), Error::<T, I>::TagTooLong);
+			let who = T::Lookup::lookup(who)?;
+			AccountTags::<T, I>::insert(&who, &tag);
+			Self::deposit_event(Event::AccountTagSet(who, tag));
+			Ok(().into())
+		}
+
+		/// Remove the tag of `who`, failing with `NotTagged` if it has none.
+		///
+		/// The dispatch origin for this call must be `TagOrigin`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - DB Weight: 1 Read and 1 Write to `AccountTags`.
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::clear_account_tag())]
+		pub fn clear_account_tag(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::TagOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(AccountTags::<T, I>::contains_key(&who), Error::<T, I>::NotTagged);
+			AccountTags::<T, I>::remove(&who);
+			Self::deposit_event(Event::AccountTagCleared(who));
+			Ok(().into())
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(
+		T::AccountId = "AccountId",
+		T::Balance = "Balance",
+		T::ReserveIdentifier = "ReserveIdentifier",
+		T::BlockNumber = "BlockNumber",
+	)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// An account was created with some free balance, funded by `by` if it was created by a
+		/// transfer. \[account, free_balance, by\]
+		Endowed(T::AccountId, T::Balance, Option<T::AccountId>),
+		/// An account was removed whose balance was non-zero but below ExistentialDeposit,
+		/// resulting in an outright loss. \[account, balance\]
+		DustLost(T::AccountId, T::Balance),
+		/// Transfer succeeded. It follows the `Endowed` of a created `to` and precedes any reaping of
+		/// `from`. \[from, to, value\]
+		Transfer(T::AccountId, T::AccountId, T::Balance),
+		/// A balance was set by root. \[who, free, reserved\]
+		BalanceSet(T::AccountId, T::Balance, T::Balance),
+		/// Some amount was deposited (e.g. for transaction fees). \[who, deposit\]
+		Deposit(T::AccountId, T::Balance),
+		/// Some balance was reserved (moved from free to reserved). \[who, value\]
+		Reserved(T::AccountId, T::Balance),
+		/// Some balance was unreserved (moved from reserved to free). \[who, value\]
+		Unreserved(T::AccountId, T::Balance),
+		/// Some balance was moved from the reserve of the first account to the second account.
+		/// Final argument indicates the destination balance type.
+		/// \[from, to, balance, destination_status\]
+		ReserveRepatriated(T::AccountId, T::AccountId, T::Balance, Status),
+		/// Transfer with an attached memo succeeded. \[from, to, value, memo\]
+		TransferWithMemo(T::AccountId, T::AccountId, T::Balance, Vec<u8>),
+		/// Some free balance was destroyed by its owner. \[who, amount\]
+		Burned(T::AccountId, T::Balance),
+		/// The existential deposit was changed. \[old, new\]
+		ExistentialDepositChanged(T::Balance, T::Balance),
+		/// The total issuance was clamped at the maximum of its type, so this amount was created
+		/// without being accounted for. \[excess\]
+		IssuanceCapped(T::Balance),
+		/// The dust collected in `BurnAccount` was burned. \[total\]
+		DustBurned(T::Balance),
+		/// An account was merged into another one and reaped. \[merged, into\]
+		AccountsMerged(T::AccountId, T::AccountId),
+		/// Some free balance was transferred into the reserved balance of another account.
+		/// \[from, to, value\]
+		ReservedTransfer(T::AccountId, T::AccountId, T::Balance),
+		/// An account allowed or forbade paying its fees from its reserved balance.
+		/// \[who, allow\]
+		FeeFromReservedSet(T::AccountId, bool),
+		/// An account whose balance was wiped out by root could not be reaped. \[who, blocker\]
+		ReapFailed(T::AccountId, ReapBlocker),
+		/// An account was frozen. \[who\]
+		AccountFrozen(T::AccountId),
+		/// An account was thawed. \[who\]
+		AccountThawed(T::AccountId),
+		/// The `SponsoredFeeRefund` of a transfer was paid to its sender by a sponsor.
+		/// \[sponsor, who, refund\]
+		TransferSponsored(T::AccountId, T::AccountId, T::Balance),
+		/// Some of an asset was transferred by `transfer_asset`. \[asset_id, from, to, value\]
+		AssetTransfer(AssetId, T::AccountId, T::AccountId, T::Balance),
+		/// A lock was set by `ForceLockOrigin`. \[who, id, amount\]
+		LockForceSet(T::AccountId, LockIdentifier, T::Balance),
+		/// A lock was removed by `ForceLockOrigin`. \[who, id\]
+		LockForceRemoved(T::AccountId, LockIdentifier),
+		/// Some balance was transferred for the recipient to claim. \[from, to, value\]
+		TransferClaimable(T::AccountId, T::AccountId, T::Balance),
+		/// Some claimable balance was claimed. \[who, value\]
+		Claimed(T::AccountId, T::Balance),
+		/// Some balance was transferred to a sub-account, resolved as given.
+		/// \[from, to, value\]
+		SubAccountTransfer(T::AccountId, T::AccountId, T::Balance),
+		/// Some balance was minted by `MintOrigin`. \[dest, value\]
+		Minted(T::AccountId, T::Balance),
+		/// The `InflationPerBlock` was changed. \[old, new\]
+		InflationPerBlockChanged(T::Balance, T::Balance),
+		/// The inflation of this block was minted to `InflationAccount`. \[amount\]
+		InflationMinted(T::Balance),
+		/// A mint was cut short by `MaxIssuance`, leaving this amount unminted. \[unminted\]
+		IssuanceCapReached(T::Balance),
+		/// Some balance was transferred under a vesting schedule. \[from, to, locked\]
+		VestedTransfer(T::AccountId, T::AccountId, T::Balance),
+		/// The vesting lock of an account was updated to what it still locks. \[who, locked\]
+		VestingUpdated(T::AccountId, T::Balance),
+		/// A `force_transfer_batch` completed, with the index and error of each move which failed.
+		/// \[successes, failures\]
+		ForceTransferBatchCompleted(u32, Vec<(u32, DispatchError)>),
+		/// A named reserve placed by `reserve_with_expiry` expired and was unreserved.
+		/// \[who, id, value\]
+		ReserveExpired(T::AccountId, T::ReserveIdentifier, T::Balance),
+		/// The transfers of all accounts were paused.
+		TransfersPaused,
+		/// The transfers of all accounts were resumed.
+		TransfersResumed,
+		/// The total issuance went up by this amount. \[amount\]
+		Issued(T::Balance),
+		/// The total issuance went down by this amount. \[amount\]
+		Rescinded(T::Balance),
+		/// The balances of an account as of a block, for off-chain mirrors to reconcile against.
+		/// \[who, free, reserved, frozen, block\]
+		BalanceCheckpoint(T::AccountId, T::Balance, T::Balance, T::Balance, T::BlockNumber),
+		/// The total issuance was forced by `force_adjust_total_issuance`. \[old, new\]
+		TotalIssuanceForced(T::Balance, T::Balance),
+		/// An account was tagged. \[who, tag\]
+		AccountTagSet(T::AccountId, Vec<u8>),
+		/// The tag of an account was removed. \[who\]
+		AccountTagCleared(T::AccountId),
+		/// A `transfer_fraction` resolved to this amount. \[from, to, fraction, value\]
+		FractionTransferred(T::AccountId, T::AccountId, Perbill, T::Balance),
+		/// A transfer through `TaxedCurrency` was charged a tax on top. \[from, value, tax\]
+		TransferTaxed(T::AccountId, T::Balance, T::Balance),
+	}
+
+	/// Old name generated by `decl_event`.
+	#[deprecated(note = "use `Event` instead")]
+	pub type RawEvent<T, I = ()> = Event<T, I>;
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Vesting balance too high to send value
+		VestingBalance,
+		/// Account liquidity restrictions prevent withdrawal. The `usable_balance` and
+		/// `usable_balance_for_fees` runtime APIs report how much is available.
+		LiquidityRestrictions,
+		/// Got an overflow after adding
+		Overflow,
+		/// Balance too low to send value
+		InsufficientBalance,
+		/// Value too low to create account due to existential deposit
+		ExistentialDeposit,
+		/// Transfer/payment would kill account
+		KeepAlive,
+		/// A vesting schedule already exists for this account
+		ExistingVestingSchedule,
+		/// Beneficiary account must pre-exist
+		DeadAccount,
+		/// Number of batch entries exceeds `MaxBatchSize`
+		TooManyRecipients,
+		/// Number of named reserves exceed MaxReserves
+		TooManyReserves,
+		/// Memo is longer than `MaxMemoLength`
+		MemoTooLong,
+		/// Number of locks exceed MaxLocks
+		TooManyLocks,
+		/// Minting would take the total issuance beyond `MaxIssuance` or overflow it
+		IssuanceOverflow,
+		/// The recipient of `transfer_if_exists` has no balance
+		RecipientDoesNotExist,
+		/// The transfer was rejected by the `TransferFilter`
+		TransferBlocked,
+		/// The account holds obligations which can't be moved by `merge_accounts`
+		MergeNotPermitted,
+		/// The signature given to `merge_accounts` isn't the merged account's
+		InvalidMergeSignature,
+		/// Value is below `MinTransfer`
+		TransferTooSmall,
+		/// The free balance left after `transfer_bounded` would be below `min_remaining`
+		WouldGoBelowMinimum,
+		/// The account can't be reaped; the `ReapFailed` event says what holds it
+		CannotReap,
+		/// The account doesn't exist, holds at least the existential deposit or is still in use
+		AccountNotReapable,
+		/// The account is frozen
+		AccountFrozen,
+		/// The sponsor can't pay the fee from its reserved balance
+		SponsorInsufficient,
+		/// The signature given to `sponsored_transfer` isn't the sponsor's
+		InvalidSponsorSignature,
+		/// The asset can't be transferred by this pallet
+		UnsupportedAsset,
+		/// The adjustment would take a balance below zero
+		AdjustmentUnderflow,
+		/// There is no claimable balance to claim
+		NothingToClaim,
+		/// A claimable balance can't be transferred to oneself
+		ClaimableToSelf,
+		/// The reserved balance would exceed `ReserveQuota` of the total balance
+		ReserveQuotaExceeded,
+		/// Sub-account seed is longer than `MaxSubAccountSeedLength`
+		SubAccountSeedTooLong,
+		/// Transfers are paused by `EmergencyOrigin`
+		TransfersPaused,
+		/// The vesting schedule releases nothing per block
+		InvalidVestingSchedule,
+		/// The account has no vesting schedule
+		NotVesting,
+		/// The sender can't pay the `AccountCreationFee` for creating the recipient
+		CannotPayCreationFee,
+		/// The lock would freeze the `FeeReserve` needed to pay for a transaction
+		WouldLockOutFees,
+		/// The transfer to this recipient was rejected by the `DestinationFilter`
+		DestinationNotAllowed,
+		/// The transfer was included after the block it was valid until
+		TransferExpired,
+		/// Tag is longer than `MaxTagLength`
+		TagTooLong,
+		/// The account has no tag to clear
+		NotTagged,
+	}
+
+	/// The total units issued in the system.
+	#[pallet::storage]
+	#[pallet::getter(fn total_issuance)]
+	pub type TotalIssuance<T: Config<I>, I: 'static = ()> = StorageValue<_, T::Balance, ValueQuery>;
+
+	/// The total issuance at the start of each of the last `IssuanceHistoryDepth` eras.
+	#[pallet::storage]
+	#[pallet::getter(fn issuance_at_era)]
+	pub type IssuanceHistory<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		EraIndex,
+		T::Balance,
+		OptionQuery
+	>;
+
+	/// The balance of an account.
+	///
+	/// NOTE: This is only used in the case that this pallet is used to store balances.
+	#[pallet::storage]
+	pub type Account<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		AccountData<T::Balance>,
+		ValueQuery
+	>;
+
+	/// The free balance of an account which has nothing reserved or frozen, kept in place of its
+	/// entry in `Account`.
+	///
+	/// NOTE: This is only used in the case that `CompactAccountStore` is used to store balances.
+	#[pallet::storage]
+	pub type FreeBalance<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Balance,
+		OptionQuery
+	>;
+
+	/// Any liquidity locks on some account balances.
+	/// NOTE: Should only be accessed when setting, changing and freeing a lock.
+	#[pallet::storage]
+	#[pallet::getter(fn locks)]
+	pub type Locks<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<BalanceLock<T::Balance, T::BlockNumber>>,
+		ValueQuery
+	>;
+
+	/// The accounts with a lock expiring at a given block, to be pruned at its initialization.
+	#[pallet::storage]
+	pub(super) type LockExpiries<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Vec<T::AccountId>,
+		ValueQuery
+	>;
+
+	/// The raw `Locks` key of the last account visited by the idle lock pruning, if it is part way
+	/// through the accounts.
+	#[pallet::storage]
+	pub(super) type PruneCursor<T: Config<I>, I: 'static = ()> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// Named reserves on some account balances.
+	#[pallet::storage]
+	#[pallet::getter(fn reserves)]
+	pub type Reserves<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<ReserveData<T::ReserveIdentifier, T::Balance>>,
+		ValueQuery
+	>;
+
+	/// The accounts which allow their transaction fees to be paid from their reserved balance.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_from_reserved)]
+	pub type FeeFromReserved<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		bool,
+		ValueQuery
+	>;
+
+	/// The part of the reserved balance of each account which was transferred to it with
+	/// `transfer_claimable` and isn't claimed yet, held under `ClaimableReserveId`.
+	#[pallet::storage]
+	#[pallet::getter(fn claimable)]
+	pub type Claimable<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Balance,
+		ValueQuery
+	>;
+
+	/// The named reserves of each account placed by `reserve_with_expiry`, as their identifier,
+	/// the block they expire at and the value to unreserve then.
+	#[pallet::storage]
+	#[pallet::getter(fn reserve_expiries)]
+	pub type ReserveExpiries<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<(T::ReserveIdentifier, T::BlockNumber, T::Balance)>,
+		ValueQuery
+	>;
+
+	/// The vesting schedule of each account with a balance locked by `vested_transfer`.
+	#[pallet::storage]
+	#[pallet::getter(fn vesting)]
+	pub type Vesting<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		VestingSchedule<T::Balance, T::BlockNumber>,
+		OptionQuery
+	>;
+
+	/// The block at which the free or reserved balance of each existing account last changed.
+	#[pallet::storage]
+	#[pallet::getter(fn last_active)]
+	pub type LastActive<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		T::BlockNumber,
+		OptionQuery
+	>;
+
+	/// The accounts which are frozen, i.e. may not withdraw, transfer or reserve any balance.
+	#[pallet::storage]
+	#[pallet::getter(fn is_frozen)]
+	pub type FrozenAccounts<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		bool,
+		ValueQuery
+	>;
+
+	/// Whether the transfers of signed origins are paused by `pause_transfers`.
+	#[pallet::storage]
+	#[pallet::getter(fn transfers_paused)]
+	pub type TransfersPaused<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
+	/// The minimum amount required to keep an account open.
+	#[pallet::storage]
+	#[pallet::getter(fn existential_deposit)]
+	pub type ExistentialDeposit<T: Config<I>, I: 'static = ()> = StorageValue<
+		_,
+		T::Balance,
+		ValueQuery,
+		T::ExistentialDeposit
+	>;
+
+	/// The dust swept into `BurnAccount` since its last burn.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_dust)]
+	pub type PendingDust<T: Config<I>, I: 'static = ()> = StorageValue<_, T::Balance, ValueQuery>;
+
+	/// The balance minted to `InflationAccount` at the initialization of every block.
+	#[pallet::storage]
+	#[pallet::getter(fn inflation_per_block)]
+	pub type InflationPerBlock<T: Config<I>, I: 'static = ()> = StorageValue<
+		_,
+		T::Balance,
+		ValueQuery
+	>;
+
+	/// The opaque tags of accounts set by `TagOrigin`, e.g. their KYC tier.
+	#[pallet::storage]
+	#[pallet::getter(fn account_tag)]
+	pub type AccountTags<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<u8>,
+		OptionQuery
+	>;
+
+	/// The reserved balance below which unreserving stops, e.g. the minimum bond of a validator,
+	/// as set by `set_reserve_floor`. Only `unreserve` and `unreserve_named` are held to it, not
+	/// slashes, repatriations, `force_unreserve`, `claim` or expiring reserves.
+	#[pallet::storage]
+	#[pallet::getter(fn reserve_floor)]
+	pub type ReserveFloor<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Balance,
+		ValueQuery
+	>;
+
+	/// The number of times each account was merged away by `merge_accounts`, which the signed
+	/// consent commits to. Unlike the system nonce, it is kept when the account is reaped.
+	#[pallet::storage]
+	#[pallet::getter(fn merge_nonce)]
+	pub type MergeNonce<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery
+	>;
+
+	/// Storage version of the pallet.
+	///
+	/// This is set to v8.0.0 for new networks.
+	#[pallet::storage]
+	pub(super) type StorageVersion<T: Config<I>, I: 'static = ()> = StorageValue<
+		_,
+		Releases,
+		ValueQuery
+	>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		pub balances: Vec<(T::AccountId, T::Balance)>,
+		/// The reserved balances of accounts endowed in `balances`.
+		#[cfg_attr(feature = "std", serde(default))]
+		pub reserved_balances: Vec<(T::AccountId, T::Balance)>,
+		/// Locks on the free balances of accounts endowed in `balances`.
+		#[cfg_attr(feature = "std", serde(default, with = "crate::genesis_locks"))]
+		pub locks: Vec<(T::AccountId, LockIdentifier, T::Balance, WithdrawReasons)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
+		fn default() -> Self {
+			Self {
+				balances: Default::default(),
+				reserved_balances: Default::default(),
+				locks: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
+		fn build(&self) {
+			<ExistentialDeposit<T, I>>::put(T::ExistentialDeposit::get());
+
+			<StorageVersion<T, I>>::put(Releases::V8_0_0);
+
+			for (_, balance) in &self.balances {
+				assert!(
+					*balance >= <T as Config<I>>::ExistentialDeposit::get(),
+					"the balance of any account should always be at least the existential deposit.",
+				)
+			}
+
+			// ensure no duplicates exist.
+			let endowed_accounts = self.balances.iter().map(|(x, _)| x).cloned().collect::<std::collections::BTreeSet<_>>();
+
+			assert!(endowed_accounts.len() == self.balances.len(), "duplicate balances in genesis.");
+
+			let reserved = self.reserved_balances.iter().cloned().collect::<std::collections::BTreeMap<_, _>>();
+
+			assert!(reserved.len() == self.reserved_balances.len(), "duplicate reserved balances in genesis.");
+			assert!(
+				reserved.keys().all(|who| endowed_accounts.contains(who)),
+				"reserved balance of an account which isn't endowed in genesis.",
+			);
+
+			let mut total: T::Balance = Zero::zero();
+			for &(ref who, free) in self.balances.iter() {
+				let reserved = reserved.get(who).copied().unwrap_or_else(Zero::zero);
+				let account_total = free.checked_add(&reserved)
+					.expect("free and reserved balances overflow in genesis.");
+				total = total.checked_add(&account_total).expect("total issuance overflows in genesis.");
+				assert!(T::AccountStore::insert(who, AccountData { free, reserved, ..Default::default() }).is_ok());
+				LastActive::<T, I>::insert(who, T::BlockNumber::zero());
+			}
+			<TotalIssuance<T, I>>::put(total);
+
+			for &(ref who, id, amount, reasons) in self.locks.iter() {
+				assert!(
+					endowed_accounts.contains(who) && amount <= Pallet::<T, I>::free_balance(who),
+					"locked balance exceeds the free balance in genesis.",
+				);
+				Pallet::<T, I>::try_set_lock(id, who, amount, reasons).expect("too many locks in genesis.");
+			}
+		}
+	}
+}
+
+/// (De)serialization of the genesis `locks`, with the `WithdrawReasons` as their SCALE encoding.
+#[cfg(feature = "std")]
+mod genesis_locks {
+	use super::*;
+	use serde::{Serialize, Serializer, Deserialize, Deserializer, de::Error};
+
+	#[allow(clippy::ptr_arg)]
+	pub fn serialize<S, AccountId, Balance>(
+		locks: &Vec<(AccountId, LockIdentifier, Balance, WithdrawReasons)>,
+		serializer: S,
+	) -> Result<S::Ok, S::Error> where
+		S: Serializer,
+		AccountId: Serialize,
+		Balance: Serialize,
+	{
+		locks.iter()
+			.map(|(who, id, amount, reasons)| (who, id, amount, reasons.encode()))
+			.collect::<Vec<_>>()
+			.serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D, AccountId, Balance>(
+		deserializer: D,
+	) -> Result<Vec<(AccountId, LockIdentifier, Balance, WithdrawReasons)>, D::Error> where
+		D: Deserializer<'de>,
+		AccountId: Deserialize<'de>,
+		Balance: Deserialize<'de>,
+	{
+		Vec::<(AccountId, LockIdentifier, Balance, Vec<u8>)>::deserialize(deserializer)?
+			.into_iter()
+			.map(|(who, id, amount, reasons)| {
+				let reasons = WithdrawReasons::decode_all(&reasons)
+					.map_err(|_| D::Error::custom("invalid withdraw reasons"))?;
+				Ok((who, id, amount, reasons))
+			})
+			.collect()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Config<I>, I: 'static> GenesisConfig<T, I> {
+	/// Direct implementation of `GenesisBuild::build_storage`.
+	///
+	/// Kept in order not to break dependency.
+	pub fn build_storage(&self) -> Result<sp_runtime::Storage, String> {
+		<Self as GenesisBuild<T, I>>::build_storage(self)
+	}
+
+	/// Direct implementation of `GenesisBuild::assimilate_storage`.
+	///
+	/// Kept in order not to break dependency.
+	pub fn assimilate_storage(
+		&self,
+		storage: &mut sp_runtime::Storage
+	) -> Result<(), String> {
+		<Self as GenesisBuild<T, I>>::assimilate_storage(self, storage)
+	}
+}
+
+/// Simplified reasons for withdrawing balance.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Reasons {
+	/// Paying system transaction fees.
+	Fee = 0,
+	/// Any reason other than paying system transaction fees.
+	Misc = 1,
+	/// Any reason at all.
+	All = 2,
+}
+
+impl From<WithdrawReasons> for Reasons {
+	fn from(r: WithdrawReasons) -> Reasons {
+		if r == WithdrawReasons::from(WithdrawReasons::TRANSACTION_PAYMENT) {
+			Reasons::Fee
+		} else if r.contains(WithdrawReasons::TRANSACTION_PAYMENT) {
+			Reasons::All
+		} else {
+			Reasons::Misc
+		}
+	}
+}
+
+impl BitOr for Reasons {
+	type Output = Reasons;
+	fn bitor(self, other: Reasons) -> Reasons {
+		if self == other { return self }
+		Reasons::All
+	}
+}
+
+/// What kept an account from being reaped when its balance was wiped out.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ReapBlocker {
+	/// Other pallets still provide for the account, so it lives on with a zero balance.
+	ProviderRefs,
+	/// Other pallets still depend on the account, and this pallet is its only provider.
+	ConsumerRefs,
+	/// The account still has balance locks.
+	Locks,
+}
+
+/// A single lock on a balance. There can be many of these on an account and they "overlap", so the
+/// same balance is frozen by multiple locks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BalanceLock<Balance, BlockNumber> {
+	/// An identifier for this lock. Only one lock may be in existence for each identifier.
+	pub id: LockIdentifier,
+	/// The amount which the free balance may not drop below when this lock is in effect.
+	pub amount: Balance,
+	/// If true, then the lock remains in effect even for payment of transaction fees.
+	pub reasons: Reasons,
+	/// The block from which this lock has expired and may be pruned, if any.
+	pub until: Option<BlockNumber>,
+}
+
+impl<Balance, BlockNumber: PartialOrd> BalanceLock<Balance, BlockNumber> {
+	/// Whether this lock has expired by block `now`.
+	pub fn is_expired(&self, now: &BlockNumber) -> bool {
+		self.until.as_ref().map_or(false, |until| until <= now)
+	}
+}
+
+/// Store named reserved balance.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReserveData<ReserveIdentifier, Balance> {
+	/// The identifier for the named reserve.
+	pub id: ReserveIdentifier,
+	/// The amount of the named reserve.
+	pub amount: Balance,
+}
+
+/// All balance information for an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct AccountData<Balance> {
+	/// Non-reserved part of the balance. There may still be restrictions on this, but it is the
+	/// total pool what may in principle be transferred, reserved and used for tipping.
+	///
+	/// This is the only balance that matters in terms of most operations on tokens. It
+	/// alone is used to determine the balance when in the contract execution environment.
+	pub free: Balance,
+	/// Balance which is reserved and may not be used at all.
+	///
+	/// This can still get slashed, but gets slashed last of all. It includes the sum of all
+	/// named reserves, any remainder being anonymous reserves.
+	///
+	/// This balance is a 'reserve' balance that other subsystems use in order to set aside tokens
+	/// that are still 'owned' by the account holder, but which are suspendable.
+	pub reserved: Balance,
+	/// The amount that `free` may not drop below when withdrawing for *anything except transaction
+	/// fee payment*.
+	pub misc_frozen: Balance,
+	/// The amount that `free` may not drop below when withdrawing specifically for transaction
+	/// fee payment.
+	pub fee_frozen: Balance,
+}
+
+impl<Balance: Saturating + Copy + Ord> AccountData<Balance> {
+	/// How much this account's balance can be reduced for the given `reasons`.
+	fn usable(&self, reasons: Reasons) -> Balance {
+		self.free.saturating_sub(self.frozen(reasons))
+	}
+	/// The amount that this account's free balance may not be reduced beyond for the given
+	/// `reasons`.
+	fn frozen(&self, reasons: Reasons) -> Balance {
+		match reasons {
+			Reasons::All => self.misc_frozen.max(self.fee_frozen),
+			Reasons::Misc => self.misc_frozen,
+			Reasons::Fee => self.fee_frozen,
+		}
+	}
+	/// The total balance in this account including any that is reserved and ignoring any frozen.
+	fn total(&self) -> Balance {
+		self.free.saturating_add(self.reserved)
+	}
+}
+
+impl<Balance: Zero> AccountData<Balance> {
+	/// Whether this account has nothing reserved or frozen, so only its free balance need be stored.
+	pub fn is_free_only(&self) -> bool {
+		self.reserved.is_zero() && self.misc_frozen.is_zero() && self.fee_frozen.is_zero()
+	}
+}
+
+/// A store of the accounts of this pallet which keeps the free balance alone, in `FreeBalance`,
+/// for an account with nothing reserved or frozen and all of `AccountData`, in `Account`, for any
+/// other, calling `L` as accounts are created and killed as `StorageMapShim` does.
+///
+/// With a `u128` balance, that is 16 rather than 64 bytes stored for most accounts. A runtime
+/// which switches to it from `StorageMapShim` over `Account` should run
+/// `migrations::migrate_to_compact_account_store` in the same upgrade.
+pub struct CompactAccountStore<T, L, I = ()>(PhantomData<(T, L, I)>);
+
+impl<T: Config<I>, L: HandleLifetime<T::AccountId>, I: 'static>
+	StoredMap<T::AccountId, AccountData<T::Balance>> for CompactAccountStore<T, L, I>
+{
+	fn get(who: &T::AccountId) -> AccountData<T::Balance> {
+		Self::load(who).unwrap_or_default()
+	}
+
+	fn try_mutate_exists<R, E: From<StoredMapError>>(
+		who: &T::AccountId,
+		f: impl FnOnce(&mut Option<AccountData<T::Balance>>) -> Result<R, E>,
+	) -> Result<R, E> {
+		let mut maybe_account = Self::load(who);
+		let existed = maybe_account.is_some();
+		let r = f(&mut maybe_account)?;
+		match (existed, maybe_account.is_some()) {
+			(false, true) => L::created(who)?,
+			(true, false) => L::killed(who)?,
+			_ => {},
+		}
+		match maybe_account {
+			Some(account) if account.is_free_only() => {
+				Account::<T, I>::remove(who);
+				FreeBalance::<T, I>::insert(who, account.free);
+			},
+			Some(account) => {
+				FreeBalance::<T, I>::remove(who);
+				Account::<T, I>::insert(who, account);
+			},
+			None => {
+				FreeBalance::<T, I>::remove(who);
+				Account::<T, I>::remove(who);
+			},
+		}
+		Ok(r)
+	}
+}
+
+impl<T: Config<I>, L, I: 'static> CompactAccountStore<T, L, I> {
+	/// The data of `who`, from whichever of the two maps it is stored in.
+	fn load(who: &T::AccountId) -> Option<AccountData<T::Balance>> {
+		match FreeBalance::<T, I>::get(who) {
+			Some(free) => Some(AccountData { free, ..Default::default() }),
+			None if Account::<T, I>::contains_key(who) => Some(Account::<T, I>::get(who)),
+			None => None,
+		}
+	}
+}
+
+/// The outcome of a hypothetical transfer, as reported by `simulate_transfer`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct SimulatedTransfer<Balance> {
+	/// Whether the sender would be reaped.
+	pub sender_reaped: bool,
+	/// The balance the sender would lose as dust when being reaped.
+	pub dust_lost: Balance,
+	/// Whether the recipient would be created, using up the existential deposit of `value`.
+	pub recipient_created: bool,
+}
+
+/// The balances of an account at a glance, as reported by `balance_summary`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct BalanceSummary<Balance> {
+	/// The free balance.
+	pub free: Balance,
+	/// The reserved balance.
+	pub reserved: Balance,
+	/// The part of the free balance frozen by the most restrictive of the locks.
+	pub frozen: Balance,
+	/// The part of the free balance which can be transferred while keeping the account alive.
+	pub transferable: Balance,
+	/// The free and reserved balance together.
+	pub total: Balance,
+}
+
+/// The schedule by which the balance transferred by `vested_transfer` is released.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct VestingSchedule<Balance, BlockNumber> {
+	/// The balance locked at first.
+	pub locked: Balance,
+	/// The balance released per block after `starting_block`.
+	pub per_block: Balance,
+	/// The block from which the release is counted.
+	pub starting_block: BlockNumber,
+	/// The block before which nothing is released.
+	pub cliff: BlockNumber,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy, BlockNumber: AtLeast32BitUnsigned + Copy>
+	VestingSchedule<Balance, BlockNumber>
+{
+	/// The balance still locked at block `n`.
+	pub fn locked_at(&self, n: BlockNumber) -> Balance {
+		if n < self.cliff {
+			return self.locked;
+		}
+		let elapsed = n.saturating_sub(self.starting_block).saturated_into::<u128>();
+		self.locked.saturating_sub(self.per_block.saturating_mul(Balance::saturated_from(elapsed)))
+	}
+}
+
+// A value placed in storage that represents the current version of the Balances storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic. This should match directly with the semantic versions of the Rust crate.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	V1_0_0,
+	V2_0_0,
+	V3_0_0,
+	V4_0_0,
+	V5_0_0,
+	V6_0_0,
+	V7_0_0,
+	V8_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+pub mod migrations {
+	use super::*;
+
+	/// Introduce named reserves.
+	///
+	/// Reserves made before this release are anonymous. They stay exactly where they are, in
+	/// `AccountData::reserved`, and `Reserves` starts out empty, so no balance is touched.
+	pub fn migrate_to_named_reserves<T: Config<I>, I: 'static>() -> Weight {
+		frame_support::debug::info!("Balances: named reserves introduced, anonymous reserves preserved.");
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	/// Bring every account back within `MaxLocks`.
+	///
+	/// Over-limit accounts keep their largest locks; ties are broken by lock id so the result
+	/// is deterministic. The frozen amounts are recomputed from the remaining locks.
+	pub fn truncate_excess_locks<T: Config<I>, I: 'static>() -> Weight {
+		let max_locks = T::MaxLocks::get() as usize;
+		let mut reads: Weight = 0;
+		let over_limit = Locks::<T, I>::iter()
+			.inspect(|_| reads += 1)
+			.filter(|(_, locks)| locks.len() > max_locks)
+			.collect::<Vec<_>>();
+		let truncated = over_limit.len() as Weight;
+		for (who, mut locks) in over_limit {
+			locks.sort_by(|a, b| b.amount.cmp(&a.amount).then_with(|| a.id.cmp(&b.id)));
+			locks.truncate(max_locks);
+			Pallet::<T, I>::update_locks(&who, &locks[..]);
+		}
+		frame_support::debug::info!("Balances: truncated the locks of {} accounts.", truncated);
+		T::DbWeight::get().reads_writes(reads.saturating_add(truncated), truncated.saturating_mul(2))
+	}
+
+	/// A lock as it was stored before locks could expire.
+	#[derive(Encode, Decode)]
+	struct OldBalanceLock<Balance> {
+		id: LockIdentifier,
+		amount: Balance,
+		reasons: Reasons,
+	}
+
+	/// Add the `until` field to every lock. Existing locks never expire.
+	pub fn migrate_to_expiring_locks<T: Config<I>, I: 'static>() -> Weight {
+		let mut translated: Weight = 0;
+		Locks::<T, I>::translate::<Vec<OldBalanceLock<T::Balance>>, _>(|_, locks| {
+			translated += 1;
+			Some(locks.into_iter()
+				.map(|l| BalanceLock { id: l.id, amount: l.amount, reasons: l.reasons, until: None })
+				.collect())
+		});
+		frame_support::debug::info!("Balances: migrated the locks of {} accounts.", translated);
+		T::DbWeight::get().reads_writes(translated, translated)
+	}
+
+	/// Turn any lock stored on its own, rather than in a `Vec`, into a single-lock `Vec`.
+	///
+	/// Single locks in either the current or the pre-expiry layout are understood; the latter
+	/// never expire. Entries already holding a `Vec` are left untouched, so this is idempotent,
+	/// and entries which can't be decoded at all are kept as they are.
+	pub fn migrate_single_locks<T: Config<I>, I: 'static>() -> Weight {
+		let prefix = Locks::<T, I>::final_prefix();
+		let mut key = prefix.to_vec();
+		let (mut reads, mut migrated): (Weight, Weight) = (0, 0);
+		while let Some(next) = sp_io::storage::next_key(&key).filter(|k| k.starts_with(&prefix)) {
+			key = next;
+			reads += 1;
+			let raw = match frame_support::storage::unhashed::get_raw(&key) {
+				Some(raw) => raw,
+				None => continue,
+			};
+			if Vec::<BalanceLock<T::Balance, T::BlockNumber>>::decode_all(&raw).is_ok() { continue }
+			let lock = BalanceLock::<T::Balance, T::BlockNumber>::decode_all(&raw).or_else(|_|
+				OldBalanceLock::<T::Balance>::decode_all(&raw)
+					.map(|l| BalanceLock { id: l.id, amount: l.amount, reasons: l.reasons, until: None })
+			);
+			match lock {
+				Ok(lock) => {
+					frame_support::storage::unhashed::put(&key, &vec![lock]);
+					migrated += 1;
+				},
+				Err(_) => frame_support::debug::warn!("Balances: kept undecodable locks at {:?}.", key),
+			}
+		}
+		frame_support::debug::info!("Balances: migrated the single lock of {} accounts.", migrated);
+		T::DbWeight::get().reads_writes(reads, migrated)
+	}
+
+	/// Start tracking `LastActive`, as of the current block for every account known to the system
+	/// pallet which holds a balance.
+	pub fn initialize_last_active<T: Config<I>, I: 'static>() -> Weight {
+		let now = system::Pallet::<T>::block_number();
+		let (mut reads, mut initialized): (Weight, Weight) = (0, 0);
+		for (who, _) in system::Account::<T>::iter() {
+			reads += 2;
+			if !Pallet::<T, I>::account(&who).total().is_zero() {
+				LastActive::<T, I>::insert(&who, now);
+				initialized += 1;
+			}
+		}
+		frame_support::debug::info!("Balances: initialized the last activity of {} accounts.", initialized);
+		T::DbWeight::get().reads_writes(reads, initialized)
+	}
+
+	/// Whether any two of `locks` have the same id.
+	fn has_duplicate_ids<Balance, BlockNumber>(locks: &[BalanceLock<Balance, BlockNumber>]) -> bool {
+		locks.iter().enumerate().any(|(i, lock)| locks[..i].iter().any(|other| other.id == lock.id))
+	}
+
+	/// Merge the locks sharing an id, which a past bug left on some accounts and which were each
+	/// counted towards the frozen balance.
+	///
+	/// The merged lock keeps the largest amount, the union of the reasons and the latest expiry,
+	/// so it freezes no less than any of the duplicates did, and takes the place of the first of
+	/// them. The frozen amounts are recomputed from the merged locks.
+	pub fn dedup_locks<T: Config<I>, I: 'static>() -> Weight {
+		let mut reads: Weight = 0;
+		let duplicated = Locks::<T, I>::iter()
+			.inspect(|_| reads += 1)
+			.filter(|(_, locks)| has_duplicate_ids(locks))
+			.collect::<Vec<_>>();
+		let deduplicated = duplicated.len() as Weight;
+		for (who, locks) in duplicated {
+			let mut merged: Vec<BalanceLock<T::Balance, T::BlockNumber>> = Vec::with_capacity(locks.len());
+			for lock in locks {
+				match merged.iter_mut().find(|m| m.id == lock.id) {
+					Some(m) => {
+						m.amount = m.amount.max(lock.amount);
+						m.reasons = m.reasons | lock.reasons;
+						m.until = match (m.until, lock.until) {
+							(Some(a), Some(b)) => Some(a.max(b)),
+							_ => None,
+						};
+					},
+					None => merged.push(lock),
+				}
+			}
+			Pallet::<T, I>::update_locks(&who, &merged[..]);
+		}
+		frame_support::debug::info!("Balances: deduplicated the locks of {} accounts.", deduplicated);
+		T::DbWeight::get().reads_writes(reads.saturating_add(deduplicated), deduplicated.saturating_mul(2))
+	}
+
+	/// Move the accounts in `Account` which have nothing reserved or frozen to `FreeBalance`, for a
+	/// runtime switching its `AccountStore` from `StorageMapShim` over `Account` to
+	/// `CompactAccountStore`. It isn't run on its own, as it would lose the accounts of a runtime
+	/// which doesn't make that switch, and running it again moves nothing.
+	pub fn migrate_to_compact_account_store<T: Config<I>, I: 'static>() -> Weight {
+		let mut reads: Weight = 0;
+		let free_only = Account::<T, I>::iter()
+			.inspect(|_| reads += 1)
+			.filter(|(_, account)| account.is_free_only())
+			.collect::<Vec<_>>();
+		let moved = free_only.len() as Weight;
+		for (who, account) in free_only {
+			Account::<T, I>::remove(&who);
+			FreeBalance::<T, I>::insert(&who, account.free);
+		}
+		frame_support::debug::info!("Balances: moved {} accounts to the compact account store.", moved);
+		T::DbWeight::get().reads_writes(reads, moved.saturating_mul(2))
+	}
+
+	/// Where `pre_upgrade` keeps the total issuance for `post_upgrade` to check.
+	const PRE_UPGRADE_ISSUANCE_KEY: &[u8] = b":balances:pre_upgrade_issuance:";
+
+	/// Record what `post_upgrade` checks the migrations preserved.
+	pub fn pre_upgrade<T: Config<I>, I: 'static>() -> Result<(), &'static str> {
+		frame_support::storage::unhashed::put(PRE_UPGRADE_ISSUANCE_KEY, &TotalIssuance::<T, I>::get());
+		Ok(())
+	}
+
+	/// Check that the migrations kept the total issuance, and that the frozen balances of every
+	/// account with locks still match its, decodable, locks.
+	pub fn post_upgrade<T: Config<I>, I: 'static>() -> Result<(), &'static str> {
+		let issuance = frame_support::storage::unhashed::take::<T::Balance>(PRE_UPGRADE_ISSUANCE_KEY)
+			.ok_or("pre_upgrade didn't run")?;
+		ensure!(TotalIssuance::<T, I>::get() == issuance, "the total issuance changed");
+		ensure!(StorageVersion::<T, I>::get() == Releases::V8_0_0, "the storage version wasn't bumped");
+
+		let prefix = Locks::<T, I>::final_prefix();
+		let mut key = prefix.to_vec();
+		while let Some(next) = sp_io::storage::next_key(&key).filter(|k| k.starts_with(&prefix)) {
+			key = next;
+			frame_support::storage::unhashed::get_raw(&key)
+				.and_then(|raw| Vec::<BalanceLock<T::Balance, T::BlockNumber>>::decode_all(&raw).ok())
+				.ok_or("some locks can't be decoded")?;
+		}
+		for (who, locks) in Locks::<T, I>::iter() {
+			ensure!(!has_duplicate_ids(&locks), "some locks share an id");
+			let (misc_frozen, fee_frozen) = Pallet::<T, I>::frozen_by_locks(&locks);
+			let account = Pallet::<T, I>::account(&who);
+			ensure!(account.misc_frozen == misc_frozen, "misc frozen balance doesn't match the locks");
+			ensure!(account.fee_frozen == fee_frozen, "fee frozen balance doesn't match the locks");
+		}
+		Pallet::<T, I>::do_try_state()
+	}
+}
+
+pub struct DustCleaner<T: Config<I>, I: 'static = ()>(Option<(T::AccountId, NegativeImbalance<T, I>)>);
+
+impl<T: Config<I>, I: 'static> Drop for DustCleaner<T, I> {
+	fn drop(&mut self) {
+		if let Some((who, dust)) = self.0.take() {
+			Module::<T, I>::deposit_event(Event::DustLost(who, dust.peek()));
+			T::DustRemoval::on_unbalanced(dust);
+		}
+	}
+}
+
+/// The identifier of the lock placed by `vested_transfer`.
+pub const VESTING_ID: LockIdentifier = *b"vesting ";
+
+/// The most accounts `accounts_above` returns at once.
+pub const MAX_ACCOUNTS_ABOVE: u32 = 1000;
+
+/// The most accounts `accounts_above` looks at in one call, whichever it returns.
+pub const MAX_ACCOUNTS_SCANNED: u32 = 10_000;
+
+/// The prefix of the message signed to consent to `merge_accounts`.
+pub const MERGE_ACCOUNTS_PREFIX: &[u8] = b"calcu/merge_accounts";
+
+/// The prefix of the message signed to sponsor a `sponsored_transfer`.
+pub const SPONSORED_TRANSFER_PREFIX: &[u8] = b"calcu/sponsored_transfer";
+
+/// A `MergeableLocks` set which is empty, so that any lock prevents `merge_accounts`.
+pub struct NoMergeableLocks;
+
+impl Contains<LockIdentifier> for NoMergeableLocks {
+	fn sorted_members() -> Vec<LockIdentifier> {
+		Vec::new()
+	}
+}
+
+/// An asset which can be named in `transfer_asset`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+pub enum AssetId {
+	/// The native currency, managed by this pallet.
+	Native,
+	/// Any other on-chain asset. None is supported yet.
+	Token(u32),
+}
+
+/// A `SupportedAssets` which only contains the native currency.
+pub struct NativeAssetOnly;
+
+impl Contains<AssetId> for NativeAssetOnly {
+	fn sorted_members() -> Vec<AssetId> {
+		vec![AssetId::Native]
+	}
+}
+
+/// A `ReserveQuota` which lets accounts reserve all of their balance.
+pub struct NoReserveQuota;
+
+impl Get<Perbill> for NoReserveQuota {
+	fn get() -> Perbill {
+		Perbill::one()
+	}
+}
+
+/// A `FeeOnlyAccounts` which contains no account.
+pub struct NoAccounts;
+
+impl<AccountId: Ord> Contains<AccountId> for NoAccounts {
+	fn sorted_members() -> Vec<AccountId> {
+		vec![]
+	}
+}
+
+/// A `TransferFilter` or `DestinationFilter` which lets every transfer go ahead.
+pub struct AllowAllTransfers;
+
+impl<AccountId: Ord, Balance: Ord> Contains<(AccountId, Balance)> for AllowAllTransfers {
+	fn contains(_: &(AccountId, Balance)) -> bool {
+		true
+	}
+
+	fn sorted_members() -> Vec<(AccountId, Balance)> {
+		Vec::new()
+	}
+}
+
+/// A `MaxIssuance` which lets the total issuance grow up to the maximum of its type.
+pub struct UncappedIssuance;
+
+impl<Balance: Bounded> Get<Balance> for UncappedIssuance {
+	fn get() -> Balance {
+		Balance::max_value()
+	}
+}
+
+/// A `DustRemoval` handler which credits reaped dust to `BurnAccount` instead of dropping it,
+/// leaving the total issuance untouched until the next `BurnInterval` burn.
+///
+/// Dust which cannot be credited (e.g. because it would not create the burn account) is
+/// burned right away.
+pub struct SweepDust<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> OnUnbalanced<NegativeImbalance<T, I>> for SweepDust<T, I> {
+	fn on_nonzero_unbalanced(dust: NegativeImbalance<T, I>) {
+		let credited = Pallet::<T, I>::credit_creating(&T::BurnAccount::get(), dust.peek());
+		PendingDust::<T, I>::mutate(|pending| *pending = pending.saturating_add(credited.peek()));
+		drop(dust.offset(credited));
+	}
+}
+
+/// Where [`RouteDust`] sends reaped dust.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum DustDestination {
+	/// The dust is burned, reducing the total issuance.
+	Burn,
+	/// The dust is swept to `BurnAccount`, as by [`SweepDust`].
+	Sweep,
+	/// The dust is credited to the author of the current block, as found by `FindAuthor`.
+	Author,
+}
+
+impl Default for DustDestination {
+	fn default() -> Self {
+		DustDestination::Burn
+	}
+}
+
+/// A `DustRemoval` handler which sends reaped dust wherever `Destination` says, so that operators
+/// can switch between burning it, sweeping it and rewarding the block author with it.
+///
+/// Dust for the author is burned if no author can be found in the digest of the block, or if it
+/// would not create the author's account.
+pub struct RouteDust<T, Destination, Author, I = ()>(PhantomData<(T, Destination, Author, I)>);
+
+impl<T, Destination, Author, I> OnUnbalanced<NegativeImbalance<T, I>>
+	for RouteDust<T, Destination, Author, I>
+where
+	T: Config<I>,
+	I: 'static,
+	Destination: Get<DustDestination>,
+	Author: FindAuthor<T::AccountId>,
+{
+	fn on_nonzero_unbalanced(dust: NegativeImbalance<T, I>) {
+		match Destination::get() {
+			DustDestination::Burn => drop(dust),
+			DustDestination::Sweep => SweepDust::<T, I>::on_unbalanced(dust),
+			DustDestination::Author => {
+				let digest = system::Pallet::<T>::digest();
+				let pre_runtime_digests = digest.logs().iter().filter_map(|d| d.as_pre_runtime());
+				match Author::find_author(pre_runtime_digests) {
+					Some(author) => <Pallet<T, I> as Currency<_>>::resolve_creating(&author, dust),
+					None => drop(dust),
+				}
+			}
+		}
+	}
+}
+
+/// How to round a fraction of a balance to a whole number of units.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum RoundingMode {
+	/// Round towards zero.
+	Floor,
+	/// Round away from zero.
+	Ceil,
+	/// Round to the nearest unit, and halves to the nearest even unit so that rounding errors
+	/// cancel out on average.
+	NearestBankers,
+}
+
+impl Default for RoundingMode {
+	fn default() -> Self {
+		RoundingMode::Floor
+	}
+}
+
+impl RoundingMode {
+	/// The `ratio` part of `n`, rounded according to `self`.
+	pub fn apply<N: AtLeast32BitUnsigned + Copy>(self, ratio: Perbill, n: N) -> N {
+		match self {
+			RoundingMode::Floor => ratio.mul_floor(n),
+			RoundingMode::Ceil => ratio.mul_ceil(n),
+			RoundingMode::NearestBankers => {
+				let floor = ratio.mul_floor(n);
+				let accuracy = Perbill::ACCURACY as u128;
+				// `n * ratio` modulo one, in parts of `accuracy`. It can't overflow as both factors
+				// are below `accuracy`.
+				let fraction = (n % N::from(Perbill::ACCURACY)).saturated_into::<u128>()
+					* ratio.deconstruct() as u128 % accuracy;
+				let half = accuracy / 2;
+				let round_up = fraction > half
+					|| (fraction == half && floor % N::from(2u32) == One::one());
+				if round_up { floor + One::one() } else { floor }
+			},
+		}
+	}
+
+	/// `n * numerator / denominator`, rounded according to `self`. `numerator` may not exceed
+	/// `denominator`, which may not be zero.
+	pub fn apply_rational(self, n: u128, numerator: u128, denominator: u128) -> u128 {
+		let floor = sp_runtime::helpers_128bit::multiply_by_rational(n, numerator, denominator)
+			.unwrap_or_else(|_| Zero::zero());
+		// `n * numerator` modulo `denominator`. The true value is below `denominator`, so the
+		// wrapping arithmetic yields it exactly.
+		let remainder = n.wrapping_mul(numerator).wrapping_sub(floor.wrapping_mul(denominator));
+		let round_up = match self {
+			RoundingMode::Floor => false,
+			RoundingMode::Ceil => !remainder.is_zero(),
+			RoundingMode::NearestBankers => {
+				let rest = denominator - remainder;
+				remainder > rest || (remainder == rest && floor % 2 == 1)
+			},
+		};
+		if round_up { floor + 1 } else { floor }
+	}
+}
+
+/// How the amounts of overlapping locks for the same reason add up to the frozen balance.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum LockAggregation {
+	/// The largest lock freezes the balance, and the others freeze nothing more.
+	Max,
+	/// Each lock freezes its own part of the balance, e.g. for distinct guarantees.
+	Sum,
+}
+
+impl Default for LockAggregation {
+	fn default() -> Self {
+		LockAggregation::Max
+	}
+}
+
+impl LockAggregation {
+	/// The frozen amount of `frozen` and another lock of `amount` together.
+	pub fn combine<N: Saturating + Ord>(self, frozen: N, amount: N) -> N {
+		match self {
+			LockAggregation::Max => frozen.max(amount),
+			LockAggregation::Sum => frozen.saturating_add(amount),
+		}
+	}
+}
+
+/// A fee handler which burns the `BurnRatio` part of each fee and deposits the rest into
+/// `TreasuryAccount`.
+///
+/// The treasury part is rounded according to `Config::Rounding`, and the rounding remainder is
+/// burned. It is burned as well if it would not create the treasury account.
+pub struct FeeSplit<T, BurnRatio, TreasuryAccount, I = ()>(PhantomData<(T, BurnRatio, TreasuryAccount, I)>);
+
+impl<T, BurnRatio, TreasuryAccount, I> OnUnbalanced<NegativeImbalance<T, I>>
+	for FeeSplit<T, BurnRatio, TreasuryAccount, I>
+where
+	T: Config<I>,
+	I: 'static,
+	BurnRatio: Get<Perbill>,
+	TreasuryAccount: Get<T::AccountId>,
+{
+	fn on_nonzero_unbalanced(fee: NegativeImbalance<T, I>) {
+		split_to_treasury::<T, I>(fee, BurnRatio::get(), &TreasuryAccount::get());
+	}
+}
+
+/// A slash handler which burns the `SlashBurnRatio` part of each slash and deposits the rest into
+/// `TreasuryAccount`, e.g. for staking and market disputes.
+///
+/// It rounds the same way as [`FeeSplit`]: the treasury part by `Config::Rounding`, burning the
+/// remainder, so that the same slash always splits the same way.
+pub struct SlashSplit<T, SlashBurnRatio, TreasuryAccount, I = ()>(
+	PhantomData<(T, SlashBurnRatio, TreasuryAccount, I)>
+);
+
+impl<T, SlashBurnRatio, TreasuryAccount, I> OnUnbalanced<NegativeImbalance<T, I>>
+	for SlashSplit<T, SlashBurnRatio, TreasuryAccount, I>
+where
+	T: Config<I>,
+	I: 'static,
+	SlashBurnRatio: Get<Perbill>,
+	TreasuryAccount: Get<T::AccountId>,
+{
+	fn on_nonzero_unbalanced(slash: NegativeImbalance<T, I>) {
+		split_to_treasury::<T, I>(slash, SlashBurnRatio::get(), &TreasuryAccount::get());
+	}
+}
+
+/// Burn the `burn_ratio` part of `imbalance` and deposit the rest into `treasury`, for
+/// [`FeeSplit`] and [`SlashSplit`].
+fn split_to_treasury<T: Config<I>, I: 'static>(
+	imbalance: NegativeImbalance<T, I>,
+	burn_ratio: Perbill,
+	treasury: &T::AccountId,
+) {
+	let to_treasury = T::Rounding::get().apply(burn_ratio.left_from_one(), imbalance.peek());
+	let (to_treasury, burn) = imbalance.split(to_treasury);
+	<Pallet<T, I> as Currency<_>>::resolve_creating(treasury, to_treasury);
+	// dropping the imbalance reduces the total issuance.
+	drop(burn);
+}
+
+/// The balances pallet as a `Currency` which charges the sender of every `transfer` a tax of
+/// the `TransferTax` part of the value on top of it, deposited into `TaxAccount`, for the
+/// pallets which are handed this rather than the pallet itself.
+///
+/// The tax is rounded by `Config::Rounding` and withdrawn before the transfer, keeping the
+/// sender alive; if either fails, neither happens. Moves from or to `TaxAccount` or the
+/// `SystemAccounts` are internal and aren't taxed, and neither is anything but `transfer`.
+pub struct TaxedCurrency<T, TransferTax, TaxAccount, I = ()>(
+	PhantomData<(T, TransferTax, TaxAccount, I)>
+);
+
+impl<T, TransferTax, TaxAccount, I> TaxedCurrency<T, TransferTax, TaxAccount, I>
+where
+	T: Config<I>,
+	I: 'static,
+	T::Balance: MaybeSerializeDeserialize + Debug,
+	TransferTax: Get<Perbill>,
+	TaxAccount: Get<T::AccountId>,
+{
+	/// Transfer `value` from `transactor` to `dest`, charging the tax on it if `taxed`, and
+	/// emitting `TransferTaxed` when a tax was charged.
+	pub fn transfer_taxed(
+		transactor: &T::AccountId,
+		dest: &T::AccountId,
+		value: T::Balance,
+		existence_requirement: ExistenceRequirement,
+		taxed: bool,
+	) -> DispatchResult {
+		let tax = T::Rounding::get().apply(TransferTax::get(), value);
+		if !taxed || tax.is_zero() || transactor == dest {
+			return <Pallet<T, I> as Currency<_>>::transfer(transactor, dest, value, existence_requirement)
+		}
+		frame_support::storage::with_transaction(|| {
+			let result = <Pallet<T, I> as Currency<_>>::withdraw(
+				transactor,
+				tax,
+				WithdrawReasons::TRANSFER,
+				KeepAlive,
+			).and_then(|tax| {
+				<Pallet<T, I> as Currency<_>>::transfer(transactor, dest, value, existence_requirement)?;
+				<Pallet<T, I> as Currency<_>>::resolve_creating(&TaxAccount::get(), tax);
+				Ok(())
+			});
+			match result {
+				Ok(()) => frame_support::storage::TransactionOutcome::Commit(Ok(())),
+				Err(e) => frame_support::storage::TransactionOutcome::Rollback(Err(e)),
+			}
+		})?;
+		Pallet::<T, I>::deposit_event(Event::TransferTaxed(transactor.clone(), value, tax));
+		Ok(())
+	}
+
+	/// Whether a transfer between `a` and `b` is an internal move of the system.
+	fn is_internal(a: &T::AccountId, b: &T::AccountId) -> bool {
+		let tax_account = TaxAccount::get();
+		let system_accounts = T::SystemAccounts::get();
+		[a, b].iter().any(|who| **who == tax_account || system_accounts.contains(*who))
+	}
+}
+
+impl<T, TransferTax, TaxAccount, I> Currency<T::AccountId>
+	for TaxedCurrency<T, TransferTax, TaxAccount, I>
+where
+	T: Config<I>,
+	I: 'static,
+	T::Balance: MaybeSerializeDeserialize + Debug,
+	TransferTax: Get<Perbill>,
+	TaxAccount: Get<T::AccountId>,
+{
+	type Balance = T::Balance;
+	type PositiveImbalance = PositiveImbalance<T, I>;
+	type NegativeImbalance = NegativeImbalance<T, I>;
+
+	fn total_balance(who: &T::AccountId) -> Self::Balance {
+		<Pallet<T, I> as Currency<_>>::total_balance(who)
+	}
+
+	fn can_slash(who: &T::AccountId, value: Self::Balance) -> bool {
+		<Pallet<T, I> as Currency<_>>::can_slash(who, value)
+	}
+
+	fn total_issuance() -> Self::Balance {
+		<Pallet<T, I> as Currency<_>>::total_issuance()
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		<Pallet<T, I> as Currency<_>>::minimum_balance()
+	}
+
+	fn burn(amount: Self::Balance) -> Self::PositiveImbalance {
+		<Pallet<T, I> as Currency<_>>::burn(amount)
+	}
+
+	fn issue(amount: Self::Balance) -> Self::NegativeImbalance {
+		<Pallet<T, I> as Currency<_>>::issue(amount)
+	}
+
+	fn free_balance(who: &T::AccountId) -> Self::Balance {
+		<Pallet<T, I> as Currency<_>>::free_balance(who)
+	}
+
+	fn ensure_can_withdraw(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+		new_balance: Self::Balance,
+	) -> DispatchResult {
+		<Pallet<T, I> as Currency<_>>::ensure_can_withdraw(who, amount, reasons, new_balance)
+	}
+
+	fn transfer(
+		transactor: &T::AccountId,
+		dest: &T::AccountId,
+		value: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		let taxed = !Self::is_internal(transactor, dest);
+		Self::transfer_taxed(transactor, dest, value, existence_requirement, taxed)
+	}
+
+	fn slash(who: &T::AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		<Pallet<T, I> as Currency<_>>::slash(who, value)
+	}
+
+	fn deposit_into_existing(
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Result<Self::PositiveImbalance, DispatchError> {
+		<Pallet<T, I> as Currency<_>>::deposit_into_existing(who, value)
+	}
+
+	fn deposit_creating(who: &T::AccountId, value: Self::Balance) -> Self::PositiveImbalance {
+		<Pallet<T, I> as Currency<_>>::deposit_creating(who, value)
+	}
+
+	fn resolve_into_existing(
+		who: &T::AccountId,
+		value: Self::NegativeImbalance,
+	) -> Result<(), Self::NegativeImbalance> {
+		<Pallet<T, I> as Currency<_>>::resolve_into_existing(who, value)
+	}
+
+	fn resolve_creating(who: &T::AccountId, value: Self::NegativeImbalance) {
+		<Pallet<T, I> as Currency<_>>::resolve_creating(who, value)
+	}
+
+	fn withdraw(
+		who: &T::AccountId,
+		value: Self::Balance,
+		reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> Result<Self::NegativeImbalance, DispatchError> {
+		<Pallet<T, I> as Currency<_>>::withdraw(who, value, reasons, liveness)
+	}
+
+	fn make_free_balance_be(
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> SignedImbalance<Self::Balance, Self::PositiveImbalance> {
+		<Pallet<T, I> as Currency<_>>::make_free_balance_be(who, value)
+	}
+}
+
+/// Which way `force_adjust_total_issuance` moves the total issuance.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum AdjustmentDirection {
+	/// The total issuance is raised.
+	Increase,
+	/// The total issuance is lowered.
+	Decrease,
+}
+
+/// Where the tip charged by `ChargeTransactionTip` goes.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum TipDestination {
+	/// The tip is handed to `TipToAuthor`, i.e. the block author.
+	Author,
+	/// The tip is burned, reducing the total issuance.
+	Burn,
+}
+
+impl Default for TipDestination {
+	fn default() -> Self {
+		TipDestination::Author
+	}
+}
+
+/// Signed extension charging a tip on top of the fee of `ChargeTransactionPayment`, which the
+/// sender can choose to burn rather than to pay to the block author.
+///
+/// The tip is withdrawn before dispatch, keeping the sender alive, and is never refunded. It is
+/// separate from the tip of `ChargeTransactionPayment`, which keeps going wherever the fee goes;
+/// a runtime with both should have senders leave that one at zero. Adding this extension changes
+/// the extrinsic format, so the runtime's `transaction_version` must be bumped with it.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeTransactionTip<T: Config<I>, I: 'static = ()> {
+	#[codec(compact)]
+	tip: T::Balance,
+	tip_destination: TipDestination,
+	_phantom: PhantomData<I>,
+}
+
+impl<T: Config<I>, I: 'static> ChargeTransactionTip<T, I> {
+	/// Utility constructor. Used only in client/factory code.
+	pub fn from(tip: T::Balance, tip_destination: TipDestination) -> Self {
+		Self { tip, tip_destination, _phantom: PhantomData }
+	}
+}
+
+impl<T: Config<I>, I: 'static> sp_std::fmt::Debug for ChargeTransactionTip<T, I> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeTransactionTip<{:?}, {:?}>", self.tip, self.tip_destination)
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config<I>, I: 'static + Clone + Eq + Send + Sync> SignedExtension for ChargeTransactionTip<T, I> {
+	const IDENTIFIER: &'static str = "ChargeTransactionTip";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+	fn additional_signed(&self) -> result::Result<(), TransactionValidityError> { Ok(()) }
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		if !self.tip.is_zero() {
+			// the same checks as the `withdraw` of `pre_dispatch`: the tip comes out of the free
+			// balance and must leave at least the existential deposit of `who`.
+			let keeps_alive = Pallet::<T, I>::free_balance(who).checked_sub(&self.tip)
+				.map_or(false, |new_free| {
+					new_free.saturating_add(Pallet::<T, I>::reserved_balance(who))
+						>= Pallet::<T, I>::existential_deposit_for(who)
+				});
+			ensure!(
+				keeps_alive && Pallet::<T, I>::can_withdraw_for(who, self.tip, WithdrawReasons::TIP),
+				InvalidTransaction::Payment
+			);
+		}
+		Ok(ValidTransaction {
+			priority: self.tip.saturated_into::<TransactionPriority>(),
+			..Default::default()
+		})
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> result::Result<Self::Pre, TransactionValidityError> {
+		if self.tip.is_zero() { return Ok(()) }
+		let tip = <Pallet<T, I> as Currency<_>>::withdraw(who, self.tip, WithdrawReasons::TIP, KeepAlive)
+			.map_err(|_| InvalidTransaction::Payment)?;
+		match self.tip_destination {
+			TipDestination::Author => T::TipToAuthor::on_unbalanced(tip),
+			// dropping the imbalance reduces the total issuance.
+			TipDestination::Burn => drop(tip),
+		}
+		Ok(())
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Get the free balance of an account.
+	pub fn free_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
+		Self::account(who.borrow()).free
+	}
+
+	/// Get the balance of an account that can be used for transfers, reservations, or any other
+	/// non-locking, non-transaction-fee activity. Will be at most `free_balance`.
+	pub fn usable_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
+		Self::account(who.borrow()).usable(Reasons::Misc)
+	}
+
+	/// Get the balance of an account that can be used for paying transaction fees (not tipping,
+	/// or any other kind of fees, though). Will be at most `free_balance`.
+	pub fn usable_balance_for_fees(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
+		Self::account(who.borrow()).usable(Reasons::Fee)
+	}
+
+	/// Get the reserved balance of an account.
+	pub fn reserved_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
+		Self::account(who.borrow()).reserved
+	}
+
+	/// Get the maximum amount that `who` can withdraw/transfer successfully.
+	///
+	/// This is the free balance minus the largest frozen amount. If `keep_alive` is `true`, or
+	/// the account cannot be killed because other modules hold references to it, the amount
+	/// needed to keep the account above the existential deposit is subtracted as well.
+	pub fn reducible_balance(who: &T::AccountId, keep_alive: bool) -> T::Balance {
+		let a = Self::account(who);
+		// Liquid balance is what is neither reserved nor locked/frozen.
+		let liquid = a.free.saturating_sub(a.fee_frozen.max(a.misc_frozen));
+		if !keep_alive && !system::Pallet::<T>::is_provider_required(who) {
+			liquid
+		} else {
+			// `must_remain_to_exist` is the part of liquid balance which must remain to keep total over ED.
+			let must_remain_to_exist = Self::existential_deposit_for(who).saturating_sub(a.total() - liquid);
+			liquid.saturating_sub(must_remain_to_exist)
+		}
+	}
+
+	/// Get the amount `who` can transfer right now while keeping the account alive.
+	///
+	/// Backs the `transferable_balance` runtime API.
+	pub fn transferable_balance(who: &T::AccountId) -> T::Balance {
+		Self::reducible_balance(who, true)
+	}
+
+	/// Dispatch `transfer` of `value` from `from` to `to` and roll it back, returning exactly what a
+	/// real submission would, e.g. `ExistentialDeposit` if `to` wouldn't be created. The
+	/// transaction fee isn't charged, so whether `from` can pay it isn't covered.
+	///
+	/// Backs the `dry_run_transfer` runtime API.
+	pub fn dry_run_transfer(from: T::AccountId, to: T::AccountId, value: T::Balance) -> DispatchResult {
+		frame_support::storage::with_transaction(|| {
+			let origin = system::RawOrigin::Signed(from).into();
+			let result = Self::transfer(origin, T::Lookup::unlookup(to), value);
+			frame_support::storage::TransactionOutcome::Rollback(result.map(|_| ()).map_err(|e| e.error))
+		})
+	}
+
+	/// Get up to `limit` accounts whose total balance is above `threshold`, with that balance,
+	/// in the order the accounts are stored in, starting after `start_key`. `limit` is bound by
+	/// `MAX_ACCOUNTS_ABOVE`, and no more than `MAX_ACCOUNTS_SCANNED` accounts are looked at, so
+	/// fewer than `limit` may come back even though there are more to find.
+	///
+	/// The account returned with them is the `start_key` to continue from, unless there are no
+	/// more accounts to look at. As the order is that of the hashed keys, it stays right even if
+	/// `start_key` is reaped in the meantime.
+	///
+	/// Backs the `accounts_above` runtime API.
+	pub fn accounts_above(
+		threshold: T::Balance,
+		start_key: Option<T::AccountId>,
+		limit: u32,
+	) -> (Vec<(T::AccountId, T::Balance)>, Option<T::AccountId>) {
+		let limit = cmp::min(limit, MAX_ACCOUNTS_ABOVE) as usize;
+		if limit == 0 { return (Vec::new(), start_key) }
+		let prefix = system::Account::<T>::final_prefix();
+		let mut key = start_key.map_or_else(
+			|| prefix.to_vec(),
+			|who| system::Account::<T>::hashed_key_for(&who),
+		);
+		let mut accounts = Vec::new();
+		let mut scanned = 0;
+		while let Some(next) = sp_io::storage::next_key(&key).filter(|k| k.starts_with(&prefix)) {
+			key = next;
+			scanned += 1;
+			// `system::Account` is keyed with `Blake2_128Concat`, so the account follows its hash.
+			let who = key.get(prefix.len() + 16..)
+				.and_then(|mut raw| T::AccountId::decode(&mut raw).ok());
+			if let Some(who) = who {
+				let total = Self::total_balance(&who);
+				if total > threshold {
+					accounts.push((who.clone(), total));
+					if accounts.len() == limit { return (accounts, Some(who)) }
+				}
+				if scanned >= MAX_ACCOUNTS_SCANNED { return (accounts, Some(who)) }
+			}
+		}
+		(accounts, None)
+	}
+
+	/// Get the free, reserved, frozen, transferable and total balance of `who` in one go.
+	///
+	/// Backs the `balance_summary` runtime API.
+	pub fn balance_summary(who: &T::AccountId) -> BalanceSummary<T::Balance> {
+		let account = Self::account(who);
+		BalanceSummary {
+			free: account.free,
+			reserved: account.reserved,
+			frozen: account.misc_frozen.max(account.fee_frozen),
+			transferable: Self::transferable_balance(who),
+			total: account.total(),
+		}
+	}
+
+	/// Check the invariants tying the balances together, for `try-runtime` and for tests to run
+	/// after each scenario:
+	/// - the total issuance is the sum of the free and reserved balance of all accounts;
+	/// - the named reserves of each account fit in its reserved balance;
+	/// - the frozen balances of each account are the ones its locks imply.
+	///
+	/// Locks may exceed the free balance, e.g. after a slash, so that isn't an error. Every
+	/// account holding a balance is known to the system pallet, whichever `AccountStore` keeps
+	/// the balance. This iterates over all accounts, so it must not be called on chain.
+	pub fn do_try_state() -> Result<(), &'static str> {
+		let mut total = T::Balance::zero();
+		for (who, _) in system::Account::<T>::iter() {
+			let account = Self::account(&who);
+			total = total.checked_add(&account.total()).ok_or("the balances overflow")?;
+
+			let named = Self::reserves(&who).iter()
+				.try_fold(T::Balance::zero(), |named, data| named.checked_add(&data.amount))
+				.ok_or("the named reserves overflow")?;
+			ensure!(named <= account.reserved, "the named reserves exceed the reserved balance");
+
+			let (misc_frozen, fee_frozen) = Self::frozen_by_locks(&Self::locks(&who));
+			ensure!(
+				account.misc_frozen == misc_frozen && account.fee_frozen == fee_frozen,
+				"the frozen balances don't match the locks",
+			);
+		}
+		ensure!(total == TotalIssuance::<T, I>::get(), "the total issuance doesn't match the balances");
+		Ok(())
+	}
+
+	/// Get how much can still be minted before the total issuance reaches `MaxIssuance`. Burns
+	/// free up capacity again.
+	pub fn remaining_issuance_capacity() -> T::Balance {
+		T::MaxIssuance::get().saturating_sub(TotalIssuance::<T, I>::get())
+	}
+
+	/// Get how much can be credited before the total issuance overflows. Resolving an existing
+	/// imbalance isn't held to `MaxIssuance`, as it creates nothing.
+	fn issuance_headroom() -> T::Balance {
+		T::Balance::max_value() - TotalIssuance::<T, I>::get()
+	}
+
+	/// Credit `value` to the free balance of the existing account `who`, regardless of `MaxIssuance`.
+	fn credit_existing(who: &T::AccountId, value: T::Balance) -> Result<PositiveImbalance<T, I>, DispatchError> {
+		if value.is_zero() { return Ok(PositiveImbalance::zero()) }
+
+		Self::try_mutate_account(who, |account, is_new| -> Result<PositiveImbalance<T, I>, DispatchError> {
+			ensure!(!is_new, Error::<T, I>::DeadAccount);
+			ensure!(value <= Self::issuance_headroom(), Error::<T, I>::IssuanceOverflow);
+			account.free = account.free.checked_add(&value).ok_or(Error::<T, I>::Overflow)?;
+			Ok(PositiveImbalance::new(value))
+		})
+	}
+
+	/// Credit `value` to the free balance of `who`, possibly creating it, regardless of `MaxIssuance`.
+	fn credit_creating(who: &T::AccountId, value: T::Balance) -> PositiveImbalance<T, I> {
+		if value.is_zero() { return PositiveImbalance::zero() }
+
+		Self::try_mutate_account(who, |account, is_new| -> Result<PositiveImbalance<T, I>, DispatchError> {
+			let ed = Self::existential_deposit_for(who);
+			ensure!(value >= ed || !is_new, Error::<T, I>::ExistentialDeposit);
+			ensure!(value <= Self::issuance_headroom(), Error::<T, I>::IssuanceOverflow);
+
+			// defensive only: overflow should never happen, however in case it does, then this
+			// operation is a no-op.
+			account.free = match account.free.checked_add(&value) {
+				Some(x) => x,
+				None => return Ok(PositiveImbalance::zero()),
+			};
+
+			Ok(PositiveImbalance::new(value))
+		}).unwrap_or_else(|_| PositiveImbalance::zero())
+	}
+
+	/// Set the total issuance to `new`, emitting `Issued` or `Rescinded` with the change.
+	///
+	/// All changes of the total issuance after genesis go through here, so that its history can
+	/// be rebuilt from these events alone.
+	fn set_total_issuance(new: T::Balance) {
+		let old = TotalIssuance::<T, I>::get();
+		if new > old {
+			Self::deposit_event(Event::Issued(new - old));
+		} else if new < old {
+			Self::deposit_event(Event::Rescinded(old - new));
+		}
+		TotalIssuance::<T, I>::put(new);
+	}
+
+	/// `a + b`, saturating. Balances are expected to never overflow here, so debug builds panic
+	/// on an overflow to surface the logic error, where release builds saturate.
+	fn defensive_saturating_add(a: T::Balance, b: T::Balance) -> T::Balance {
+		debug_assert!(a.checked_add(&b).is_some(), "balance arithmetic overflowed: {:?} + {:?}", a, b);
+		a.saturating_add(b)
+	}
+
+	/// `a - b`, saturating, with the same debug build checks as `defensive_saturating_add`.
+	fn defensive_saturating_sub(a: T::Balance, b: T::Balance) -> T::Balance {
+		debug_assert!(a.checked_sub(&b).is_some(), "balance arithmetic overflowed: {:?} - {:?}", a, b);
+		a.saturating_sub(b)
+	}
+
+	/// Report that `excess` was lost because the total issuance was clamped at its maximum.
+	fn note_issuance_capped(excess: T::Balance) {
+		frame_support::debug::warn!(
+			"Warning: The total issuance was clamped at its maximum, {:?} were not accounted for.",
+			excess,
+		);
+		Self::deposit_event(Event::IssuanceCapped(excess));
+	}
+
+	/// Get the amounts of the free balance of `who` frozen for paying transaction fees and for
+	/// anything else, in that order. These are exactly what `withdraw` enforces for the respective
+	/// reasons; withdrawing for both is bound by the larger of the two.
+	///
+	/// Backs the `frozen_balance_breakdown` runtime API.
+	pub fn frozen_balance_breakdown(who: &T::AccountId) -> (T::Balance, T::Balance) {
+		let a = Self::account(who);
+		(a.frozen(Reasons::Fee), a.frozen(Reasons::Misc))
+	}
+
+	/// Whether `withdraw` of `value` from `who` for `reasons` would succeed, allowing `who` to be
+	/// reaped. This accounts for locks, the existential deposit, the consumers of `who` and fees
+	/// paid from its reserved balance.
+	///
+	/// Backs the `can_withdraw` runtime API.
+	pub fn can_withdraw_for(who: &T::AccountId, value: T::Balance, reasons: WithdrawReasons) -> bool {
+		if value.is_zero() { return true }
+		if Self::is_frozen(who) { return false }
+		let account = Self::account(who);
+		let from_reserved = Self::fee_shortfall(who, &account, value, reasons);
+		if !from_reserved.is_zero() && from_reserved > Self::anonymous_reserve(who, &account) { return false }
+		account.free.checked_sub(&(value - from_reserved)).map_or(false, |new_free| {
+			if Self::ensure_can_withdraw(who, value, reasons, new_free).is_err() { return false }
+			// same existence rules as `post_mutation`.
+			let new_total = new_free.saturating_add(account.reserved - from_reserved);
+			new_total >= Self::existential_deposit_for(who)
+				|| (!new_total.is_zero() && T::DustRemovalWhitelist::contains(who))
+				|| !system::Pallet::<T>::is_provider_required(who)
+		})
+	}
+
+	/// The total issuance minus the free and reserved balances of the `SystemAccounts`.
+	///
+	/// Backs the `circulating_supply` runtime API. It isn't cached, as the few system accounts
+	/// change balance in most blocks while this is only ever queried off-chain.
+	pub fn circulating_supply() -> T::Balance {
+		let held = T::SystemAccounts::get().iter()
+			.fold(Zero::zero(), |held: T::Balance, who| held.saturating_add(Self::total_balance(who)));
+		Self::total_issuance().saturating_sub(held)
+	}
+
+	/// What transferring `value` from `from` to `to` with `AllowDeath` would do to both accounts,
+	/// without changing any state.
+	///
+	/// Backs the `simulate_transfer` runtime API. The transfer is assumed to go ahead, i.e. it
+	/// doesn't check for locks, `TransferFilter` or whether `from` can afford it.
+	pub fn simulate_transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		value: T::Balance,
+	) -> SimulatedTransfer<T::Balance> {
+		if value.is_zero() || from == to { return Default::default() }
+
+		let remaining = Self::account(from).total().saturating_sub(value);
+		let sender_reaped = remaining < Self::existential_deposit_for(from)
+			&& (remaining.is_zero() || !T::DustRemovalWhitelist::contains(from));
+		SimulatedTransfer {
+			sender_reaped,
+			dust_lost: if sender_reaped { remaining } else { Zero::zero() },
+			recipient_created: Self::account(to).total().is_zero(),
+		}
+	}
+
+	/// The weight of `ReservableCurrency::repatriate_reserved` into the `status` balance of the
+	/// beneficiary, for pallets to charge for it.
+	pub fn repatriate_reserved_weight(status: Status) -> Weight {
+		match status {
+			Status::Free => T::WeightInfo::repatriate_reserved_free(),
+			Status::Reserved => T::WeightInfo::repatriate_reserved(),
+		}
+	}
+
+	/// Get the balances of an account, all at once.
+	///
+	/// Backs the `account_data` runtime API.
+	pub fn account_data(who: &T::AccountId) -> AccountData<T::Balance> {
+		Self::account(who)
+	}
+
+	/// Slash up to `value` from `who`, taking from the reserved balance before the free balance.
+	///
+	/// Returns the negative imbalance created and the part of `value` that could not be slashed.
+	/// Is a no-op if `value` is zero or the account does not exist.
+	pub fn slash_reserved_first(who: &T::AccountId, value: T::Balance) -> (NegativeImbalance<T, I>, T::Balance) {
+		let (mut imbalance, remaining) = <Self as ReservableCurrency<_>>::slash_reserved(who, value);
+		if remaining.is_zero() { return (imbalance, Zero::zero()) }
+		// the reserved balance is exhausted at this point, so this only slashes free balance.
+		let (free_imbalance, remaining) = <Self as Currency<_>>::slash(who, remaining);
+		imbalance.subsume(free_imbalance);
+		(imbalance, remaining)
+	}
+
+	/// Slash up to `total_slash` from the reserve of `who` named `reserve_id` and pay it out to
+	/// the beneficiaries of `shares` in proportion to their weights, e.g. the clients of a
+	/// merchant sharing its collateral. Neither the other reserves of `who` nor its free balance
+	/// are touched.
+	///
+	/// Each share is rounded by `Config::Rounding`, and is paid in the order of `shares`. What the
+	/// rounding leaves is burned, while rounding up may leave the last shares with less, so no
+	/// unit is ever created or lost. A share which can't be credited, e.g. because it wouldn't
+	/// create its beneficiary, is burned too. Returns what each beneficiary was actually paid, in
+	/// the order of `shares`. Nothing is slashed if the weights add up to zero.
+	pub fn slash_pro_rata(
+		reserve_id: &T::ReserveIdentifier,
+		who: &T::AccountId,
+		total_slash: T::Balance,
+		shares: Vec<(T::AccountId, u32)>,
+	) -> Vec<(T::AccountId, T::Balance)> {
+		let total_weight = shares.iter().map(|(_, weight)| *weight as u128).sum::<u128>();
+		if total_weight.is_zero() {
+			return shares.into_iter().map(|(beneficiary, _)| (beneficiary, Zero::zero())).collect()
+		}
+		let (mut imbalance, _) =
+			<Self as NamedReservableCurrency<_>>::slash_reserved_named(reserve_id, who, total_slash);
+		let slashed = imbalance.peek().saturated_into::<u128>();
+
+		let rounding = T::Rounding::get();
+		// `imbalance` is dropped at the end, burning whatever the rounding left.
+		shares.into_iter().map(|(beneficiary, weight)| {
+			let amount = rounding.apply_rational(slashed, weight as u128, total_weight).saturated_into();
+			let (share, rest) = imbalance.split(amount);
+			imbalance = rest;
+			let credited = Self::credit_creating(&beneficiary, share.peek());
+			let amount = credited.peek();
+			// dropping what couldn't be credited reduces the total issuance.
+			drop(share.offset(credited));
+			(beneficiary, amount)
+		}).collect()
+	}
+
+	/// Same as `Currency::deposit_into_existing`, but on failure the undeposited `value` is handed
+	/// back along with the error, so that the caller can pay it elsewhere.
+	///
+	/// A payout to an account which was reaped in the meantime fails with `DeadAccount`.
+	pub fn try_deposit(
+		who: &T::AccountId,
+		value: T::Balance,
+	) -> Result<PositiveImbalance<T, I>, (T::Balance, DispatchError)> {
+		<Self as Currency<_>>::deposit_into_existing(who, value).map_err(|e| (value, e))
+	}
+
+	/// Bring the reserved balance of `who` to exactly `target`, reserving the difference from the
+	/// free balance when below it and unreserving the surplus when above it.
+	///
+	/// Fails if there isn't enough free balance to reserve. Emits `Reserved` or `Unreserved` with
+	/// the difference, or nothing if the reserved balance is already at `target`.
+	pub fn ensure_reserved(who: &T::AccountId, target: T::Balance) -> DispatchResult {
+		let reserved = Self::reserved_balance(who);
+		if reserved < target {
+			<Self as ReservableCurrency<_>>::reserve(who, target - reserved)
+		} else {
+			<Self as ReservableCurrency<_>>::unreserve(who, reserved - target);
+			Ok(())
+		}
+	}
+
+	/// Move reserved balance of `slashed` to `beneficiary`: `to_free` lands in their free balance
+	/// and `to_reserved` in their reserved balance. The free portion is covered first.
+	///
+	/// Returns the part of `to_free + to_reserved` that could not be covered by the reserves of
+	/// `slashed`. If `beneficiary` doesn't exist, it is only created if the moved amount reaches
+	/// the existential deposit; otherwise nothing is moved, as when the move would overflow.
+	pub fn repatriate_reserved_split(
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		to_free: T::Balance,
+		to_reserved: T::Balance,
+	) -> T::Balance {
+		let value = to_free.saturating_add(to_reserved);
+		if value.is_zero() { return Zero::zero() }
+
+		if slashed == beneficiary {
+			let free_shortfall = Self::do_unreserve(slashed, to_free, Zero::zero()).0;
+			return free_shortfall.saturating_add(to_reserved.saturating_sub(Self::reserved_balance(slashed)))
+		}
+
+		let result = Self::try_mutate_account_with_dust(
+			beneficiary,
+			|to_account, is_new| -> Result<((T::Balance, T::Balance), DustCleaner<T, I>), DispatchError> {
+				Self::try_mutate_account_with_dust(
+					slashed,
+					|from_account, _| -> Result<(T::Balance, T::Balance), DispatchError> {
+						let free = cmp::min(from_account.reserved, to_free);
+						let reserved = cmp::min(from_account.reserved - free, to_reserved);
+						ensure!(
+							!is_new || free + reserved >= Self::existential_deposit_for(beneficiary),
+							Error::<T, I>::ExistentialDeposit,
+						);
+						to_account.free = to_account.free.checked_add(&free).ok_or(Error::<T, I>::Overflow)?;
+						to_account.reserved = to_account.reserved
+							.checked_add(&reserved)
+							.ok_or(Error::<T, I>::Overflow)?;
+						from_account.reserved -= free + reserved;
+						Ok((free, reserved))
+					}
+				)
+			}
+		);
+
+		match result {
+			Ok((((free, reserved), _maybe_one_dust), _maybe_other_dust)) => {
+				if !free.is_zero() {
+					Self::deposit_event(Event::ReserveRepatriated(slashed.clone(), beneficiary.clone(), free, Status::Free));
+				}
+				if !reserved.is_zero() {
+					Self::deposit_event(
+						Event::ReserveRepatriated(slashed.clone(), beneficiary.clone(), reserved, Status::Reserved)
+					);
+				}
+				value - free - reserved
+			},
+			Err(_) => value,
+		}
+	}
+
+	/// Get the lock with the given `id` on `who`, if there is one.
+	pub fn lock_by_id(who: &T::AccountId, id: LockIdentifier) -> Option<BalanceLock<T::Balance, T::BlockNumber>> {
+		Self::locks(who).into_iter().find(|lock| lock.id == id)
+	}
+
+	/// The providers, consumers and sufficients reference counts of `who`, in that order.
+	///
+	/// Backs the `account_refs` runtime API. The system pallet doesn't track sufficients yet, so
+	/// that count is always zero.
+	pub fn account_refs(who: &T::AccountId) -> (u32, u32, u32) {
+		(system::Pallet::<T>::providers(who), system::Pallet::<T>::consumers(who), 0)
+	}
+
+	/// The balance below which `who` is reaped: `FeeOnlyMinimum` for the `FeeOnlyAccounts`,
+	/// provided it is lower, and the existential deposit for everyone else.
+	pub fn existential_deposit_for(who: &T::AccountId) -> T::Balance {
+		let ed = Self::existential_deposit();
+		if T::FeeOnlyAccounts::contains(who) {
+			// a zero minimum would keep empty accounts around.
+			ed.min(T::FeeOnlyMinimum::get()).max(One::one())
+		} else {
+			ed
+		}
+	}
+
+	/// The part of the balance under the vesting schedule of `who` which is vested by now,
+	/// whether or not `claim_vested` was called for it. Zero once the schedule is gone.
+	///
+	/// Backs the `vested_balance` runtime API.
+	pub fn vested_balance(who: &T::AccountId) -> T::Balance {
+		Vesting::<T, I>::get(who).map_or_else(Zero::zero, |schedule| {
+			schedule.locked - schedule.locked_at(system::Pallet::<T>::block_number())
+		})
+	}
+
+	/// The sub-account `sub` of the pallet whose `ModuleId` is `pallet_id`.
+	pub fn sub_account(pallet_id: [u8; 8], sub: &[u8]) -> T::AccountId {
+		ModuleId(pallet_id).into_sub_account(sub)
+	}
+
+	/// Whether `who` would be reaped once its balance is gone, i.e. it holds no locks, nothing
+	/// consumes it and no other pallet provides for it.
+	///
+	/// Backs the `is_reapable` runtime API.
+	pub fn is_reapable(who: &T::AccountId) -> bool {
+		let (providers, consumers, _) = Self::account_refs(who);
+		Self::locks(who).is_empty() && consumers.is_zero() && providers <= 1
+	}
+
+	/// Slash up to `amount` of the collateral `who` reserved under `reserve_id`, e.g. for a market
+	/// order, returning the slashed imbalance and the shortfall which the reserve couldn't cover.
+	///
+	/// Only that named reserve is slashed, never the free balance of `who` or its other reserves.
+	pub fn slash_collateral(
+		who: &T::AccountId,
+		reserve_id: &T::ReserveIdentifier,
+		amount: T::Balance,
+	) -> (NegativeImbalance<T, I>, T::Balance) {
+		<Self as NamedReservableCurrency<_>>::slash_reserved_named(reserve_id, who, amount)
+	}
+
+	/// Slash the entire free and reserved balance of `who` and reap the account, returning the
+	/// slashed imbalance for the caller to route.
+	///
+	/// Meant for permanently banned validators. The locks, named reserves and everything else this
+	/// pallet keeps for `who` are removed. Only the consumer reference of the locks is released,
+	/// so staking must first run its own cleanup of `who` (e.g. `kill_stash`), dropping its
+	/// references; fails with `CannotReap` and changes nothing while other pallets still consume
+	/// `who`. `ReapFailed` is emitted if another pallet providing for `who` keeps it alive.
+	pub fn slash_all_and_reap(who: &T::AccountId) -> Result<NegativeImbalance<T, I>, DispatchError> {
+		let own_consumers = if Self::locks(who).is_empty() { 0 } else { 1 };
+		if system::Pallet::<T>::consumers(who) > own_consumers
+			&& system::Pallet::<T>::providers(who) <= 1
+		{
+			Self::deposit_event(Event::ReapFailed(who.clone(), ReapBlocker::ConsumerRefs));
+			return Err(Error::<T, I>::CannotReap.into());
+		}
+		// The consumer reference of the locks goes with them.
+		Self::update_locks(who, &[]);
+		Reserves::<T, I>::remove(who);
+		ReserveExpiries::<T, I>::remove(who);
+		Vesting::<T, I>::remove(who);
+		Claimable::<T, I>::remove(who);
+		FrozenAccounts::<T, I>::remove(who);
+		FeeFromReserved::<T, I>::remove(who);
+		AccountTags::<T, I>::remove(who);
+		let total = Self::total_balance(who);
+		Self::mutate_account(who, |account| {
+			account.free = Zero::zero();
+			account.reserved = Zero::zero();
+		})?;
+		Self::note_unreaped(who.clone());
+		Ok(NegativeImbalance::new(total))
+	}
+
+	/// Set the free and reserved balances of `who`, as `set_balance` does.
+	fn do_set_balance(who: T::AccountId, new_free: T::Balance, new_reserved: T::Balance) -> DispatchResult {
+		let wipeout = new_free.saturating_add(new_reserved) < Self::existential_deposit_for(&who);
+		let new_free = if wipeout { Zero::zero() } else { new_free };
+		let new_reserved = if wipeout { Zero::zero() } else { new_reserved };
+		if wipeout {
+			Self::ensure_can_reap(&who)?;
+		}
+
+		let (free, reserved) = Self::mutate_account(&who, |account| {
+			if new_free > account.free {
+				mem::drop(PositiveImbalance::<T, I>::new(new_free - account.free));
+			} else if new_free < account.free {
+				mem::drop(NegativeImbalance::<T, I>::new(account.free - new_free));
+			}
+
+			if new_reserved > account.reserved {
+				mem::drop(PositiveImbalance::<T, I>::new(new_reserved - account.reserved));
+			} else if new_reserved < account.reserved {
+				mem::drop(NegativeImbalance::<T, I>::new(account.reserved - new_reserved));
+			}
+
+			account.free = new_free;
+			account.reserved = new_reserved;
+
+			(account.free, account.reserved)
+		})?;
+		Self::deposit_event(Event::BalanceSet(who.clone(), free, reserved));
+		if wipeout {
+			Self::note_unreaped(who);
+		}
+		Ok(())
+	}
+
+	/// `balance` plus `delta`, failing with `AdjustmentUnderflow` below zero and with `Overflow`
+	/// beyond what a balance can hold.
+	fn apply_delta(balance: T::Balance, delta: i128) -> Result<T::Balance, DispatchError> {
+		let magnitude = T::Balance::try_from(delta.unsigned_abs()).ok();
+		if delta < 0 {
+			magnitude.and_then(|m| balance.checked_sub(&m)).ok_or_else(|| Error::<T, I>::AdjustmentUnderflow.into())
+		} else {
+			magnitude.and_then(|m| balance.checked_add(&m)).ok_or_else(|| Error::<T, I>::Overflow.into())
+		}
+	}
+
+	/// Fail with `CannotReap` if wiping out the balance of `who` can't reap the account, emitting
+	/// `ReapFailed` with the reason. Another pallet providing for `who` does not block it.
+	fn ensure_can_reap(who: &T::AccountId) -> DispatchResult {
+		let blocker = if !Self::locks(who).is_empty() {
+			Some(ReapBlocker::Locks)
+		} else if !system::Pallet::<T>::consumers(who).is_zero()
+			&& system::Pallet::<T>::providers(who) <= 1
+		{
+			Some(ReapBlocker::ConsumerRefs)
+		} else {
+			None
+		};
+		if let Some(blocker) = blocker {
+			Self::deposit_event(Event::ReapFailed(who.clone(), blocker));
+			return Err(Error::<T, I>::CannotReap.into());
+		}
+		Ok(())
+	}
+
+	/// Emit `ReapFailed` if `who` outlived having its balance wiped out because other pallets
+	/// still provide for it.
+	fn note_unreaped(who: T::AccountId) {
+		if system::Pallet::<T>::providers(&who) > 0 {
+			Self::deposit_event(Event::ReapFailed(who, ReapBlocker::ProviderRefs));
+		}
+	}
+
+	/// Get both the free and reserved balances of an account.
+	fn account(who: &T::AccountId) -> AccountData<T::Balance> {
+		T::AccountStore::get(&who)
+	}
+
+	/// Handles any steps needed after mutating an account.
+	///
+	/// This includes DustRemoval unbalancing, in the case than the `new` account's total balance
+	/// is non-zero but below ED and the account is not in `DustRemovalWhitelist`.
+	///
+	/// Returns two values:
+	/// - `Some` containing the the `new` account, iff the account has sufficient balance or is
+	///   whitelisted with a non-zero balance.
+	/// - `Some` containing the dust to be dropped, iff some dust should be dropped.
+	fn post_mutation(
+		who: &T::AccountId,
+		new: AccountData<T::Balance>,
+	) -> (Option<AccountData<T::Balance>>, Option<NegativeImbalance<T, I>>) {
+		let total = new.total();
+		if total < Self::existential_deposit_for(who) {
+			if total.is_zero() {
+				(None, None)
+			} else if T::DustRemovalWhitelist::contains(who) {
+				(Some(new), None)
+			} else {
+				(None, Some(NegativeImbalance::new(total)))
+			}
+		} else {
+			(Some(new), None)
+		}
+	}
+
+	/// Mutate an account to some new value, or delete it entirely with `None`. Will enforce
+	/// `ExistentialDeposit` law, annulling the account as needed.
+	///
+	/// NOTE: Doesn't do any preparatory work for creating a new account, so should only be used
+	/// when it is known that the account already exists.
+	///
+	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
+	/// the caller will do this.
+	pub fn mutate_account<R>(
+		who: &T::AccountId,
+		f: impl FnOnce(&mut AccountData<T::Balance>) -> R
+	) -> Result<R, StoredMapError> {
+		Self::try_mutate_account(who, |a, _| -> Result<R, StoredMapError> { Ok(f(a)) })
+	}
+
+	/// Mutate an account to some new value, or delete it entirely with `None`. Will enforce
+	/// `ExistentialDeposit` law, annulling the account as needed. This will do nothing if the
+	/// result of `f` is an `Err`.
+	///
+	/// NOTE: Doesn't do any preparatory work for creating a new account, so should only be used
+	/// when it is known that the account already exists.
+	///
+	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
+	/// the caller will do this.
+	fn try_mutate_account<R, E: From<StoredMapError>>(
+		who: &T::AccountId,
+		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> Result<R, E>
+	) -> Result<R, E> {
+		Self::try_mutate_account_with_dust(who, f)
+			.map(|(result, dust_cleaner)| {
+				drop(dust_cleaner);
+				result
+			})
+	}
+
+	/// Mutate an account to some new value, or delete it entirely with `None`. Will enforce
+	/// `ExistentialDeposit` law, annulling the account as needed. This will do nothing if the
+	/// result of `f` is an `Err`.
+	///
+	/// It returns both the result from the closure, and an optional `DustCleaner` instance which
+	/// should be dropped once it is known that all nested mutates that could affect storage items
+	/// what the dust handler touches have completed.
+	///
+	/// NOTE: Doesn't do any preparatory work for creating a new account, so should only be used
+	/// when it is known that the account already exists.
+	///
+	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
+	/// the caller will do this.
+	fn try_mutate_account_with_dust<R, E: From<StoredMapError>>(
+		who: &T::AccountId,
+		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> Result<R, E>
+	) -> Result<(R, DustCleaner<T, I>), E> {
+		Self::try_mutate_account_with_dust_by(who, None, f)
+	}
+
+	/// Same as `try_mutate_account_with_dust`, but if the account is created then `by` is recorded
+	/// as its funding source in the `Endowed` event.
+	fn try_mutate_account_with_dust_by<R, E: From<StoredMapError>>(
+		who: &T::AccountId,
+		by: Option<&T::AccountId>,
+		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> Result<R, E>
+	) -> Result<(R, DustCleaner<T, I>), E> {
+		let result = T::AccountStore::try_mutate_exists(who, |maybe_account| {
+			let is_new = maybe_account.is_none();
+			let mut account = maybe_account.take().unwrap_or_default();
+			let before = (account.free, account.reserved);
+			f(&mut account, is_new).map(move |result| {
+				let touched = before != (account.free, account.reserved);
+				let maybe_endowed = if is_new { Some(account.free) } else { None };
+				let maybe_account_maybe_dust = Self::post_mutation(who, account);
+				*maybe_account = maybe_account_maybe_dust.0;
+				let exists = maybe_account.is_some();
+				let reserved_after = maybe_account.as_ref().map_or_else(Zero::zero, |a| a.reserved);
+				let reserved = (before.1, reserved_after);
+				(maybe_endowed, maybe_account_maybe_dust.1, !is_new, exists, touched, reserved, result)
+			})
+		});
+		result.map(|(maybe_endowed, maybe_dust, existed, exists, touched, reserved, result)| {
+			if let Some(endowed) = maybe_endowed {
+				Self::deposit_event(Event::Endowed(who.clone(), endowed, by.cloned()));
+			}
+			if reserved.0 != reserved.1 {
+				T::OnReservedChanged::on_reserved_changed(who, reserved.0, reserved.1);
+			}
+			if existed && !exists {
+				LastActive::<T, I>::remove(who);
+				ReserveFloor::<T, I>::remove(who);
+				Claimable::<T, I>::remove(who);
+				Vesting::<T, I>::remove(who);
+			} else if touched {
+				LastActive::<T, I>::insert(who, system::Pallet::<T>::block_number());
+			}
+			// Only actual transitions of existence are reported, so mutations which leave the
+			// account alive (or dead) never fire the hooks again.
+			match (existed, exists) {
+				(false, true) => <T as Config<I>>::OnNewAccount::on_new_account(who),
+				(true, false) => <T as Config<I>>::OnKilledAccount::on_killed_account(who),
+				_ => {},
+			}
+			let dust_cleaner = DustCleaner(maybe_dust.map(|dust| (who.clone(), dust)));
+			(result, dust_cleaner)
+		})
+	}
+
+	/// Same as `LockableCurrency::set_lock`, but fails with `TooManyLocks` instead of dropping a
+	/// lock with a new id when `who` already has `MaxLocks` locks, and with `WouldLockOutFees`
+	/// instead of dropping a lock which would take the `FeeReserve`.
+	pub fn try_set_lock(
+		id: LockIdentifier,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::do_set_lock(id, who, amount, reasons, None)
+	}
+
+	/// Same as `try_set_lock`, but the lock expires at block `until`, from which it no longer has
+	/// any effect once pruned. It is pruned at the initialization of that block at the latest.
+	///
+	/// If `until` is not in the future then any lock under `id` is simply removed.
+	pub fn try_set_lock_until(
+		id: LockIdentifier,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+		until: T::BlockNumber,
+	) -> DispatchResult {
+		if until <= system::Pallet::<T>::block_number() {
+			<Self as LockableCurrency<_>>::remove_lock(id, who);
+			return Ok(())
+		}
+		Self::do_set_lock(id, who, amount, reasons, Some(until))?;
+		LockExpiries::<T, I>::mutate(until, |accounts| if !accounts.contains(who) {
+			accounts.push(who.clone())
+		});
+		Ok(())
+	}
 
-	/// Any liquidity locks on some account balances.
-	/// NOTE: Should only be accessed when setting, changing and freeing a lock.
-	#[pallet::storage]
-	#[pallet::getter(fn locks)]
-	pub type Locks<T: Config<I>, I: 'static = ()> = StorageMap<
-		_,
-		Blake2_128Concat,
-		T::AccountId,
-		Vec<BalanceLock<T::Balance>>,
-		ValueQuery
-	>;
+	/// Same as `reserve_named`, but `value` is unreserved again once block `until` is reached, the
+	/// next time the named reserves of `who` change or `release_expired_reserves` is called.
+	///
+	/// Reserving more under `id` with an expiry adds to the value to unreserve and keeps the later
+	/// of both expiries. If `until` is not in the future then nothing is reserved.
+	pub fn reserve_with_expiry(
+		id: &T::ReserveIdentifier,
+		who: &T::AccountId,
+		value: T::Balance,
+		until: T::BlockNumber,
+	) -> DispatchResult {
+		if value.is_zero() || until <= system::Pallet::<T>::block_number() {
+			return Ok(())
+		}
+		<Self as NamedReservableCurrency<_>>::reserve_named(id, who, value)?;
+		ReserveExpiries::<T, I>::mutate(who, |expiries| {
+			match expiries.iter_mut().find(|(expiring, _, _)| expiring == id) {
+				Some((_, expiry, expiring_value)) => {
+					*expiry = cmp::max(*expiry, until);
+					*expiring_value = expiring_value.saturating_add(value);
+				},
+				None => expiries.push((*id, until, value)),
+			}
+		});
+		Ok(())
+	}
 
-	/// Storage version of the pallet.
+	/// Mint the `InflationPerBlock` to `InflationAccount`, or as much of it as `MaxIssuance` still
+	/// allows, returning the weight it took.
 	///
-	/// This is set to v2.0.0 for new networks.
-	#[pallet::storage]
-	pub(super) type StorageVersion<T: Config<I>, I: 'static = ()> = StorageValue<
-		_,
-		Releases,
-		ValueQuery
-	>;
+	/// Nothing is minted while `InflationAccount` doesn't exist and the amount is below its
+	/// existential deposit.
+	fn mint_inflation() -> Weight {
+		let per_block = InflationPerBlock::<T, I>::get();
+		if per_block.is_zero() { return T::DbWeight::get().reads(1) }
+		let capacity = Self::remaining_issuance_capacity();
+		if per_block > capacity {
+			Self::deposit_event(Event::IssuanceCapReached(per_block - capacity));
+		}
+		let amount = cmp::min(per_block, capacity);
+		// dropping the imbalance is what increases the total issuance.
+		let minted = <Self as Currency<_>>::deposit_creating(&T::InflationAccount::get(), amount);
+		if !minted.peek().is_zero() {
+			Self::deposit_event(Event::InflationMinted(minted.peek()));
+		}
+		drop(minted);
+		T::WeightInfo::on_initialize_inflation()
+	}
 
-	#[pallet::genesis_config]
-	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
-		pub balances: Vec<(T::AccountId, T::Balance)>,
+	/// Same as `reserve`, but returning the reserved balance of `who` afterwards, which spares
+	/// reading it again.
+	pub fn reserve_returning(
+		who: &T::AccountId,
+		value: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		Self::do_reserve(who, value, true)
 	}
 
-	#[cfg(feature = "std")]
-	impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
-		fn default() -> Self {
-			Self {
-				balances: Default::default(),
-			}
+	/// Keep `unreserve` and `unreserve_named` from taking the reserved balance of `who` below
+	/// `floor`, for pallets whose collateral must not momentarily drop below a minimum, e.g. a
+	/// bond. What would go below it is left reserved and returned as not unreserved. A zero
+	/// `floor` removes it; it is also removed when `who` is reaped.
+	pub fn set_reserve_floor(who: &T::AccountId, floor: T::Balance) {
+		if floor.is_zero() {
+			ReserveFloor::<T, I>::remove(who);
+		} else {
+			ReserveFloor::<T, I>::insert(who, floor);
 		}
 	}
 
-	#[pallet::genesis_build]
-	impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
-		fn build(&self) {
-			let total = self.balances
-				.iter()
-				.fold(Zero::zero(), |acc: T::Balance, &(_, n)| acc + n);
-			<TotalIssuance<T, I>>::put(total);
+	/// Same as `unreserve`, but returning the reserved balance of `who` afterwards rather than the
+	/// amount that could not be unreserved.
+	pub fn unreserve_returning(who: &T::AccountId, value: T::Balance) -> T::Balance {
+		Self::do_unreserve(who, value, ReserveFloor::<T, I>::get(who)).1
+	}
 
-			<StorageVersion<T, I>>::put(Releases::V2_0_0);
+	/// Withdraw `fee` and `tip` from `who` at once, returning them as two imbalances so that they
+	/// can go to different handlers. The balance and the locks are checked once against their sum,
+	/// as a single `withdraw` keeping `who` alive.
+	pub fn withdraw_split(
+		who: &T::AccountId,
+		fee: T::Balance,
+		tip: T::Balance,
+		reasons: WithdrawReasons,
+	) -> Result<(NegativeImbalance<T, I>, NegativeImbalance<T, I>), DispatchError> {
+		let total = fee.checked_add(&tip).ok_or(Error::<T, I>::Overflow)?;
+		let imbalance = <Self as Currency<_>>::withdraw(who, total, reasons, KeepAlive)?;
+		Ok(imbalance.split(fee))
+	}
 
-			for (_, balance) in &self.balances {
-				assert!(
-					*balance >= <T as Config<I>>::ExistentialDeposit::get(),
-					"the balance of any account should always be at least the existential deposit.",
-				)
-			}
+	/// Make sure that `locks`, replacing those of `who` to change the lock `id`, leave
+	/// `FeeReserve` of the free balance to pay fees with, unless they freeze no more than now.
+	///
+	/// Only a lock freezing both fees and other withdrawals is checked: one freezing other
+	/// withdrawals only leaves fees payable, and one freezing fees only is meant to stop them.
+	fn ensure_fees_payable(
+		who: &T::AccountId,
+		id: LockIdentifier,
+		locks: &[BalanceLock<T::Balance, T::BlockNumber>],
+	) -> DispatchResult {
+		let fee_reserve = T::FeeReserve::get();
+		let freezes_all = locks.iter().any(|l| l.id == id && l.reasons == Reasons::All);
+		if fee_reserve.is_zero() || !freezes_all { return Ok(()) }
+		let account = Self::account(who);
+		let (_, fee_frozen) = Self::frozen_by_locks(locks);
+		ensure!(
+			fee_frozen <= account.fee_frozen || fee_frozen.saturating_add(fee_reserve) <= account.free,
+			Error::<T, I>::WouldLockOutFees,
+		);
+		Ok(())
+	}
 
-			// ensure no duplicates exist.
-			let endowed_accounts = self.balances.iter().map(|(x, _)| x).cloned().collect::<std::collections::BTreeSet<_>>();
+	fn do_set_lock(
+		id: LockIdentifier,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+		until: Option<T::BlockNumber>,
+	) -> DispatchResult {
+		if amount.is_zero() || reasons.is_empty() { return Ok(()) }
+		let mut new_lock = Some(BalanceLock { id, amount, reasons: reasons.into(), until });
+		let mut locks = Self::live_locks(who).into_iter()
+			.filter_map(|l| if l.id == id { new_lock.take() } else { Some(l) })
+			.collect::<Vec<_>>();
+		if let Some(lock) = new_lock {
+			ensure!((locks.len() as u32) < T::MaxLocks::get(), Error::<T, I>::TooManyLocks);
+			locks.push(lock)
+		}
+		Self::ensure_fees_payable(who, id, &locks[..])?;
+		Self::update_locks(who, &locks[..]);
+		Ok(())
+	}
 
-			assert!(endowed_accounts.len() == self.balances.len(), "duplicate balances in genesis.");
+	/// Same as `LockableCurrency::extend_lock`, but fails with `TooManyLocks` instead of dropping
+	/// a lock with a new id when `who` already has `MaxLocks` locks, and with `WouldLockOutFees`
+	/// instead of dropping an extension which would take the `FeeReserve`.
+	pub fn try_extend_lock(
+		id: LockIdentifier,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		if amount.is_zero() || reasons.is_empty() { return Ok(()) }
+		let mut new_lock = Some(BalanceLock { id, amount, reasons: reasons.into(), until: None });
+		let mut locks = Self::live_locks(who).into_iter().filter_map(|l|
+			if l.id == id {
+				// the extended lock never expires, as the extension itself doesn't.
+				new_lock.take().map(|nl| {
+					BalanceLock {
+						id: l.id,
+						amount: l.amount.max(nl.amount),
+						reasons: l.reasons | nl.reasons,
+						until: None,
+					}
+				})
+			} else {
+				Some(l)
+			}).collect::<Vec<_>>();
+		if let Some(lock) = new_lock {
+			ensure!((locks.len() as u32) < T::MaxLocks::get(), Error::<T, I>::TooManyLocks);
+			locks.push(lock)
+		}
+		Self::ensure_fees_payable(who, id, &locks[..])?;
+		Self::update_locks(who, &locks[..]);
+		Ok(())
+	}
 
-			for &(ref who, free) in self.balances.iter() {
-				assert!(T::AccountStore::insert(who, AccountData { free, ..Default::default() }).is_ok());
+	/// Add `additional_reasons` to the lock `id` of `who`, raising its amount to `amount` if that
+	/// is more, or create the lock if there is none.
+	///
+	/// Unlike `set_lock`, which replaces the amount and reasons of the lock, this never weakens
+	/// it, so that subsystems sharing `id` keep each other's protection. It merges the same way
+	/// `extend_lock` does, including that the lock then never expires, and fails as
+	/// `try_extend_lock` does.
+	pub fn amend_lock(
+		id: LockIdentifier,
+		who: &T::AccountId,
+		amount: T::Balance,
+		additional_reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Self::try_extend_lock(id, who, amount, additional_reasons)
+	}
+
+	/// The locks of `who` which haven't expired yet.
+	fn live_locks(who: &T::AccountId) -> Vec<BalanceLock<T::Balance, T::BlockNumber>> {
+		let now = system::Pallet::<T>::block_number();
+		let mut locks = Self::locks(who);
+		locks.retain(|l| !l.is_expired(&now));
+		locks
+	}
+
+	/// Remove the locks of `who` which have expired by `now`, returning how many were removed.
+	fn do_prune_expired_locks(who: &T::AccountId, now: &T::BlockNumber) -> u32 {
+		let mut locks = Self::locks(who);
+		let before = locks.len();
+		locks.retain(|l| !l.is_expired(now));
+		let pruned = (before - locks.len()) as u32;
+		if pruned > 0 {
+			Self::update_locks(who, &locks[..]);
+		}
+		pruned
+	}
+
+	/// Remove the locks of `who` which have expired by `now` or have a zero amount, returning how
+	/// many were removed.
+	fn prune_stale_locks(who: &T::AccountId, now: &T::BlockNumber) -> u32 {
+		let mut locks = Self::locks(who);
+		let before = locks.len();
+		locks.retain(|l| !l.is_expired(now) && !l.amount.is_zero());
+		let pruned = (before - locks.len()) as u32;
+		if pruned > 0 {
+			Self::update_locks(who, &locks[..]);
+		}
+		pruned
+	}
+
+	/// Prune the stale locks of as many accounts as fit in `remaining_weight`, picking up from
+	/// `PruneCursor` and wrapping around once every account was visited. Returns the weight used,
+	/// which never exceeds `remaining_weight`, and is zero if not even one account fits.
+	pub fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let per_account = db_weight.reads(1)
+			.saturating_add(T::WeightInfo::prune_expired_locks(T::MaxLocks::get()));
+		// reading and writing the cursor.
+		let mut used = db_weight.reads_writes(1, 1);
+		if used.saturating_add(per_account) > remaining_weight { return 0 }
+
+		let prefix = Locks::<T, I>::final_prefix();
+		let mut cursor = PruneCursor::<T, I>::get();
+		while used.saturating_add(per_account) <= remaining_weight {
+			let start = cursor.as_ref().map_or(&prefix[..], |key| &key[..]);
+			match sp_io::storage::next_key(start).filter(|k| k.starts_with(&prefix)) {
+				Some(key) => {
+					// `Locks` is keyed with `Blake2_128Concat`, so the account follows its hash.
+					let who = key.get(prefix.len() + 16..)
+						.and_then(|mut raw| T::AccountId::decode(&mut raw).ok());
+					if let Some(who) = who {
+						Self::prune_stale_locks(&who, &now);
+					}
+					used = used.saturating_add(per_account);
+					cursor = Some(key);
+				},
+				None => {
+					cursor = None;
+					break
+				},
 			}
 		}
+		match cursor {
+			Some(key) => PruneCursor::<T, I>::put(key),
+			None => PruneCursor::<T, I>::kill(),
+		}
+		used
 	}
-}
 
-#[cfg(feature = "std")]
-impl<T: Config<I>, I: 'static> GenesisConfig<T, I> {
-	/// Direct implementation of `GenesisBuild::build_storage`.
+	/// The message `other` must sign for `who` to be able to merge it with `merge_accounts`.
 	///
-	/// Kept in order not to break dependency.
-	pub fn build_storage(&self) -> Result<sp_runtime::Storage, String> {
-		<Self as GenesisBuild<T, I>>::build_storage(self)
+	/// It commits to the genesis hash and to the `MergeNonce` of `other`, so a signature can
+	/// neither be replayed on another chain nor once `other` was merged, even if it is since
+	/// funded again.
+	pub fn merge_accounts_payload(who: &T::AccountId, other: &T::AccountId) -> Vec<u8> {
+		let genesis_hash = system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+		(MERGE_ACCOUNTS_PREFIX, genesis_hash, who, other, MergeNonce::<T, I>::get(other)).encode()
 	}
 
-	/// Direct implementation of `GenesisBuild::assimilate_storage`.
+	/// The message a sponsor must sign to pay the fee of the `sponsored_transfer` of `value` from
+	/// `who` to `dest`.
 	///
-	/// Kept in order not to break dependency.
-	pub fn assimilate_storage(
-		&self,
-		storage: &mut sp_runtime::Storage
-	) -> Result<(), String> {
-		<Self as GenesisBuild<T, I>>::assimilate_storage(self, storage)
+	/// It commits to the genesis hash, so it can't be replayed on another chain, and to the nonce
+	/// of `who` as of the dispatch, i.e. one above the nonce the transfer is signed with, so a
+	/// signature only ever sponsors one transaction.
+	pub fn sponsored_transfer_payload(who: &T::AccountId, dest: &T::AccountId, value: T::Balance) -> Vec<u8> {
+		let genesis_hash = system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+		let nonce = system::Pallet::<T>::account_nonce(who);
+		(SPONSORED_TRANSFER_PREFIX, genesis_hash, who, dest, value, nonce).encode()
 	}
-}
 
-/// Simplified reasons for withdrawing balance.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
-pub enum Reasons {
-	/// Paying system transaction fees.
-	Fee = 0,
-	/// Any reason other than paying system transaction fees.
-	Misc = 1,
-	/// Any reason at all.
-	All = 2,
-}
+	/// Pay `SponsoredFeeRefund` to `who` from the reserved balance of `sponsor`, then
+	/// transfer `value` from `who` to `dest`.
+	fn do_sponsored_transfer(
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		dest: &T::AccountId,
+		value: T::Balance,
+	) -> DispatchResult {
+		let refund = T::SponsoredFeeRefund::get();
+		Self::ensure_not_frozen(sponsor)?;
+		ensure!(
+			refund <= Self::anonymous_reserve(sponsor, &Self::account(sponsor)),
+			Error::<T, I>::SponsorInsufficient
+		);
+		// the refund is paid first, as the transfer may reap `who`.
+		let remaining = <Self as ReservableCurrency<_>>::repatriate_reserved(sponsor, who, refund, Status::Free)?;
+		ensure!(remaining.is_zero(), Error::<T, I>::SponsorInsufficient);
+		Self::transfer_charging_creation_fee(who, dest, value, AllowDeath)?;
+		Self::deposit_event(Event::TransferSponsored(sponsor.clone(), who.clone(), refund));
+		Ok(())
+	}
+
+	/// Move the balances and mergeable locks of `other` to `who`, reaping `other`.
+	fn do_merge_accounts(who: &T::AccountId, other: &T::AccountId) -> DispatchResult {
+		Self::ensure_not_frozen(other)?;
+		let other_locks = Self::live_locks(other);
+		ensure!(
+			Self::reserves(other).is_empty() &&
+				other_locks.iter().all(|l| T::MergeableLocks::contains(&l.id)),
+			Error::<T, I>::MergeNotPermitted
+		);
+
+		let mut locks = Self::live_locks(who);
+		for lock in other_locks {
+			if let Some(until) = lock.until {
+				LockExpiries::<T, I>::mutate(until, |accounts| if !accounts.contains(who) {
+					accounts.push(who.clone())
+				});
+			}
+			match locks.iter_mut().find(|l| l.id == lock.id) {
+				// both locks keep holding the funds they held before the merge.
+				Some(l) => {
+					l.amount = l.amount.saturating_add(lock.amount);
+					l.reasons = l.reasons | lock.reasons;
+					l.until = match (l.until, lock.until) {
+						(Some(a), Some(b)) => Some(a.max(b)),
+						_ => None,
+					};
+				},
+				None => {
+					ensure!((locks.len() as u32) < T::MaxLocks::get(), Error::<T, I>::TooManyLocks);
+					locks.push(lock);
+				},
+			}
+		}
+		Self::update_locks(who, &locks[..]);
+		Self::update_locks(other, &[]);
+		ensure!(system::Pallet::<T>::consumers(other).is_zero(), Error::<T, I>::MergeNotPermitted);
+
+		let moved = Self::account(other);
+		Self::try_mutate_account(who, |account, _| -> DispatchResult {
+			account.free = account.free.checked_add(&moved.free).ok_or(Error::<T, I>::Overflow)?;
+			account.reserved = account.reserved.checked_add(&moved.reserved).ok_or(Error::<T, I>::Overflow)?;
+			Ok(())
+		})?;
+		Self::try_mutate_account(other, |account, _| -> DispatchResult {
+			account.free = Zero::zero();
+			account.reserved = Zero::zero();
+			Ok(())
+		})?;
+		Claimable::<T, I>::remove(other);
+		MergeNonce::<T, I>::mutate(other, |nonce| *nonce = nonce.saturating_add(1));
+		Ok(())
+	}
+
+	/// Ensure the `TransferFilter` lets `who` transfer `value`.
+	fn ensure_transfer_allowed(who: &T::AccountId, value: T::Balance) -> DispatchResult {
+		ensure!(T::TransferFilter::contains(&(who.clone(), value)), Error::<T, I>::TransferBlocked);
+		Ok(())
+	}
+
+	/// Ensure the `DestinationFilter` lets `who` transfer to `dest`.
+	fn ensure_destination_allowed(who: &T::AccountId, dest: &T::AccountId) -> DispatchResult {
+		ensure!(
+			T::DestinationFilter::contains(&(who.clone(), dest.clone())),
+			Error::<T, I>::DestinationNotAllowed,
+		);
+		Ok(())
+	}
+
+	/// Fail with `TransfersPaused` if `pause_transfers` is in effect.
+	fn ensure_transfers_not_paused() -> DispatchResult {
+		ensure!(!Self::transfers_paused(), Error::<T, I>::TransfersPaused);
+		Ok(())
+	}
+
+	/// Reap `who`, which must hold a non-zero total balance below the existential deposit and be
+	/// otherwise unused, handing the whole balance to `DustRemoval`.
+	fn do_reap_account(who: &T::AccountId) -> DispatchResult {
+		let total = Self::total_balance(who);
+		ensure!(
+			!total.is_zero()
+				&& total < Self::existential_deposit_for(who)
+				&& !T::DustRemovalWhitelist::contains(who)
+				&& Self::locks(who).is_empty()
+				&& Self::reserves(who).is_empty()
+				&& system::Pallet::<T>::consumers(who).is_zero()
+				&& system::Pallet::<T>::providers(who) <= 1,
+			Error::<T, I>::AccountNotReapable
+		);
+		Self::mutate_account(who, |account| {
+			account.free = Zero::zero();
+			account.reserved = Zero::zero();
+		})?;
+		drop(DustCleaner::<T, I>(Some((who.clone(), NegativeImbalance::new(total)))));
+		Ok(())
+	}
+
+	/// Move `value` from the free balance of `who` to their reserved balance, as `reserve` does,
+	/// returning the reserved balance it leaves. It is only bound by `ReserveQuota` if
+	/// `within_quota`.
+	fn do_reserve(
+		who: &T::AccountId,
+		value: T::Balance,
+		within_quota: bool,
+	) -> Result<T::Balance, DispatchError> {
+		if value.is_zero() { return Ok(Self::account(who).reserved) }
+		Self::ensure_not_frozen(who)?;
+
+		let reserved = Self::try_mutate_account(who, |account, _| -> Result<_, DispatchError> {
+			ensure!(
+				!within_quota || Self::within_reserve_quota(account, value),
+				Error::<T, I>::ReserveQuotaExceeded,
+			);
+			account.free = account.free.checked_sub(&value).ok_or(Error::<T, I>::InsufficientBalance)?;
+			account.reserved = account.reserved.checked_add(&value).ok_or(Error::<T, I>::Overflow)?;
+			Self::ensure_can_withdraw(&who, value.clone(), WithdrawReasons::RESERVE, account.free)?;
+			Ok(account.reserved)
+		})?;
+
+		Self::deposit_event(Event::Reserved(who.clone(), value));
+		Ok(reserved)
+	}
+
+	/// Unreserve up to `value` of `who` without taking its reserved balance below `floor`,
+	/// returning the amount that could not be unreserved and the reserved balance it leaves.
+	///
+	/// Only `unreserve` and `unreserve_named` pass the `ReserveFloor` of `who`; root, claims,
+	/// expiries and repatriations pass zero.
+	fn do_unreserve(
+		who: &T::AccountId,
+		value: T::Balance,
+		floor: T::Balance,
+	) -> (T::Balance, T::Balance) {
+		let account = Self::account(who);
+		if value.is_zero() { return (Zero::zero(), account.reserved) }
+		if account.total().is_zero() { return (value, Zero::zero()) }
+
+		let (actual, reserved) = match Self::mutate_account(who, |account| {
+			let actual = cmp::min(account.reserved.saturating_sub(floor), value);
+			account.reserved -= actual;
+			// defensive only: this can never fail since total issuance which is at least free+reserved
+			// fits into the same data type.
+			account.free = Self::defensive_saturating_add(account.free, actual);
+			(actual, account.reserved)
+		}) {
+			Ok(x) => x,
+			Err(_) => {
+				// This should never happen since we don't alter the total amount in the account.
+				// If it ever does, then we should fail gracefully though, indicating that nothing
+				// could be done.
+				return (value, account.reserved)
+			}
+		};
+
+		Self::deposit_event(Event::Unreserved(who.clone(), actual.clone()));
+		(value - actual, reserved)
+	}
+
+	/// Whether reserving `value` more keeps `account` within `ReserveQuota`.
+	fn within_reserve_quota(account: &AccountData<T::Balance>, value: T::Balance) -> bool {
+		account.reserved.saturating_add(value) <= T::ReserveQuota::get().mul_floor(account.total())
+	}
+
+	/// Fail with `AccountFrozen` if `who` is frozen.
+	fn ensure_not_frozen(who: &T::AccountId) -> DispatchResult {
+		ensure!(!Self::is_frozen(who), Error::<T, I>::AccountFrozen);
+		Ok(())
+	}
+
+	/// Same as `Currency::withdraw`, but regardless of whether `who` is frozen.
+	fn do_withdraw(
+		who: &T::AccountId,
+		value: T::Balance,
+		reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> Result<NegativeImbalance<T, I>, DispatchError> {
+		if value.is_zero() { return Ok(NegativeImbalance::zero()); }
+
+		Self::try_mutate_account(who, |account, _|
+									   -> Result<NegativeImbalance<T, I>, DispatchError>
+			{
+				let from_reserved = Self::fee_shortfall(who, account, value, reasons);
+				ensure!(
+					from_reserved.is_zero() || from_reserved <= Self::anonymous_reserve(who, account),
+					Error::<T, I>::InsufficientBalance
+				);
+				// Both safe, as the shortfall is at most `value` and at most the reserved balance.
+				let new_reserved_account = account.reserved - from_reserved;
+				let new_free_account = account.free.checked_sub(&(value - from_reserved))
+					.ok_or(Error::<T, I>::InsufficientBalance)?;
+
+				// bail if we need to keep the account alive and this would kill it.
+				let ed = Self::existential_deposit_for(who);
+				let would_be_dead = new_free_account + new_reserved_account < ed;
+				let would_kill = would_be_dead && account.free + account.reserved >= ed;
+				ensure!(liveness == AllowDeath || !would_kill, Error::<T, I>::KeepAlive);
+
+				Self::ensure_can_withdraw(who, value, reasons, new_free_account)?;
+
+				account.free = new_free_account;
+				account.reserved = new_reserved_account;
+
+				Ok(NegativeImbalance::new(value))
+			})
+	}
+
+	/// Same as `Currency::transfer`, but regardless of whether `transactor` is frozen.
+	///
+	/// The events come in a fixed order: the `Endowed` of `dest` if it is created, then
+	/// `Transfer`, then whatever reaping `transactor` emits, the `DustLost` of its dust included.
+	#[transactional]
+	fn do_transfer(
+		transactor: &T::AccountId,
+		dest: &T::AccountId,
+		value: T::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		if value.is_zero() || transactor == dest { return Ok(()) }
+		ensure!(Self::account(transactor).free >= value, Error::<T, I>::InsufficientBalance);
+		// `dest` is credited before `transactor` is debited to get the events in order. Debiting
+		// `transactor` may still fail, reverting the whole transfer, events included.
+		Self::try_mutate_account_with_dust_by(
+			dest,
+			Some(transactor),
+			|to_account, _| -> DispatchResult {
+				// NOTE: total stake being stored in the same type means that this could never overflow
+				// but better to be safe than sorry.
+				to_account.free = to_account.free.checked_add(&value).ok_or(Error::<T, I>::Overflow)?;
+				ensure!(
+					to_account.total() >= Self::existential_deposit_for(dest),
+					Error::<T, I>::ExistentialDeposit,
+				);
+				Ok(())
+			}
+		)?;
+
+		// Emit transfer event.
+		Self::deposit_event(Event::Transfer(transactor.clone(), dest.clone(), value));
+
+		// The dust cleaner is dropped once done, sweeping the dust after any `KilledAccount`.
+		Self::try_mutate_account_with_dust(
+			transactor,
+			|from_account, _| -> DispatchResult {
+				from_account.free = from_account.free.checked_sub(&value)
+					.ok_or(Error::<T, I>::InsufficientBalance)?;
+
+				Self::ensure_can_withdraw(
+					transactor,
+					value,
+					WithdrawReasons::TRANSFER,
+					from_account.free,
+				).map_err(|_| Error::<T, I>::LiquidityRestrictions)?;
+
+				// TODO: This is over-conservative. There may now be other providers, and this pallet
+				//   may not even be a provider.
+				let allow_death = existence_requirement == ExistenceRequirement::AllowDeath;
+				let allow_death = allow_death && !system::Pallet::<T>::is_provider_required(transactor);
+				ensure!(
+					allow_death || from_account.free >= Self::existential_deposit_for(transactor),
+					Error::<T, I>::KeepAlive,
+				);
 
-impl From<WithdrawReasons> for Reasons {
-	fn from(r: WithdrawReasons) -> Reasons {
-		if r == WithdrawReasons::from(WithdrawReasons::TRANSACTION_PAYMENT) {
-			Reasons::Fee
-		} else if r.contains(WithdrawReasons::TRANSACTION_PAYMENT) {
-			Reasons::All
+				Ok(())
+			}
+		)?;
+
+		Ok(())
+	}
+
+	/// A single move of `force_transfer_batch`, which the caller reverts if it fails.
+	fn do_force_transfer_move(
+		source: <T::Lookup as StaticLookup>::Source,
+		dest: <T::Lookup as StaticLookup>::Source,
+		value: T::Balance,
+	) -> DispatchResult {
+		let source = T::Lookup::lookup(source)?;
+		let dest = T::Lookup::lookup(dest)?;
+		Self::charge_creation_fee(&source, &dest, value, true)?;
+		Self::do_transfer(&source, &dest, value, AllowDeath)
+	}
+
+	/// The `AccountCreationFee` a transfer from `transactor` to `dest` is charged: zero unless
+	/// it creates `dest`.
+	fn creation_fee(transactor: &T::AccountId, dest: &T::AccountId) -> T::Balance {
+		if transactor != dest && Self::account(dest).total().is_zero() {
+			T::AccountCreationFee::get()
 		} else {
-			Reasons::Misc
+			Zero::zero()
 		}
 	}
-}
 
-impl BitOr for Reasons {
-	type Output = Reasons;
-	fn bitor(self, other: Reasons) -> Reasons {
-		if self == other { return self }
-		Reasons::All
+	/// The weight charging the `AccountCreationFee` adds to a transfer.
+	fn creation_fee_weight() -> Weight {
+		T::WeightInfo::transfer_paying_creation_fee().saturating_sub(T::WeightInfo::transfer())
 	}
-}
-
-/// A single lock on a balance. There can be many of these on an account and they "overlap", so the
-/// same balance is frozen by multiple locks.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct BalanceLock<Balance> {
-	/// An identifier for this lock. Only one lock may be in existence for each identifier.
-	pub id: LockIdentifier,
-	/// The amount which the free balance may not drop below when this lock is in effect.
-	pub amount: Balance,
-	/// If true, then the lock remains in effect even for payment of transaction fees.
-	pub reasons: Reasons,
-}
 
-/// All balance information for an account.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
-pub struct AccountData<Balance> {
-	/// Non-reserved part of the balance. There may still be restrictions on this, but it is the
-	/// total pool what may in principle be transferred, reserved and used for tipping.
+	/// Charge `AccountCreationFee` to `transactor` if transferring `value` to `dest` creates it,
+	/// returning whether it was charged. The fee goes to `CreationFeeDestination`. A frozen
+	/// `transactor` fails with `AccountFrozen` unless `even_if_frozen`, as for root's transfers.
 	///
-	/// This is the only balance that matters in terms of most operations on tokens. It
-	/// alone is used to determine the balance when in the contract execution environment.
-	pub free: Balance,
-	/// Balance which is reserved and may not be used at all.
-	///
-	/// This can still get slashed, but gets slashed last of all.
-	///
-	/// This balance is a 'reserve' balance that other subsystems use in order to set aside tokens
-	/// that are still 'owned' by the account holder, but which are suspendable.
-	pub reserved: Balance,
-	/// The amount that `free` may not drop below when withdrawing for *anything except transaction
-	/// fee payment*.
-	pub misc_frozen: Balance,
-	/// The amount that `free` may not drop below when withdrawing specifically for transaction
-	/// fee payment.
-	pub fee_frozen: Balance,
-}
+	/// Callers must be `#[transactional]`, so that the fee is given back if the transfer fails.
+	fn charge_creation_fee(
+		transactor: &T::AccountId,
+		dest: &T::AccountId,
+		value: T::Balance,
+		even_if_frozen: bool,
+	) -> Result<bool, DispatchError> {
+		let fee = Self::creation_fee(transactor, dest);
+		if fee.is_zero() || value.is_zero() {
+			return Ok(false)
+		}
+		ensure!(
+			Self::account(transactor).free >= value.saturating_add(fee),
+			Error::<T, I>::CannotPayCreationFee,
+		);
+		if !even_if_frozen {
+			Self::ensure_not_frozen(transactor)?;
+		}
+		let fee = Self::do_withdraw(transactor, fee, WithdrawReasons::FEE, AllowDeath)
+			.map_err(|_| Error::<T, I>::CannotPayCreationFee)?;
+		T::CreationFeeDestination::on_unbalanced(fee);
+		Ok(true)
+	}
 
-impl<Balance: Saturating + Copy + Ord> AccountData<Balance> {
-	/// How much this account's balance can be reduced for the given `reasons`.
-	fn usable(&self, reasons: Reasons) -> Balance {
-		self.free.saturating_sub(self.frozen(reasons))
+	/// Transfer `value` from `transactor` to `dest` as `Currency::transfer` does, charging the
+	/// `AccountCreationFee` first if this creates `dest`. Returns whether the fee was charged.
+	///
+	/// Every signed call which may create the recipient of a transfer goes through this.
+	#[transactional]
+	fn transfer_charging_creation_fee(
+		transactor: &T::AccountId,
+		dest: &T::AccountId,
+		value: T::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> Result<bool, DispatchError> {
+		let fee_charged = Self::charge_creation_fee(transactor, dest, value, false)?;
+		<Self as Currency<_>>::transfer(transactor, dest, value, existence_requirement)?;
+		Ok(fee_charged)
 	}
-	/// The amount that this account's free balance may not be reduced beyond for the given
-	/// `reasons`.
-	fn frozen(&self, reasons: Reasons) -> Balance {
-		match reasons {
-			Reasons::All => self.misc_frozen.max(self.fee_frozen),
-			Reasons::Misc => self.misc_frozen,
-			Reasons::Fee => self.fee_frozen,
+
+	/// Unreserve the named reserves of `who` placed by `reserve_with_expiry` which have expired by
+	/// `now`, returning how many were released. Expiries whose named reserve is already gone are
+	/// dropped as well.
+	fn do_release_expired_reserves(who: &T::AccountId, now: &T::BlockNumber) -> u32 {
+		let expiries = ReserveExpiries::<T, I>::get(who);
+		if expiries.is_empty() { return 0 }
+		let before = expiries.len();
+		let mut released = 0;
+		let mut kept = Vec::with_capacity(before);
+		for (id, until, value) in expiries {
+			if until <= *now {
+				let actual = value - Self::do_unreserve_named(&id, who, value, Zero::zero());
+				if !actual.is_zero() {
+					Self::deposit_event(Event::ReserveExpired(who.clone(), id, actual));
+				}
+				released += 1;
+			} else if !<Self as NamedReservableCurrency<_>>::reserved_balance_named(&id, who).is_zero() {
+				kept.push((id, until, value));
+			}
 		}
+		if kept.is_empty() {
+			ReserveExpiries::<T, I>::remove(who);
+		} else if kept.len() < before {
+			ReserveExpiries::<T, I>::insert(who, kept);
+		}
+		released
 	}
-	/// The total balance in this account including any that is reserved and ignoring any frozen.
-	fn total(&self) -> Balance {
-		self.free.saturating_add(self.reserved)
-	}
-}
 
-// A value placed in storage that represents the current version of the Balances storage.
-// This value is used by the `on_runtime_upgrade` logic to determine whether we run
-// storage migration logic. This should match directly with the semantic versions of the Rust crate.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
-enum Releases {
-	V1_0_0,
-	V2_0_0,
-}
+	/// Unreserve up to `value` of the named reserve `id` of `who` without taking its reserved
+	/// balance below `floor`, returning any amount that was unable to be unreserved, without
+	/// releasing its expired reserves first.
+	fn do_unreserve_named(
+		id: &T::ReserveIdentifier,
+		who: &T::AccountId,
+		value: T::Balance,
+		floor: T::Balance,
+	) -> T::Balance {
+		Reserves::<T, I>::mutate_exists(who, |maybe_reserves| -> T::Balance {
+			if let Some(reserves) = maybe_reserves.as_mut() {
+				match reserves.binary_search_by_key(id, |data| data.id) {
+					Ok(index) => {
+						let to_change = cmp::min(reserves[index].amount, value);
+
+						let remain = Self::do_unreserve(who, to_change, floor).0;
+
+						// remain should always be zero but just to be defensive here
+						let actual = to_change.saturating_sub(remain);
+
+						// `actual <= to_change` and `to_change <= amount`; qed;
+						reserves[index].amount -= actual;
+
+						if reserves[index].amount.is_zero() {
+							if reserves.len() == 1 {
+								// no more named reserves
+								*maybe_reserves = None;
+							} else {
+								// remove this named reserve
+								reserves.remove(index);
+							}
+						}
 
-impl Default for Releases {
-	fn default() -> Self {
-		Releases::V1_0_0
+						value - actual
+					},
+					Err(_) => {
+						value
+					},
+				}
+			} else {
+				value
+			}
+		})
 	}
-}
 
-pub struct DustCleaner<T: Config<I>, I: 'static = ()>(Option<(T::AccountId, NegativeImbalance<T, I>)>);
+	/// Remove the zero-amount named reserves of `who`, returning how many were removed.
+	fn do_compact_reserves(who: &T::AccountId) -> u32 {
+		Reserves::<T, I>::mutate_exists(who, |maybe_reserves| {
+			let reserves = match maybe_reserves.as_mut() {
+				Some(reserves) => reserves,
+				None => return 0,
+			};
+			let before = reserves.len();
+			reserves.retain(|data| !data.amount.is_zero());
+			let purged = (before - reserves.len()) as u32;
+			if reserves.is_empty() {
+				*maybe_reserves = None;
+			}
+			purged
+		})
+	}
 
-impl<T: Config<I>, I: 'static> Drop for DustCleaner<T, I> {
-	fn drop(&mut self) {
-		if let Some((who, dust)) = self.0.take() {
-			Module::<T, I>::deposit_event(Event::DustLost(who, dust.peek()));
-			T::DustRemoval::on_unbalanced(dust);
+	/// Move `value` from the free balance of `transactor` into the reserved balance of `dest`,
+	/// following the same rules as `Currency::transfer` with `AllowDeath`.
+	fn do_reserved_transfer(transactor: &T::AccountId, dest: &T::AccountId, value: T::Balance) -> DispatchResult {
+		if value.is_zero() { return Ok(()) }
+		if transactor == dest {
+			return <Self as ReservableCurrency<_>>::reserve(transactor, value)
 		}
-	}
-}
+		Self::ensure_not_frozen(transactor)?;
 
-impl<T: Config<I>, I: 'static> Pallet<T, I> {
-	/// Get the free balance of an account.
-	pub fn free_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
-		Self::account(who.borrow()).free
-	}
+		let _dust_cleaners = Self::try_mutate_account_with_dust_by(
+			dest,
+			Some(transactor),
+			|to_account, _| -> Result<DustCleaner<T, I>, DispatchError> {
+				Self::try_mutate_account_with_dust(
+					transactor,
+					|from_account, _| -> DispatchResult {
+						from_account.free = from_account.free.checked_sub(&value)
+							.ok_or(Error::<T, I>::InsufficientBalance)?;
+						to_account.reserved = to_account.reserved.checked_add(&value)
+							.ok_or(Error::<T, I>::Overflow)?;
 
-	/// Get the balance of an account that can be used for transfers, reservations, or any other
-	/// non-locking, non-transaction-fee activity. Will be at most `free_balance`.
-	pub fn usable_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
-		Self::account(who.borrow()).usable(Reasons::Misc)
-	}
+						ensure!(
+							to_account.total() >= Self::existential_deposit_for(dest),
+							Error::<T, I>::ExistentialDeposit,
+						);
 
-	/// Get the balance of an account that can be used for paying transaction fees (not tipping,
-	/// or any other kind of fees, though). Will be at most `free_balance`.
-	pub fn usable_balance_for_fees(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
-		Self::account(who.borrow()).usable(Reasons::Fee)
-	}
+						Self::ensure_can_withdraw(
+							transactor,
+							value,
+							WithdrawReasons::TRANSFER,
+							from_account.free,
+						).map_err(|_| Error::<T, I>::LiquidityRestrictions)?;
 
-	/// Get the reserved balance of an account.
-	pub fn reserved_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
-		Self::account(who.borrow()).reserved
+						let allow_death = !system::Pallet::<T>::is_provider_required(transactor);
+						ensure!(
+							allow_death || from_account.free >= Self::existential_deposit_for(transactor),
+							Error::<T, I>::KeepAlive,
+						);
+						Ok(())
+					}
+				).map(|(_, maybe_dust_cleaner)| maybe_dust_cleaner)
+			}
+		)?;
+		Ok(())
 	}
 
-	/// Get both the free and reserved balances of an account.
-	fn account(who: &T::AccountId) -> AccountData<T::Balance> {
-		T::AccountStore::get(&who)
+	/// The part of a withdrawal of `value` for `reasons` from `who` to take from its reserved
+	/// balance: the shortfall of its usable free balance, if this pays a fee and `who` allows
+	/// fees to be paid from its reserved balance. Zero otherwise.
+	fn fee_shortfall(
+		who: &T::AccountId,
+		account: &AccountData<T::Balance>,
+		value: T::Balance,
+		reasons: WithdrawReasons,
+	) -> T::Balance {
+		if !reasons.contains(WithdrawReasons::TRANSACTION_PAYMENT) || !Self::fee_from_reserved(who) {
+			return Zero::zero()
+		}
+		value.saturating_sub(account.usable(reasons.into()))
 	}
 
-	/// Handles any steps needed after mutating an account.
-	///
-	/// This includes DustRemoval unbalancing, in the case than the `new` account's total balance
-	/// is non-zero but below ED.
-	///
-	/// Returns two values:
-	/// - `Some` containing the the `new` account, iff the account has sufficient balance.
-	/// - `Some` containing the dust to be dropped, iff some dust should be dropped.
-	fn post_mutation(
-		_who: &T::AccountId,
-		new: AccountData<T::Balance>,
-	) -> (Option<AccountData<T::Balance>>, Option<NegativeImbalance<T, I>>) {
-		let total = new.total();
-		if total < T::ExistentialDeposit::get() {
-			if total.is_zero() {
-				(None, None)
-			} else {
-				(None, Some(NegativeImbalance::new(total)))
-			}
-		} else {
-			(Some(new), None)
-		}
+	/// The reserved balance of `who` which isn't held in any named reserve, nor waiting to be
+	/// claimed.
+	fn anonymous_reserve(who: &T::AccountId, account: &AccountData<T::Balance>) -> T::Balance {
+		let named = Self::reserves(who).iter()
+			.fold(Zero::zero(), |acc: T::Balance, r| acc.saturating_add(r.amount));
+		account.reserved.saturating_sub(named).saturating_sub(Self::claimable(who))
 	}
 
-	/// Mutate an account to some new value, or delete it entirely with `None`. Will enforce
-	/// `ExistentialDeposit` law, annulling the account as needed.
-	///
-	/// NOTE: Doesn't do any preparatory work for creating a new account, so should only be used
-	/// when it is known that the account already exists.
-	///
-	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
-	/// the caller will do this.
-	pub fn mutate_account<R>(
-		who: &T::AccountId,
-		f: impl FnOnce(&mut AccountData<T::Balance>) -> R
-	) -> Result<R, StoredMapError> {
-		Self::try_mutate_account(who, |a, _| -> Result<R, StoredMapError> { Ok(f(a)) })
+	/// Ensure `value` is at least `MinTransfer`.
+	fn ensure_min_transfer(value: T::Balance) -> DispatchResult {
+		ensure!(value >= T::MinTransfer::get(), Error::<T, I>::TransferTooSmall);
+		Ok(())
 	}
 
-	/// Mutate an account to some new value, or delete it entirely with `None`. Will enforce
-	/// `ExistentialDeposit` law, annulling the account as needed. This will do nothing if the
-	/// result of `f` is an `Err`.
-	///
-	/// NOTE: Doesn't do any preparatory work for creating a new account, so should only be used
-	/// when it is known that the account already exists.
-	///
-	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
-	/// the caller will do this.
-	fn try_mutate_account<R, E: From<StoredMapError>>(
-		who: &T::AccountId,
-		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> Result<R, E>
-	) -> Result<R, E> {
-		Self::try_mutate_account_with_dust(who, f)
-			.map(|(result, dust_cleaner)| {
-				drop(dust_cleaner);
-				result
-			})
+	/// Burn the dust collected in `BurnAccount` since the last burn.
+	fn burn_pending_dust() {
+		let pending = PendingDust::<T, I>::take();
+		if pending.is_zero() {
+			return;
+		}
+		let (burned, _) = <Self as Currency<_>>::slash(&T::BurnAccount::get(), pending);
+		Self::deposit_event(Event::DustBurned(burned.peek()));
 	}
 
-	/// Mutate an account to some new value, or delete it entirely with `None`. Will enforce
-	/// `ExistentialDeposit` law, annulling the account as needed. This will do nothing if the
-	/// result of `f` is an `Err`.
-	///
-	/// It returns both the result from the closure, and an optional `DustCleaner` instance which
-	/// should be dropped once it is known that all nested mutates that could affect storage items
-	/// what the dust handler touches have completed.
-	///
-	/// NOTE: Doesn't do any preparatory work for creating a new account, so should only be used
-	/// when it is known that the account already exists.
+	/// The `misc_frozen` and `fee_frozen` amounts implied by `locks`, the overlapping locks of
+	/// each reason being combined as `LockAggregation` says.
 	///
-	/// NOTE: LOW-LEVEL: This will not attempt to maintain total issuance. It is expected that
-	/// the caller will do this.
-	fn try_mutate_account_with_dust<R, E: From<StoredMapError>>(
-		who: &T::AccountId,
-		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> Result<R, E>
-	) -> Result<(R, DustCleaner<T, I>), E> {
-		let result = T::AccountStore::try_mutate_exists(who, |maybe_account| {
-			let is_new = maybe_account.is_none();
-			let mut account = maybe_account.take().unwrap_or_default();
-			f(&mut account, is_new).map(move |result| {
-				let maybe_endowed = if is_new { Some(account.free) } else { None };
-				let maybe_account_maybe_dust = Self::post_mutation(who, account);
-				*maybe_account = maybe_account_maybe_dust.0;
-				(maybe_endowed, maybe_account_maybe_dust.1, result)
-			})
-		});
-		result.map(|(maybe_endowed, maybe_dust, result)| {
-			if let Some(endowed) = maybe_endowed {
-				Self::deposit_event(Event::Endowed(who.clone(), endowed));
+	/// These are cached in `AccountData` whenever the locks change, so that withdrawing only has to
+	/// read the account rather than scan its locks.
+	fn frozen_by_locks(locks: &[BalanceLock<T::Balance, T::BlockNumber>]) -> (T::Balance, T::Balance) {
+		let aggregation = T::LockAggregation::get();
+		let mut misc_frozen = Zero::zero();
+		let mut fee_frozen = Zero::zero();
+		for l in locks.iter() {
+			if l.reasons == Reasons::All || l.reasons == Reasons::Misc {
+				misc_frozen = aggregation.combine(misc_frozen, l.amount);
 			}
-			let dust_cleaner = DustCleaner(maybe_dust.map(|dust| (who.clone(), dust)));
-			(result, dust_cleaner)
-		})
+			if l.reasons == Reasons::All || l.reasons == Reasons::Fee {
+				fee_frozen = aggregation.combine(fee_frozen, l.amount);
+			}
+		}
+		(misc_frozen, fee_frozen)
 	}
 
 	/// Update the account entry for `who`, given the locks.
-	fn update_locks(who: &T::AccountId, locks: &[BalanceLock<T::Balance>]) {
+	fn update_locks(who: &T::AccountId, locks: &[BalanceLock<T::Balance, T::BlockNumber>]) {
 		if locks.len() as u32 > T::MaxLocks::get() {
 			frame_support::debug::warn!(
 				"Warning: A user has more currency locks than expected. \
@@ -751,19 +5076,17 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			);
 		}
 		// No way this can fail since we do not alter the existential balances.
+		let (misc_frozen, fee_frozen) = Self::frozen_by_locks(locks);
 		let _ = Self::mutate_account(who, |b| {
-			b.misc_frozen = Zero::zero();
-			b.fee_frozen = Zero::zero();
-			for l in locks.iter() {
-				if l.reasons == Reasons::All || l.reasons == Reasons::Misc {
-					b.misc_frozen = b.misc_frozen.max(l.amount);
-				}
-				if l.reasons == Reasons::All || l.reasons == Reasons::Fee {
-					b.fee_frozen = b.fee_frozen.max(l.amount);
-				}
-			}
+			b.misc_frozen = misc_frozen;
+			b.fee_frozen = fee_frozen;
 		});
 
+		// A vesting schedule is meaningless without the lock it releases, whichever way that goes.
+		if !locks.iter().any(|lock| lock.id == VESTING_ID) && Vesting::<T, I>::contains_key(who) {
+			Vesting::<T, I>::remove(who);
+		}
+
 		let existed = Locks::<T, I>::contains_key(who);
 		if locks.is_empty() {
 			Locks::<T, I>::remove(who);
@@ -793,7 +5116,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 // of the inner member.
 mod imbalances {
 	use super::{
-		result, Imbalance, Config, Zero, Saturating,
+		result, Imbalance, Config, Pallet, Zero, Saturating,
 		TryDrop, RuntimeDebug,
 	};
 	use sp_std::mem;
@@ -928,18 +5251,24 @@ mod imbalances {
 
 	impl<T: Config<I>, I: 'static> Drop for PositiveImbalance<T, I> {
 		/// Basic drop handler will just square up the total issuance.
+		///
+		/// `MaxIssuance` is enforced before anything is credited, so only an overflow of the total
+		/// issuance is clamped here, and reported as not accounted for.
 		fn drop(&mut self) {
-			<super::TotalIssuance<T, I>>::mutate(
-				|v| *v = v.saturating_add(self.0)
-			);
+			let issuance = <super::TotalIssuance<T, I>>::get();
+			let new = issuance.saturating_add(self.0);
+			Pallet::<T, I>::set_total_issuance(new);
+			if new - issuance < self.0 {
+				Pallet::<T, I>::note_issuance_capped(self.0 - (new - issuance));
+			}
 		}
 	}
 
 	impl<T: Config<I>, I: 'static> Drop for NegativeImbalance<T, I> {
 		/// Basic drop handler will just square up the total issuance.
 		fn drop(&mut self) {
-			<super::TotalIssuance<T, I>>::mutate(
-				|v| *v = v.saturating_sub(self.0)
+			Pallet::<T, I>::set_total_issuance(
+				<super::TotalIssuance<T, I>>::get().saturating_sub(self.0)
 			);
 		}
 	}
@@ -967,33 +5296,34 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 	}
 
 	fn minimum_balance() -> Self::Balance {
-		T::ExistentialDeposit::get()
+		Self::existential_deposit()
 	}
 
 	// Burn funds from the total issuance, returning a positive imbalance for the amount burned.
 	// Is a no-op if amount to be burned is zero.
 	fn burn(mut amount: Self::Balance) -> Self::PositiveImbalance {
 		if amount.is_zero() { return PositiveImbalance::zero() }
-		<TotalIssuance<T, I>>::mutate(|issued| {
-			*issued = issued.checked_sub(&amount).unwrap_or_else(|| {
-				amount = *issued;
-				Zero::zero()
-			});
+		let issued = <TotalIssuance<T, I>>::get();
+		let new = issued.checked_sub(&amount).unwrap_or_else(|| {
+			amount = issued;
+			Zero::zero()
 		});
+		Self::set_total_issuance(new);
 		PositiveImbalance::new(amount)
 	}
 
 	// Create new funds into the total issuance, returning a negative imbalance
 	// for the amount issued.
-	// Is a no-op if amount to be issued it zero.
-	fn issue(mut amount: Self::Balance) -> Self::NegativeImbalance {
+	// Is a no-op if amount to be issued it zero, or if it would overflow the total issuance.
+	fn issue(amount: Self::Balance) -> Self::NegativeImbalance {
 		if amount.is_zero() { return NegativeImbalance::zero() }
-		<TotalIssuance<T, I>>::mutate(|issued|
-			*issued = issued.checked_add(&amount).unwrap_or_else(|| {
-				amount = Self::Balance::max_value() - *issued;
-				Self::Balance::max_value()
-			})
-		);
+		if amount > Self::remaining_issuance_capacity() {
+			frame_support::debug::warn!(
+				"Warning: Refused to issue funds which would overflow the total issuance."
+			);
+			return NegativeImbalance::zero()
+		}
+		Self::set_total_issuance(<TotalIssuance<T, I>>::get() + amount);
 		NegativeImbalance::new(amount)
 	}
 
@@ -1023,6 +5353,7 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 
 	// Transfer some free balance from `transactor` to `dest`, respecting existence requirements.
 	// Is a no-op if value to be transferred is zero or the `transactor` is the same as `dest`.
+	// Fails with `AccountFrozen` if `transactor` is frozen.
 	fn transfer(
 		transactor: &T::AccountId,
 		dest: &T::AccountId,
@@ -1030,46 +5361,8 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 		existence_requirement: ExistenceRequirement,
 	) -> DispatchResult {
 		if value.is_zero() || transactor == dest { return Ok(()) }
-
-		Self::try_mutate_account_with_dust(
-			dest,
-			|to_account, _| -> Result<DustCleaner<T, I>, DispatchError> {
-				Self::try_mutate_account_with_dust(
-					transactor,
-					|from_account, _| -> DispatchResult {
-						from_account.free = from_account.free.checked_sub(&value)
-							.ok_or(Error::<T, I>::InsufficientBalance)?;
-
-						// NOTE: total stake being stored in the same type means that this could never overflow
-						// but better to be safe than sorry.
-						to_account.free = to_account.free.checked_add(&value).ok_or(Error::<T, I>::Overflow)?;
-
-						let ed = T::ExistentialDeposit::get();
-						ensure!(to_account.total() >= ed, Error::<T, I>::ExistentialDeposit);
-
-						Self::ensure_can_withdraw(
-							transactor,
-							value,
-							WithdrawReasons::TRANSFER,
-							from_account.free,
-						).map_err(|_| Error::<T, I>::LiquidityRestrictions)?;
-
-						// TODO: This is over-conservative. There may now be other providers, and this pallet
-						//   may not even be a provider.
-						let allow_death = existence_requirement == ExistenceRequirement::AllowDeath;
-						let allow_death = allow_death && !system::Pallet::<T>::is_provider_required(transactor);
-						ensure!(allow_death || from_account.free >= ed, Error::<T, I>::KeepAlive);
-
-						Ok(())
-					}
-				).map(|(_, maybe_dust_cleaner)| maybe_dust_cleaner)
-			}
-		)?;
-
-		// Emit transfer event.
-		Self::deposit_event(Event::Transfer(transactor.clone(), dest.clone(), value));
-
-		Ok(())
+		Self::ensure_not_frozen(transactor)?;
+		Self::do_transfer(transactor, dest, value, existence_requirement)
 	}
 
 	/// Slash a target account `who`, returning the negative imbalance created and any left over
@@ -1097,7 +5390,7 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 												   0 => value,
 												   // If acting as a critical provider (i.e. first attempt failed), then slash
 												   // as much as possible while leaving at least at ED.
-												   _ => value.min((account.free + account.reserved).saturating_sub(T::ExistentialDeposit::get())),
+												   _ => value.min((account.free + account.reserved).saturating_sub(Self::existential_deposit_for(who))),
 											   };
 
 											   let free_slash = cmp::min(account.free, best_value);
@@ -1132,18 +5425,15 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 
 	/// Deposit some `value` into the free balance of an existing target account `who`.
 	///
-	/// Is a no-op if the `value` to be deposited is zero.
+	/// Is a no-op if the `value` to be deposited is zero. Fails with `DeadAccount`, leaving the
+	/// value undeposited, if `who` doesn't exist, e.g. because it was reaped, and with
+	/// `IssuanceOverflow` if it would take the total issuance beyond `MaxIssuance`.
 	fn deposit_into_existing(
 		who: &T::AccountId,
 		value: Self::Balance
 	) -> Result<Self::PositiveImbalance, DispatchError> {
-		if value.is_zero() { return Ok(PositiveImbalance::zero()) }
-
-		Self::try_mutate_account(who, |account, is_new| -> Result<Self::PositiveImbalance, DispatchError> {
-			ensure!(!is_new, Error::<T, I>::DeadAccount);
-			account.free = account.free.checked_add(&value).ok_or(Error::<T, I>::Overflow)?;
-			Ok(PositiveImbalance::new(value))
-		})
+		ensure!(value <= Self::remaining_issuance_capacity(), Error::<T, I>::IssuanceOverflow);
+		Self::credit_existing(who, value)
 	}
 
 	/// Deposit some `value` into the free balance of `who`, possibly creating a new account.
@@ -1152,29 +5442,37 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 	/// - the `value` to be deposited is zero; or
 	/// - the `value` to be deposited is less than the required ED and the account does not yet exist; or
 	/// - the deposit would necessitate the account to exist and there are no provider references; or
-	/// - `value` is so large it would cause the balance of `who` to overflow.
+	/// - `value` is so large it would cause the balance of `who` or the total issuance to overflow; or
+	/// - `value` would take the total issuance beyond `MaxIssuance`, which emits `IssuanceCapReached`.
 	fn deposit_creating(
 		who: &T::AccountId,
 		value: Self::Balance,
 	) -> Self::PositiveImbalance {
-		if value.is_zero() { return Self::PositiveImbalance::zero() }
-
-		let r = Self::try_mutate_account(who, |account, is_new| -> Result<Self::PositiveImbalance, DispatchError> {
-
-			let ed = T::ExistentialDeposit::get();
-			ensure!(value >= ed || !is_new, Error::<T, I>::ExistentialDeposit);
-
-			// defensive only: overflow should never happen, however in case it does, then this
-			// operation is a no-op.
-			account.free = match account.free.checked_add(&value) {
-				Some(x) => x,
-				None => return Ok(Self::PositiveImbalance::zero()),
-			};
+		if value > Self::remaining_issuance_capacity() {
+			frame_support::debug::warn!(
+				"Warning: Refused to deposit funds which would take the total issuance beyond its cap."
+			);
+			Self::deposit_event(Event::IssuanceCapReached(value));
+			return Self::PositiveImbalance::zero()
+		}
+		Self::credit_creating(who, value)
+	}
 
-			Ok(PositiveImbalance::new(value))
-		}).unwrap_or_else(|_| Self::PositiveImbalance::zero());
+	// Resolving an existing imbalance creates nothing, so unlike the deposits it isn't held to
+	// `MaxIssuance`.
+	fn resolve_into_existing(
+		who: &T::AccountId,
+		value: Self::NegativeImbalance,
+	) -> Result<(), Self::NegativeImbalance> {
+		match Self::credit_existing(who, value.peek()) {
+			Ok(credited) => Ok(drop(value.offset(credited))),
+			Err(_) => Err(value),
+		}
+	}
 
-		r
+	fn resolve_creating(who: &T::AccountId, value: Self::NegativeImbalance) {
+		let credited = Self::credit_creating(who, value.peek());
+		drop(value.offset(credited));
 	}
 
 	/// Withdraw some free balance from an account, respecting existence requirements.
@@ -1187,25 +5485,8 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 		liveness: ExistenceRequirement,
 	) -> result::Result<Self::NegativeImbalance, DispatchError> {
 		if value.is_zero() { return Ok(NegativeImbalance::zero()); }
-
-		Self::try_mutate_account(who, |account, _|
-									   -> Result<Self::NegativeImbalance, DispatchError>
-			{
-				let new_free_account = account.free.checked_sub(&value)
-					.ok_or(Error::<T, I>::InsufficientBalance)?;
-
-				// bail if we need to keep the account alive and this would kill it.
-				let ed = T::ExistentialDeposit::get();
-				let would_be_dead = new_free_account + account.reserved < ed;
-				let would_kill = would_be_dead && account.free + account.reserved >= ed;
-				ensure!(liveness == AllowDeath || !would_kill, Error::<T, I>::KeepAlive);
-
-				Self::ensure_can_withdraw(who, value, reasons, new_free_account)?;
-
-				account.free = new_free_account;
-
-				Ok(NegativeImbalance::new(value))
-			})
+		Self::ensure_not_frozen(who)?;
+		Self::do_withdraw(who, value, reasons, liveness)
 	}
 
 	/// Force the new free balance of a target account `who` to some new value `balance`.
@@ -1215,7 +5496,7 @@ impl<T: Config<I>, I: 'static> Currency<T::AccountId> for Pallet<T, I> where
 		Self::try_mutate_account(who, |account, is_new|
 									   -> Result<SignedImbalance<Self::Balance, Self::PositiveImbalance>, DispatchError>
 			{
-				let ed = T::ExistentialDeposit::get();
+				let ed = Self::existential_deposit_for(who);
 				let total = value.saturating_add(account.reserved);
 				// If we're attempting to set an existing account to less than ED, then
 				// bypass the entire operation. It's a no-op if you follow it through, but
@@ -1245,7 +5526,10 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 	/// Always `true` if value to be reserved is zero.
 	fn can_reserve(who: &T::AccountId, value: Self::Balance) -> bool {
 		if value.is_zero() { return true }
-		Self::account(who).free
+		if Self::is_frozen(who) { return false }
+		let account = Self::account(who);
+		if !Self::within_reserve_quota(&account, value) { return false }
+		account.free
 			.checked_sub(&value)
 			.map_or(false, |new_balance|
 				Self::ensure_can_withdraw(who, value, WithdrawReasons::RESERVE, new_balance).is_ok()
@@ -1260,44 +5544,15 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 	///
 	/// Is a no-op if value to be reserved is zero.
 	fn reserve(who: &T::AccountId, value: Self::Balance) -> DispatchResult {
-		if value.is_zero() { return Ok(()) }
-
-		Self::try_mutate_account(who, |account, _| -> DispatchResult {
-			account.free = account.free.checked_sub(&value).ok_or(Error::<T, I>::InsufficientBalance)?;
-			account.reserved = account.reserved.checked_add(&value).ok_or(Error::<T, I>::Overflow)?;
-			Self::ensure_can_withdraw(&who, value.clone(), WithdrawReasons::RESERVE, account.free)
-		})?;
-
-		Self::deposit_event(Event::Reserved(who.clone(), value));
-		Ok(())
+		Self::do_reserve(who, value, true).map(|_| ())
 	}
 
 	/// Unreserve some funds, returning any amount that was unable to be unreserved.
 	///
-	/// Is a no-op if the value to be unreserved is zero or the account does not exist.
+	/// Is a no-op if the value to be unreserved is zero or the account does not exist. Never
+	/// takes the reserved balance below the `ReserveFloor` of `who`.
 	fn unreserve(who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if value.is_zero() { return Zero::zero() }
-		if Self::total_balance(&who).is_zero() { return value }
-
-		let actual = match Self::mutate_account(who, |account| {
-			let actual = cmp::min(account.reserved, value);
-			account.reserved -= actual;
-			// defensive only: this can never fail since total issuance which is at least free+reserved
-			// fits into the same data type.
-			account.free = account.free.saturating_add(actual);
-			actual
-		}) {
-			Ok(x) => x,
-			Err(_) => {
-				// This should never happen since we don't alter the total amount in the account.
-				// If it ever does, then we should fail gracefully though, indicating that nothing
-				// could be done.
-				return value
-			}
-		};
-
-		Self::deposit_event(Event::Unreserved(who.clone(), actual.clone()));
-		value - actual
+		Self::do_unreserve(who, value, ReserveFloor::<T, I>::get(who)).0
 	}
 
 	/// Slash from reserved balance, returning the negative imbalance created,
@@ -1320,7 +5575,7 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 					0 => value,
 					// If acting as a critical provider (i.e. first attempt failed), then ensure
 					// slash leaves at least the ED.
-					_ => value.min((account.free + account.reserved).saturating_sub(T::ExistentialDeposit::get())),
+					_ => value.min((account.free + account.reserved).saturating_sub(Self::existential_deposit_for(who))),
 				};
 
 				let actual = cmp::min(account.reserved, best_value);
@@ -1353,7 +5608,7 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 
 		if slashed == beneficiary {
 			return match status {
-				Status::Free => Ok(Self::unreserve(slashed, value)),
+				Status::Free => Ok(Self::do_unreserve(slashed, value, Zero::zero()).0),
 				Status::Reserved => Ok(value.saturating_sub(Self::reserved_balance(slashed))),
 			};
 		}
@@ -1386,6 +5641,164 @@ impl<T: Config<I>, I: 'static> ReservableCurrency<T::AccountId> for Pallet<T, I>
 	}
 }
 
+impl<T: Config<I>, I: 'static> NamedReservableCurrency<T::AccountId> for Pallet<T, I> where
+	T::Balance: MaybeSerializeDeserialize + Debug
+{
+	type ReserveIdentifier = T::ReserveIdentifier;
+
+	fn reserved_balance_named(id: &Self::ReserveIdentifier, who: &T::AccountId) -> Self::Balance {
+		let reserves = Self::reserves(who);
+		reserves
+			.binary_search_by_key(id, |data| data.id)
+			.map(|index| reserves[index].amount)
+			.unwrap_or_default()
+	}
+
+	/// Move `value` from the free balance from `who` to a named reserve balance.
+	///
+	/// Is a no-op if value to be reserved is zero.
+	fn reserve_named(id: &Self::ReserveIdentifier, who: &T::AccountId, value: Self::Balance) -> DispatchResult {
+		if value.is_zero() { return Ok(()) }
+
+		Self::do_release_expired_reserves(who, &system::Pallet::<T>::block_number());
+		Reserves::<T, I>::try_mutate(who, |reserves| -> DispatchResult {
+			match reserves.binary_search_by_key(id, |data| data.id) {
+				Ok(index) => {
+					// this add can't overflow but just to be defensive.
+					reserves[index].amount = Self::defensive_saturating_add(reserves[index].amount, value);
+				},
+				Err(index) => {
+					ensure!((reserves.len() as u32) < T::MaxReserves::get(), Error::<T, I>::TooManyReserves);
+					reserves.insert(index, ReserveData { id: *id, amount: value });
+				},
+			};
+			<Self as ReservableCurrency<_>>::reserve(who, value)?;
+			Ok(())
+		})
+	}
+
+	/// Unreserve some funds, returning any amount that was unable to be unreserved.
+	///
+	/// Is a no-op if the value to be unreserved is zero.
+	fn unreserve_named(id: &Self::ReserveIdentifier, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		if value.is_zero() { return Zero::zero() }
+
+		Self::do_release_expired_reserves(who, &system::Pallet::<T>::block_number());
+		Self::do_unreserve_named(id, who, value, ReserveFloor::<T, I>::get(who))
+	}
+
+	/// Slash from reserved balance, returning the negative imbalance created,
+	/// and any amount that was unable to be slashed.
+	///
+	/// Is a no-op if the value to be slashed is zero.
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		who: &T::AccountId,
+		value: Self::Balance
+	) -> (Self::NegativeImbalance, Self::Balance) {
+		if value.is_zero() { return (NegativeImbalance::zero(), Zero::zero()) }
+
+		// Expired collateral is no longer at stake.
+		Self::do_release_expired_reserves(who, &system::Pallet::<T>::block_number());
+		Reserves::<T, I>::mutate(who, |reserves| -> (Self::NegativeImbalance, Self::Balance) {
+			match reserves.binary_search_by_key(id, |data| data.id) {
+				Ok(index) => {
+					let to_change = cmp::min(reserves[index].amount, value);
+
+					let (imb, remain) = <Self as ReservableCurrency<_>>::slash_reserved(who, to_change);
+
+					// remain should always be zero but just to be defensive here
+					let actual = to_change.saturating_sub(remain);
+
+					// `actual <= to_change` and `to_change <= amount`; qed;
+					reserves[index].amount -= actual;
+
+					(imb, value - actual)
+				},
+				Err(_) => {
+					(NegativeImbalance::zero(), value)
+				},
+			}
+		})
+	}
+
+	/// Move the reserved balance of one account into the balance of another, according to `status`.
+	/// If `status` is `Reserved`, the balance will be reserved with given `id`.
+	///
+	/// Is a no-op if:
+	/// - the value to be moved is zero; or
+	/// - the `slashed` id equal to `beneficiary` and the `status` is `Reserved`.
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: Status,
+	) -> Result<Self::Balance, DispatchError> {
+		if value.is_zero() { return Ok(Zero::zero()) }
+
+		Self::do_release_expired_reserves(slashed, &system::Pallet::<T>::block_number());
+		if slashed == beneficiary {
+			return match status {
+				Status::Free => Ok(Self::do_unreserve_named(id, slashed, value, Zero::zero())),
+				Status::Reserved => Ok(value.saturating_sub(Self::reserved_balance_named(id, slashed))),
+			};
+		}
+
+		Reserves::<T, I>::try_mutate(slashed, |reserves| -> Result<Self::Balance, DispatchError> {
+			match reserves.binary_search_by_key(id, |data| data.id) {
+				Ok(index) => {
+					let to_change = cmp::min(reserves[index].amount, value);
+
+					let actual = if status == Status::Reserved {
+						// make it the reserved under same identifier
+						Reserves::<T, I>::try_mutate(beneficiary, |reserves| -> Result<T::Balance, DispatchError> {
+							match reserves.binary_search_by_key(id, |data| data.id) {
+								Ok(index) => {
+									let remain = <Self as ReservableCurrency<_>>::repatriate_reserved(slashed, beneficiary, to_change, status)?;
+
+									// remain should always be zero but just to be defensive here
+									let actual = to_change.saturating_sub(remain);
+
+									// this add can't overflow but just to be defensive.
+									reserves[index].amount = Self::defensive_saturating_add(reserves[index].amount, actual);
+
+									Ok(actual)
+								},
+								Err(index) => {
+									ensure!((reserves.len() as u32) < T::MaxReserves::get(), Error::<T, I>::TooManyReserves);
+
+									let remain = <Self as ReservableCurrency<_>>::repatriate_reserved(slashed, beneficiary, to_change, status)?;
+
+									// remain should always be zero but just to be defensive here
+									let actual = to_change.saturating_sub(remain);
+
+									reserves.insert(index, ReserveData { id: *id, amount: actual });
+
+									Ok(actual)
+								},
+							}
+						})?
+					} else {
+						let remain = <Self as ReservableCurrency<_>>::repatriate_reserved(slashed, beneficiary, to_change, status)?;
+
+						// remain should always be zero but just to be defensive here
+						to_change.saturating_sub(remain)
+					};
+
+					// `actual <= to_change` and `to_change <= amount`; qed;
+					reserves[index].amount -= actual;
+
+					Ok(value - actual)
+				},
+				Err(_) => {
+					Ok(value)
+				},
+			}
+		})
+	}
+}
+
 impl<T: Config<I>, I: 'static> LockableCurrency<T::AccountId> for Pallet<T, I>
 	where
 		T::Balance: MaybeSerializeDeserialize + Debug
@@ -1396,64 +5809,225 @@ impl<T: Config<I>, I: 'static> LockableCurrency<T::AccountId> for Pallet<T, I>
 
 	// Set a lock on the balance of `who`.
 	// Is a no-op if lock amount is zero or `reasons` `is_none()`.
+	//
+	// A lock with a new id is dropped, with a warning, if `who` already has `MaxLocks` locks, as
+	// is a lock which would take the `FeeReserve`. Use `try_set_lock` to get an error instead.
 	fn set_lock(
 		id: LockIdentifier,
 		who: &T::AccountId,
 		amount: T::Balance,
 		reasons: WithdrawReasons,
 	) {
-		if amount.is_zero() || reasons.is_empty() { return }
-		let mut new_lock = Some(BalanceLock { id, amount, reasons: reasons.into() });
-		let mut locks = Self::locks(who).into_iter()
-			.filter_map(|l| if l.id == id { new_lock.take() } else { Some(l) })
-			.collect::<Vec<_>>();
-		if let Some(lock) = new_lock {
-			locks.push(lock)
+		if let Err(e) = Self::try_set_lock(id, who, amount, reasons) {
+			frame_support::debug::warn!("Warning: A lock was dropped: {:?}.", e);
 		}
-		Self::update_locks(who, &locks[..]);
 	}
 
 	// Extend a lock on the balance of `who`.
 	// Is a no-op if lock amount is zero or `reasons` `is_none()`.
+	//
+	// A lock with a new id is dropped, with a warning, if `who` already has `MaxLocks` locks, as
+	// is an extension which would take the `FeeReserve`. Use `try_extend_lock` to get an error
+	// instead.
 	fn extend_lock(
 		id: LockIdentifier,
 		who: &T::AccountId,
 		amount: T::Balance,
 		reasons: WithdrawReasons,
 	) {
-		if amount.is_zero() || reasons.is_empty() { return }
-		let mut new_lock = Some(BalanceLock { id, amount, reasons: reasons.into() });
-		let mut locks = Self::locks(who).into_iter().filter_map(|l|
-			if l.id == id {
-				new_lock.take().map(|nl| {
-					BalanceLock {
-						id: l.id,
-						amount: l.amount.max(nl.amount),
-						reasons: l.reasons | nl.reasons,
-					}
-				})
-			} else {
-				Some(l)
-			}).collect::<Vec<_>>();
-		if let Some(lock) = new_lock {
-			locks.push(lock)
+		if let Err(e) = Self::try_extend_lock(id, who, amount, reasons) {
+			frame_support::debug::warn!("Warning: A lock extension was dropped: {:?}.", e);
 		}
-		Self::update_locks(who, &locks[..]);
 	}
 
 	fn remove_lock(
 		id: LockIdentifier,
 		who: &T::AccountId,
 	) {
-		let mut locks = Self::locks(who);
+		let mut locks = Self::live_locks(who);
 		locks.retain(|l| l.id != id);
 		Self::update_locks(who, &locks[..]);
 	}
 }
 
+impl<T: Config<I>, I: 'static> ReservedFirstSlash<T::AccountId> for Pallet<T, I> where
+	T::Balance: MaybeSerializeDeserialize + Debug
+{
+	fn slash_reserved_first(who: &T::AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		Self::slash_reserved_first(who, value)
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnEraBoundary for Pallet<T, I> {
+	/// Snapshot the total issuance at the start of `era`, forgetting the era which just fell out
+	/// of the history.
+	fn on_era_boundary(era: EraIndex) {
+		let depth = T::IssuanceHistoryDepth::get();
+		if depth.is_zero() { return }
+		IssuanceHistory::<T, I>::insert(era, TotalIssuance::<T, I>::get());
+		if let Some(expired) = era.checked_sub(depth) {
+			IssuanceHistory::<T, I>::remove(expired);
+		}
+	}
+}
+
 impl<T: Config<I>, I: 'static> UsableCurrency<T::AccountId> for Module<T, I>
 	where T::Balance: MaybeSerializeDeserialize + Debug {
 	fn usable_balance(who: &T::AccountId) -> Self::Balance {
 		Self::account(who).usable(Reasons::All)
 	}
-}
\ No newline at end of file
+}
+
+impl<T: Config<I>, I: 'static> fungible::Inspect<T::AccountId> for Pallet<T, I> where
+	T::Balance: MaybeSerializeDeserialize + Debug
+{
+	type Balance = T::Balance;
+
+	fn total_issuance() -> Self::Balance {
+		TotalIssuance::<T, I>::get()
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		Self::existential_deposit()
+	}
+
+	fn balance(who: &T::AccountId) -> Self::Balance {
+		Self::account(who).total()
+	}
+
+	fn reducible_balance(who: &T::AccountId, keep_alive: bool) -> Self::Balance {
+		Self::reducible_balance(who, keep_alive)
+	}
+
+	// Mirrors the checks of `Currency::deposit_creating`.
+	fn can_deposit(who: &T::AccountId, amount: Self::Balance) -> DepositConsequence {
+		if amount.is_zero() { return DepositConsequence::Success }
+		if amount > Self::issuance_headroom() {
+			return DepositConsequence::Overflow
+		}
+		let account = Self::account(who);
+		if account.free.checked_add(&amount).is_none() {
+			return DepositConsequence::Overflow
+		}
+		if account.total().is_zero() && amount < Self::existential_deposit_for(who) {
+			return DepositConsequence::BelowMinimum
+		}
+		DepositConsequence::Success
+	}
+
+	// Mirrors the checks of `Currency::withdraw` for all reasons, allowing death.
+	fn can_withdraw(who: &T::AccountId, amount: Self::Balance) -> WithdrawConsequence<Self::Balance> {
+		if amount.is_zero() { return WithdrawConsequence::Success }
+		if TotalIssuance::<T, I>::get().checked_sub(&amount).is_none() {
+			return WithdrawConsequence::Underflow
+		}
+		let account = Self::account(who);
+		let new_free = match account.free.checked_sub(&amount) {
+			Some(free) => free,
+			None => return WithdrawConsequence::NoFunds,
+		};
+		if new_free < account.frozen(Reasons::All) {
+			return WithdrawConsequence::Frozen
+		}
+		// same existence rules as `post_mutation`.
+		let new_total = new_free.saturating_add(account.reserved);
+		if new_total >= Self::existential_deposit_for(who)
+			|| (!new_total.is_zero() && T::DustRemovalWhitelist::contains(who))
+		{
+			WithdrawConsequence::Success
+		} else if system::Pallet::<T>::is_provider_required(who) {
+			WithdrawConsequence::WouldDie
+		} else {
+			WithdrawConsequence::ReducedToZero(new_total)
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungible::Mutate<T::AccountId> for Pallet<T, I> where
+	T::Balance: MaybeSerializeDeserialize + Debug
+{
+	fn mint_into(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		ensure!(amount <= Self::remaining_issuance_capacity(), Error::<T, I>::IssuanceOverflow);
+		match <Self as fungible::Inspect<_>>::can_deposit(who, amount) {
+			DepositConsequence::BelowMinimum => Err(Error::<T, I>::ExistentialDeposit.into()),
+			DepositConsequence::Overflow => Err(Error::<T, I>::Overflow.into()),
+			DepositConsequence::Success => {
+				mem::drop(<Self as Currency<_>>::deposit_creating(who, amount));
+				Ok(())
+			},
+		}
+	}
+
+	fn burn_from(who: &T::AccountId, amount: Self::Balance) -> Result<Self::Balance, DispatchError> {
+		<Self as Currency<_>>::withdraw(who, amount, WithdrawReasons::all(), AllowDeath)
+			.map(|imbalance| imbalance.peek())
+	}
+}
+
+// Holds are the named reserves, keyed by their `ReserveIdentifier`, so the total on hold is part
+// of the reserved balance, which may hold more in anonymous reserves.
+impl<T: Config<I>, I: 'static> fungible::InspectHold<T::AccountId> for Pallet<T, I> where
+	T::Balance: MaybeSerializeDeserialize + Debug
+{
+	type Reason = T::ReserveIdentifier;
+
+	fn balance_on_hold(reason: &Self::Reason, who: &T::AccountId) -> Self::Balance {
+		<Self as NamedReservableCurrency<_>>::reserved_balance_named(reason, who)
+	}
+
+	fn total_balance_on_hold(who: &T::AccountId) -> Self::Balance {
+		Self::reserves(who).iter().fold(Zero::zero(), |total, data| total.saturating_add(data.amount))
+	}
+
+	fn can_hold(reason: &Self::Reason, who: &T::AccountId, amount: Self::Balance) -> bool {
+		let reserves = Self::reserves(who);
+		let has_room = reserves.binary_search_by_key(reason, |data| data.id).is_ok()
+			|| (reserves.len() as u32) < T::MaxReserves::get();
+		has_room && <Self as ReservableCurrency<_>>::can_reserve(who, amount)
+	}
+}
+
+impl<T: Config<I>, I: 'static> fungible::MutateHold<T::AccountId> for Pallet<T, I> where
+	T::Balance: MaybeSerializeDeserialize + Debug
+{
+	fn hold(reason: &Self::Reason, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		<Self as NamedReservableCurrency<_>>::reserve_named(reason, who, amount)
+	}
+
+	fn release(
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		ensure!(
+			best_effort || amount <= <Self as fungible::InspectHold<_>>::balance_on_hold(reason, who),
+			Error::<T, I>::InsufficientBalance,
+		);
+		let not_released = <Self as NamedReservableCurrency<_>>::unreserve_named(reason, who, amount);
+		Ok(amount - not_released)
+	}
+
+	fn transfer_held(
+		reason: &Self::Reason,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+		on_hold: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		ensure!(
+			best_effort || amount <= <Self as fungible::InspectHold<_>>::balance_on_hold(reason, source),
+			Error::<T, I>::InsufficientBalance,
+		);
+		let status = if on_hold { Status::Reserved } else { Status::Free };
+		let not_moved = <Self as NamedReservableCurrency<_>>::repatriate_reserved_named(
+			reason,
+			source,
+			dest,
+			amount,
+			status,
+		)?;
+		Ok(amount - not_moved)
+	}
+}
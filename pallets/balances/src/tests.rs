@@ -12,7 +12,7 @@ macro_rules! decl_tests {
 		use crate::*;
 		use sp_runtime::{FixedPointNumber, traits::{SignedExtension, BadOrigin}};
 		use frame_support::{
-			assert_noop, assert_storage_noop, assert_ok, assert_err, StorageValue,
+			assert_noop, assert_storage_noop, assert_ok, assert_err, StorageValue, weights::Pays,
 			traits::{
 				LockableCurrency, LockIdentifier, WithdrawReasons,
 				Currency, ReservableCurrency, ExistenceRequirement::AllowDeath
@@ -598,6 +598,8 @@ macro_rules! decl_tests {
 			let mut t = frame_system::GenesisConfig::default().build_storage::<$test>().unwrap();
 			let _ = pallet_balances::GenesisConfig::<$test> {
 				balances: vec![(1, 10)],
+				reserved_balances: vec![],
+				locks: vec![],
 			}.assimilate_storage(&mut t).unwrap();
 		}
 
@@ -607,6 +609,8 @@ macro_rules! decl_tests {
 			let mut t = frame_system::GenesisConfig::default().build_storage::<$test>().unwrap();
 			let _ = pallet_balances::GenesisConfig::<$test> {
 				balances: vec![(1, 10), (2, 20), (1, 15)],
+				reserved_balances: vec![],
+				locks: vec![],
 			}.assimilate_storage(&mut t).unwrap();
 		}
 
@@ -707,8 +711,9 @@ macro_rules! decl_tests {
 					assert_eq!(
 						events(),
 						[
+							Event::pallet_balances(crate::Event::Issued(100)),
 							Event::frame_system(system::Event::NewAccount(1)),
-							Event::pallet_balances(crate::Event::Endowed(1, 100)),
+							Event::pallet_balances(crate::Event::Endowed(1, 100, None)),
 							Event::pallet_balances(crate::Event::BalanceSet(1, 100, 0)),
 						]
 					);
@@ -720,6 +725,8 @@ macro_rules! decl_tests {
 						[
 							Event::frame_system(system::Event::KilledAccount(1)),
 							Event::pallet_balances(crate::Event::DustLost(1, 99)),
+							Event::pallet_balances(crate::Event::Rescinded(99)),
+							Event::pallet_balances(crate::Event::Rescinded(1)),
 						]
 					);
 				});
@@ -736,8 +743,9 @@ macro_rules! decl_tests {
 					assert_eq!(
 						events(),
 						[
+							Event::pallet_balances(crate::Event::Issued(100)),
 							Event::frame_system(system::Event::NewAccount(1)),
-							Event::pallet_balances(crate::Event::Endowed(1, 100)),
+							Event::pallet_balances(crate::Event::Endowed(1, 100, None)),
 							Event::pallet_balances(crate::Event::BalanceSet(1, 100, 0)),
 						]
 					);
@@ -747,7 +755,8 @@ macro_rules! decl_tests {
 					assert_eq!(
 						events(),
 						[
-							Event::frame_system(system::Event::KilledAccount(1))
+							Event::frame_system(system::Event::KilledAccount(1)),
+							Event::pallet_balances(crate::Event::Rescinded(100)),
 						]
 					);
 				});
@@ -977,5 +986,2528 @@ macro_rules! decl_tests {
                 assert_eq!(Balances::usable_balance(&1), 150);
             });
         }
+
+		#[test]
+		fn transfer_all_works() {
+			<$ext_builder>::default()
+				.existential_deposit(100)
+				.build()
+				.execute_with(|| {
+					// setup
+					assert_ok!(Balances::set_balance(Origin::root(), 1, 200, 0));
+					assert_ok!(Balances::set_balance(Origin::root(), 2, 0, 0));
+					// transfer all and allow death
+					assert_ok!(Balances::transfer_all(Some(1).into(), 2, false));
+					assert_eq!(Balances::total_balance(&1), 0);
+					assert_eq!(Balances::total_balance(&2), 200);
+					assert_eq!(
+						last_event(),
+						Event::pallet_balances(crate::Event::Transfer(1, 2, 200)),
+					);
+
+					// setup
+					assert_ok!(Balances::set_balance(Origin::root(), 1, 200, 0));
+					assert_ok!(Balances::set_balance(Origin::root(), 2, 0, 0));
+					// transfer all and keep alive
+					assert_ok!(Balances::transfer_all(Some(1).into(), 2, true));
+					assert_eq!(Balances::total_balance(&1), 100);
+					assert_eq!(Balances::total_balance(&2), 100);
+
+					// setup
+					assert_ok!(Balances::set_balance(Origin::root(), 1, 200, 0));
+					assert_ok!(Balances::set_balance(Origin::root(), 2, 0, 0));
+					// locked funds count towards the existential deposit that must remain
+					Balances::set_lock(ID_1, &1, 50, WithdrawReasons::all());
+					assert_ok!(Balances::transfer_all(Some(1).into(), 2, true));
+					assert_eq!(Balances::total_balance(&1), 100);
+					assert_eq!(Balances::total_balance(&2), 100);
+				});
+		}
+
+		#[test]
+		fn transfer_batch_works() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 111);
+				assert_ok!(Balances::transfer_batch(Some(1).into(), vec![(2, 30), (3, 40)]));
+				assert_eq!(Balances::total_balance(&1), 41);
+				assert_eq!(Balances::total_balance(&2), 30);
+				assert_eq!(Balances::total_balance(&3), 40);
+				assert!(events().contains(&Event::pallet_balances(crate::Event::Transfer(1, 2, 30))));
+			});
+		}
+
+		#[test]
+		fn transfer_batch_is_atomic() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 111);
+				// The second leg cannot be paid, so the first one must not happen either.
+				assert_noop!(
+					Balances::transfer_batch(Some(1).into(), vec![(2, 69), (3, 69)]),
+					Error::<$test, _>::InsufficientBalance,
+				);
+				assert_eq!(Balances::total_balance(&1), 111);
+				assert_eq!(Balances::total_balance(&2), 0);
+			});
+		}
+
+		#[test]
+		fn transfer_batch_respects_max_batch_size() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 1_000);
+				let max = <$test as Config>::MaxBatchSize::get() as u64;
+				let recipients = (0..=max).map(|i| (100 + i, 1)).collect::<Vec<_>>();
+				assert_noop!(
+					Balances::transfer_batch(Some(1).into(), recipients),
+					Error::<$test, _>::TooManyRecipients,
+				);
+			});
+		}
+
+		#[test]
+		fn named_reserve_should_work() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 111);
+
+				let id_1 = [1u8; 8];
+				let id_2 = [2u8; 8];
+
+				// reserve
+
+				assert_noop!(Balances::reserve_named(&id_1, &1, 112), Error::<$test, _>::InsufficientBalance);
+
+				assert_ok!(Balances::reserve_named(&id_1, &1, 12));
+
+				assert_eq!(Balances::reserved_balance(1), 12);
+				assert_eq!(Balances::reserved_balance_named(&id_1, &1), 12);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 0);
+
+				assert_ok!(Balances::reserve_named(&id_1, &1, 2));
+
+				assert_eq!(Balances::reserved_balance(1), 14);
+				assert_eq!(Balances::reserved_balance_named(&id_1, &1), 14);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 0);
+
+				assert_ok!(Balances::reserve_named(&id_2, &1, 23));
+
+				assert_eq!(Balances::reserved_balance(1), 37);
+				assert_eq!(Balances::reserved_balance_named(&id_1, &1), 14);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 23);
+
+				assert_ok!(Balances::reserve(&1, 34));
+
+				assert_eq!(Balances::reserved_balance(1), 71);
+				assert_eq!(Balances::reserved_balance_named(&id_1, &1), 14);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 23);
+
+				assert_eq!(Balances::total_balance(&1), 111);
+				assert_eq!(Balances::free_balance(1), 40);
+
+				// unreserve
+
+				assert_eq!(Balances::unreserve_named(&id_1, &1, 10), 0);
+
+				assert_eq!(Balances::reserved_balance(1), 61);
+				assert_eq!(Balances::reserved_balance_named(&id_1, &1), 4);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 23);
+
+				assert_eq!(Balances::unreserve_named(&id_1, &1, 5), 1);
+
+				assert_eq!(Balances::reserved_balance(1), 57);
+				assert_eq!(Balances::reserved_balance_named(&id_1, &1), 0);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 23);
+
+				assert_eq!(Balances::unreserve_named(&id_2, &1, 3), 0);
+
+				assert_eq!(Balances::reserved_balance(1), 54);
+				assert_eq!(Balances::reserved_balance_named(&id_1, &1), 0);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 20);
+
+				assert_eq!(Balances::total_balance(&1), 111);
+				assert_eq!(Balances::free_balance(1), 57);
+
+				// slash_reserved_named
+
+				assert_ok!(Balances::reserve_named(&id_1, &1, 10));
+
+				assert_eq!(Balances::slash_reserved_named(&id_1, &1, 25).1, 15);
+
+				assert_eq!(Balances::reserved_balance(1), 54);
+				assert_eq!(Balances::reserved_balance_named(&id_1, &1), 0);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 20);
+				assert_eq!(Balances::total_balance(&1), 101);
+
+				assert_eq!(Balances::slash_reserved_named(&id_2, &1, 5).1, 0);
+
+				assert_eq!(Balances::reserved_balance(1), 49);
+				assert_eq!(Balances::reserved_balance_named(&id_1, &1), 0);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 15);
+				assert_eq!(Balances::total_balance(&1), 96);
+
+				// repatriate_reserved_named
+
+				let _ = Balances::deposit_creating(&2, 100);
+
+				assert_eq!(Balances::repatriate_reserved_named(&id_2, &1, &2, 10, Status::Reserved).unwrap(), 0);
+
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 5);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &2), 10);
+				assert_eq!(Balances::reserved_balance(&2), 10);
+
+				assert_eq!(Balances::repatriate_reserved_named(&id_2, &2, &1, 11, Status::Reserved).unwrap(), 1);
+
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 15);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &2), 0);
+				assert_eq!(Balances::reserved_balance(&2), 0);
+
+				assert_eq!(Balances::repatriate_reserved_named(&id_2, &1, &2, 10, Status::Free).unwrap(), 0);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 5);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &2), 0);
+				assert_eq!(Balances::free_balance(&2), 110);
+
+				// repatriate_reserved_named to self
+
+				assert_eq!(Balances::repatriate_reserved_named(&id_2, &1, &1, 10, Status::Reserved).unwrap(), 5);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 5);
+
+				assert_eq!(Balances::free_balance(&1), 47);
+
+				assert_eq!(Balances::repatriate_reserved_named(&id_2, &1, &1, 15, Status::Free).unwrap(), 10);
+				assert_eq!(Balances::reserved_balance_named(&id_2, &1), 0);
+
+				assert_eq!(Balances::free_balance(&1), 52);
+			});
+		}
+
+		#[test]
+		fn reserve_named_respects_max_reserves() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 1_000);
+				let max = <$test as Config>::MaxReserves::get();
+				for i in 0..max {
+					assert_ok!(Balances::reserve_named(&[i as u8; 8], &1, 1));
+				}
+				assert_noop!(
+					Balances::reserve_named(&[max as u8; 8], &1, 1),
+					Error::<$test, _>::TooManyReserves,
+				);
+				// topping up an existing reserve is still fine.
+				assert_ok!(Balances::reserve_named(&[0u8; 8], &1, 1));
+				assert_eq!(Balances::reserved_balance(1), max as u64 + 1);
+				assert_eq!(Balances::reserves(1).len() as u32, max);
+			});
+		}
+
+		#[test]
+		fn transfer_with_memo_works() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_ok!(Balances::transfer_with_memo(Some(1).into(), 2, 42, b"user-123".to_vec()));
+				assert_eq!(Balances::free_balance(1), 58);
+				assert_eq!(Balances::free_balance(2), 42);
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::TransferWithMemo(1, 2, 42, b"user-123".to_vec())),
+				);
+			});
+		}
+
+		#[test]
+		fn transfer_with_memo_rejects_long_memo() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				let max = <$test as Config>::MaxMemoLength::get() as usize;
+				assert_ok!(Balances::transfer_with_memo(Some(1).into(), 2, 10, vec![1u8; max]));
+				assert_noop!(
+					Balances::transfer_with_memo(Some(1).into(), 2, 10, vec![1u8; max + 1]),
+					Error::<$test, _>::MemoTooLong,
+				);
+			});
+		}
+
+		#[test]
+		fn transfer_with_memo_keeps_sender_alive() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_noop!(
+					Balances::transfer_with_memo(Some(1).into(), 2, 100, vec![]),
+					Error::<$test, _>::KeepAlive,
+				);
+			});
+		}
+
+		#[test]
+		fn transferable_balance_subtracts_max_of_overlapping_locks() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_eq!(Balances::transferable_balance(&1), 90);
+
+				Balances::set_lock(ID_1, &1, 30, WithdrawReasons::TRANSACTION_PAYMENT);
+				Balances::set_lock(ID_2, &1, 50, WithdrawReasons::TRANSFER);
+				// the larger frozen amount is subtracted, not the sum of both.
+				assert_eq!(Balances::transferable_balance(&1), 50);
+
+				Balances::set_lock(ID_1, &1, 70, WithdrawReasons::TRANSACTION_PAYMENT);
+				assert_eq!(Balances::transferable_balance(&1), 30);
+			});
+		}
+
+		#[test]
+		fn transferable_balance_keeps_existential_deposit() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				Balances::set_lock(ID_1, &1, 3, WithdrawReasons::TRANSACTION_PAYMENT);
+				Balances::set_lock(ID_2, &1, 5, WithdrawReasons::TRANSFER);
+				// the locked 5 only covers part of the existential deposit.
+				assert_eq!(Balances::transferable_balance(&1), 90);
+				assert_ok!(Balances::transfer_keep_alive(Some(1).into(), 2, 90));
+				assert_eq!(Balances::transferable_balance(&1), 0);
+			});
+		}
+
+		#[test]
+		fn lock_by_id_should_work() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				const STAKING_ID: LockIdentifier = *b"staking ";
+				const VESTING_ID: LockIdentifier = *b"vesting ";
+
+				assert_eq!(Balances::lock_by_id(&1, STAKING_ID), None);
+
+				Balances::set_lock(STAKING_ID, &1, 5, WithdrawReasons::all());
+				Balances::set_lock(VESTING_ID, &1, 8, WithdrawReasons::TRANSFER);
+
+				assert_eq!(Balances::locks(&1).len(), 2);
+				assert_eq!(
+					Balances::lock_by_id(&1, STAKING_ID),
+					Some(BalanceLock { id: STAKING_ID, amount: 5, reasons: Reasons::All, until: None }),
+				);
+				assert_eq!(
+					Balances::lock_by_id(&1, VESTING_ID),
+					Some(BalanceLock { id: VESTING_ID, amount: 8, reasons: Reasons::Misc, until: None }),
+				);
+				assert_eq!(Balances::lock_by_id(&1, ID_1), None);
+				assert_eq!(Balances::lock_by_id(&2, STAKING_ID), None);
+
+				Balances::remove_lock(VESTING_ID, &1);
+				assert_eq!(Balances::lock_by_id(&1, VESTING_ID), None);
+				assert!(Balances::lock_by_id(&1, STAKING_ID).is_some());
+			});
+		}
+
+		#[test]
+		fn locks_are_bounded_by_max_locks() {
+			<$ext_builder>::default().existential_deposit(1).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 1_000);
+				let max = <$test as Config>::MaxLocks::get();
+				for i in 0..max {
+					assert_ok!(Balances::try_set_lock([i as u8; 8], &1, 1, WithdrawReasons::all()));
+				}
+				assert_noop!(
+					Balances::try_set_lock([max as u8; 8], &1, 1, WithdrawReasons::all()),
+					Error::<$test, _>::TooManyLocks,
+				);
+				assert_noop!(
+					Balances::try_extend_lock([max as u8; 8], &1, 1, WithdrawReasons::all()),
+					Error::<$test, _>::TooManyLocks,
+				);
+				// the infallible variants drop the new lock instead.
+				assert_storage_noop!(Balances::set_lock([max as u8; 8], &1, 1, WithdrawReasons::all()));
+				assert_storage_noop!(Balances::extend_lock([max as u8; 8], &1, 1, WithdrawReasons::all()));
+
+				// updating existing lock ids does not count against the limit.
+				assert_ok!(Balances::try_set_lock([0u8; 8], &1, 7, WithdrawReasons::all()));
+				assert_ok!(Balances::try_extend_lock([1u8; 8], &1, 9, WithdrawReasons::all()));
+				assert_eq!(Balances::locks(&1).len() as u32, max);
+				assert_eq!(Balances::lock_by_id(&1, [0u8; 8]).map(|l| l.amount), Some(7));
+				assert_eq!(Balances::lock_by_id(&1, [1u8; 8]).map(|l| l.amount), Some(9));
+			});
+		}
+
+		#[test]
+		fn truncate_excess_locks_drops_smallest_locks() {
+			<$ext_builder>::default().existential_deposit(1).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 1_000);
+				let max = <$test as Config>::MaxLocks::get();
+				let locks = (0..max + 2)
+					.map(|i| BalanceLock { id: [i as u8; 8], amount: i as u64 + 1, reasons: Reasons::All, until: None })
+					.collect::<Vec<_>>();
+				Locks::<$test>::insert(1, locks);
+
+				migrations::truncate_excess_locks::<$test, ()>();
+
+				let locks = Balances::locks(&1);
+				assert_eq!(locks.len() as u32, max);
+				assert_eq!(Balances::lock_by_id(&1, [0u8; 8]), None);
+				assert_eq!(Balances::lock_by_id(&1, [1u8; 8]), None);
+				assert!(Balances::lock_by_id(&1, [2u8; 8]).is_some());
+				assert_eq!(Balances::account(&1).misc_frozen, max as u64 + 2);
+			});
+		}
+
+		#[test]
+		fn force_unreserve_clamps_to_reserved_balance() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 111);
+				assert_ok!(Balances::reserve(&1, 42));
+
+				assert_noop!(Balances::force_unreserve(Some(1).into(), 1, 42), BadOrigin);
+
+				assert_ok!(Balances::force_unreserve(RawOrigin::Root.into(), 1, 100));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::Unreserved(1, 42)),
+				);
+				assert_eq!(Balances::reserved_balance(1), 0);
+				assert_eq!(Balances::free_balance(1), 111);
+				assert_eq!(Balances::total_balance(&1), 111);
+			});
+		}
+
+		#[test]
+		fn slash_reserved_first_fully_covered_by_reserved() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 111);
+				assert_ok!(Balances::reserve(&1, 69));
+				let (imbalance, missing) = Balances::slash_reserved_first(&1, 42);
+				assert_eq!(imbalance.peek(), 42);
+				assert_eq!(missing, 0);
+				drop(imbalance);
+				assert_eq!(Balances::free_balance(1), 42);
+				assert_eq!(Balances::reserved_balance(1), 27);
+				assert_eq!(<TotalIssuance<$test>>::get(), 69);
+			});
+		}
+
+		#[test]
+		fn slash_reserved_first_spans_reserved_and_free() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 111);
+				assert_ok!(Balances::reserve(&1, 42));
+				let (imbalance, missing) = Balances::slash_reserved_first(&1, 60);
+				assert_eq!(imbalance.peek(), 60);
+				assert_eq!(missing, 0);
+				drop(imbalance);
+				assert_eq!(Balances::free_balance(1), 51);
+				assert_eq!(Balances::reserved_balance(1), 0);
+				assert_eq!(<TotalIssuance<$test>>::get(), 51);
+			});
+		}
+
+		#[test]
+		fn slash_reserved_first_with_only_free_balance() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 111);
+				let (imbalance, missing) = Balances::slash_reserved_first(&1, 200);
+				assert_eq!(imbalance.peek(), 111);
+				assert_eq!(missing, 89);
+				drop(imbalance);
+				assert_eq!(Balances::free_balance(1), 0);
+				assert_eq!(<TotalIssuance<$test>>::get(), 0);
+			});
+		}
+
+		#[test]
+		fn usable_balances_follow_lock_reasons() {
+			<$ext_builder>::default().existential_deposit(1).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				Balances::set_lock(ID_1, &1, 60, WithdrawReasons::TRANSFER);
+				Balances::set_lock(ID_2, &1, 20, WithdrawReasons::TRANSACTION_PAYMENT);
+				assert_eq!(Balances::usable_balance(&1), 40);
+				assert_eq!(Balances::usable_balance_for_fees(&1), 80);
+				assert_noop!(
+					<Balances as Currency<_>>::transfer(&1, &2, 41, AllowDeath),
+					Error::<$test, _>::LiquidityRestrictions
+				);
+				assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 40, AllowDeath));
+			});
+		}
+
+		#[test]
+		fn endowed_records_funding_source_before_transfer() {
+			<$ext_builder>::default()
+				.existential_deposit(10)
+				.build()
+				.execute_with(|| {
+					let _ = Balances::deposit_creating(&1, 100);
+					assert!(events().contains(&Event::pallet_balances(crate::Event::Endowed(1, 100, None))));
+
+					assert_ok!(Balances::transfer(Some(1).into(), 2, 95));
+					let evts = events();
+					let endowed = Event::pallet_balances(crate::Event::Endowed(2, 95, Some(1)));
+					let position = evts.iter().position(|e| *e == endowed).expect("Endowed expected");
+					assert_eq!(evts[position + 1], Event::pallet_balances(crate::Event::Transfer(1, 2, 95)));
+					// the dust of the reaped sender is only swept after the transfer.
+					assert!(evts.ends_with(&[
+						Event::pallet_balances(crate::Event::DustLost(1, 5)),
+						Event::pallet_balances(crate::Event::Rescinded(5)),
+					]));
+
+					assert_ok!(Balances::force_transfer(RawOrigin::Root.into(), 2, 3, 20));
+					assert!(events().contains(&Event::pallet_balances(crate::Event::Endowed(3, 20, Some(2)))));
+				});
+		}
+
+		#[test]
+		fn burn_extrinsic_reduces_total_issuance() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				let _ = Balances::deposit_creating(&2, 100);
+				assert_eq!(Balances::total_issuance(), 200);
+
+				assert_ok!(Balances::burn(Some(1).into(), 30, true));
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::Burned(1, 30)));
+				assert_eq!(Balances::free_balance(1), 70);
+				assert_eq!(Balances::total_issuance(), 170);
+
+				// burning down below the existential deposit is refused when keeping alive.
+				assert_noop!(Balances::burn(Some(1).into(), 65, true), Error::<$test, _>::KeepAlive);
+
+				assert_ok!(Balances::burn(Some(1).into(), 70, false));
+				assert_eq!(Balances::total_balance(&1), 0);
+				assert_eq!(Balances::total_issuance(), 100);
+
+				assert_noop!(Balances::burn(Some(2).into(), 101, false), Error::<$test, _>::InsufficientBalance);
+			});
+		}
+
+		#[test]
+		fn transfer_refunds_weight_in_best_case() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				let _ = Balances::deposit_creating(&2, 100);
+				let worst_case = <$test as Config>::WeightInfo::transfer();
+
+				// both accounts exist before and after.
+				let post_info = Balances::transfer(Some(1).into(), 2, 10).unwrap();
+				let actual = post_info.actual_weight.expect("refund expected");
+				assert!(actual < worst_case);
+				assert_eq!(actual, <$test as Config>::WeightInfo::transfer_best_case());
+
+				// creating the recipient is charged the full weight.
+				let post_info = Balances::transfer(Some(1).into(), 3, 10).unwrap();
+				assert_eq!(post_info.actual_weight, None);
+
+				// so is reaping the sender.
+				let post_info = Balances::transfer(Some(3).into(), 2, 10).unwrap();
+				assert_eq!(post_info.actual_weight, None);
+			});
+		}
+
+		#[test]
+		fn set_balance_batch_works() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_eq!(Balances::total_issuance(), 100);
+				System::set_block_number(1);
+				assert_ok!(Balances::set_balance_batch(RawOrigin::Root.into(), vec![(1, 40, 0), (2, 50, 30)]));
+				assert_eq!(Balances::free_balance(1), 40);
+				assert_eq!(Balances::free_balance(2), 50);
+				assert_eq!(Balances::reserved_balance(2), 30);
+				// -60 for the first entry, +80 for the second one.
+				assert_eq!(Balances::total_issuance(), 120);
+				let events = events();
+				assert!(events.contains(&Event::pallet_balances(crate::Event::BalanceSet(1, 40, 0))));
+				assert!(events.contains(&Event::pallet_balances(crate::Event::BalanceSet(2, 50, 30))));
+			});
+		}
+
+		#[test]
+		fn set_balance_batch_is_atomic() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				// The second entry overflows the total issuance, so the first one must not happen either.
+				assert_noop!(
+					Balances::set_balance_batch(RawOrigin::Root.into(), vec![(2, 50, 0), (3, u64::max_value(), 0)]),
+					Error::<$test, _>::Overflow,
+				);
+				assert_eq!(Balances::free_balance(2), 0);
+				assert_eq!(Balances::total_issuance(), 100);
+			});
+		}
+
+		#[test]
+		fn set_balance_batch_requires_root_and_respects_max_batch_size() {
+			<$ext_builder>::default().build().execute_with(|| {
+				assert_noop!(Balances::set_balance_batch(Some(1).into(), vec![(2, 50, 0)]), BadOrigin);
+				let max = <$test as Config>::MaxBatchSize::get() as u64;
+				let entries = (0..=max).map(|i| (100 + i, 10, 0)).collect::<Vec<_>>();
+				assert_noop!(
+					Balances::set_balance_batch(RawOrigin::Root.into(), entries),
+					Error::<$test, _>::TooManyRecipients,
+				);
+			});
+		}
+
+		#[test]
+		fn fungible_inspect_matches_currency() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_ok!(Balances::reserve(&1, 20));
+				Balances::set_lock(ID_1, &1, 30, WithdrawReasons::all());
+				let _ = Balances::deposit_creating(&3, 15);
+
+				assert_eq!(
+					<Balances as fungible::Inspect<_>>::total_issuance(),
+					<Balances as Currency<_>>::total_issuance(),
+				);
+				assert_eq!(
+					<Balances as fungible::Inspect<_>>::minimum_balance(),
+					<Balances as Currency<_>>::minimum_balance(),
+				);
+				assert_eq!(<Balances as fungible::Inspect<_>>::balance(&1), Balances::total_balance(&1));
+
+				// only the free balance above the lock is reducible.
+				assert_eq!(<Balances as fungible::Inspect<_>>::reducible_balance(&1, true), 50);
+				assert_eq!(<Balances as fungible::Inspect<_>>::reducible_balance(&1, false), 50);
+				assert_ok!(Balances::ensure_can_withdraw(&1, 50, WithdrawReasons::all(), 30));
+				assert!(Balances::ensure_can_withdraw(&1, 51, WithdrawReasons::all(), 29).is_err());
+				assert_eq!(<Balances as fungible::Inspect<_>>::can_withdraw(&1, 50), WithdrawConsequence::Success);
+				assert_eq!(<Balances as fungible::Inspect<_>>::can_withdraw(&1, 51), WithdrawConsequence::Frozen);
+				assert_eq!(<Balances as fungible::Inspect<_>>::can_withdraw(&1, 81), WithdrawConsequence::NoFunds);
+				assert_eq!(
+					<Balances as fungible::Inspect<_>>::can_withdraw(&3, 10),
+					WithdrawConsequence::ReducedToZero(5),
+				);
+
+				// `deposit_creating` ignores deposits which can't create the account.
+				assert_eq!(<Balances as fungible::Inspect<_>>::can_deposit(&2, 5), DepositConsequence::BelowMinimum);
+				assert_eq!(Balances::deposit_creating(&2, 5).peek(), 0);
+				assert_eq!(<Balances as fungible::Inspect<_>>::can_deposit(&2, 10), DepositConsequence::Success);
+				assert_eq!(
+					<Balances as fungible::Inspect<_>>::can_deposit(&1, u64::max_value()),
+					DepositConsequence::Overflow,
+				);
+			});
+		}
+
+		#[test]
+		fn fungible_mutate_matches_currency() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				assert_ok!(<Balances as fungible::Mutate<_>>::mint_into(&1, 50));
+				let _ = Balances::deposit_creating(&2, 50);
+				assert_eq!(Balances::free_balance(1), Balances::free_balance(2));
+				assert_eq!(Balances::total_issuance(), 100);
+
+				assert_noop!(
+					<Balances as fungible::Mutate<_>>::mint_into(&3, 5),
+					Error::<$test, _>::ExistentialDeposit,
+				);
+
+				assert_eq!(<Balances as fungible::Mutate<_>>::burn_from(&1, 20), Ok(20));
+				assert_ok!(Balances::withdraw(&2, 20, WithdrawReasons::all(), AllowDeath));
+				assert_eq!(Balances::free_balance(1), Balances::free_balance(2));
+				assert_eq!(Balances::total_issuance(), 60);
+
+				assert_noop!(
+					<Balances as fungible::Mutate<_>>::burn_from(&1, 31),
+					Error::<$test, _>::InsufficientBalance,
+				);
+				Balances::set_lock(ID_1, &1, 25, WithdrawReasons::all());
+				assert_noop!(
+					<Balances as fungible::Mutate<_>>::burn_from(&1, 10),
+					Error::<$test, _>::LiquidityRestrictions,
+				);
+			});
+		}
+
+		#[test]
+		fn repatriating_reserved_balance_emits_actual_amount_and_status() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 110);
+				let _ = Balances::deposit_creating(&2, 1);
+				assert_ok!(Balances::reserve(&1, 50));
+				System::set_block_number(2);
+
+				// only the 50 which are actually reserved can be moved.
+				assert_ok!(Balances::repatriate_reserved(&1, &2, 60, Status::Reserved), 10);
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::ReserveRepatriated(1, 2, 50, Status::Reserved)),
+				);
+				assert_eq!(Balances::reserved_balance(2), 50);
+
+				// repatriating to oneself as free is a plain unreserve.
+				assert_ok!(Balances::repatriate_reserved(&2, &2, 20, Status::Free), 0);
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::Unreserved(2, 20)));
+				assert_eq!(Balances::free_balance(2), 21);
+			});
+		}
+
+		#[test]
+		fn expired_locks_are_pruned_at_their_expiry_block() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_ok!(Balances::try_set_lock_until(ID_1, &1, 50, WithdrawReasons::all(), 3));
+				Balances::set_lock(ID_2, &1, 20, WithdrawReasons::all());
+				assert_noop!(
+					<Balances as Currency<_>>::transfer(&1, &2, 60, AllowDeath),
+					Error::<$test, _>::LiquidityRestrictions,
+				);
+
+				System::set_block_number(3);
+				<Balances as frame_support::traits::OnInitialize<u64>>::on_initialize(3);
+				assert_eq!(Balances::lock_by_id(&1, ID_1), None);
+				assert!(Balances::lock_by_id(&1, ID_2).is_some());
+				assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 60, AllowDeath));
+				assert_noop!(
+					<Balances as Currency<_>>::transfer(&1, &2, 21, AllowDeath),
+					Error::<$test, _>::LiquidityRestrictions,
+				);
+			});
+		}
+
+		#[test]
+		fn expired_locks_are_pruned_on_access_or_on_request() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_ok!(Balances::try_set_lock_until(ID_1, &1, 50, WithdrawReasons::all(), 3));
+				assert_ok!(Balances::try_set_lock_until(ID_2, &1, 30, WithdrawReasons::all(), 5));
+				Balances::set_lock(*b"vesting ", &1, 10, WithdrawReasons::all());
+
+				// the expiry block was skipped, so the lock is still in effect...
+				System::set_block_number(4);
+				assert_eq!(Balances::account(&1).misc_frozen, 50);
+
+				// ...until anyone asks for it to be pruned.
+				assert_ok!(Balances::prune_expired_locks(Some(2).into(), 1));
+				assert_eq!(Balances::locks(&1).len(), 2);
+				assert_eq!(Balances::account(&1).misc_frozen, 30);
+
+				// changing the locks prunes the expired ones as well.
+				System::set_block_number(6);
+				Balances::set_lock(ID_1, &1, 5, WithdrawReasons::all());
+				assert_eq!(Balances::lock_by_id(&1, ID_2), None);
+				assert_eq!(Balances::locks(&1).len(), 2);
+				assert_eq!(Balances::account(&1).misc_frozen, 10);
+
+				// a lock which is already expired is never set.
+				assert_ok!(Balances::try_set_lock_until(ID_1, &1, 50, WithdrawReasons::all(), 6));
+				assert_eq!(Balances::lock_by_id(&1, ID_1), None);
+			});
+		}
+
+		#[test]
+		fn migrate_to_expiring_locks_keeps_locks_forever() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				let old_locks = vec![(ID_1, 50u64, Reasons::All), (ID_2, 20u64, Reasons::Fee)];
+				frame_support::storage::unhashed::put(&Locks::<$test>::hashed_key_for(1), &old_locks);
+
+				migrations::migrate_to_expiring_locks::<$test, ()>();
+
+				assert_eq!(
+					Balances::locks(&1),
+					vec![
+						BalanceLock { id: ID_1, amount: 50, reasons: Reasons::All, until: None },
+						BalanceLock { id: ID_2, amount: 20, reasons: Reasons::Fee, until: None },
+					],
+				);
+			});
+		}
+
+		#[test]
+		fn repatriate_reserved_split_moves_both_portions() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 110);
+				let _ = Balances::deposit_creating(&2, 1);
+				assert_ok!(Balances::reserve(&1, 100));
+				System::set_block_number(2);
+
+				assert_eq!(Balances::repatriate_reserved_split(&1, &2, 30, 40), 0);
+				assert_eq!(Balances::reserved_balance(1), 30);
+				assert_eq!(Balances::free_balance(2), 31);
+				assert_eq!(Balances::reserved_balance(2), 40);
+				let events = events();
+				assert!(events.contains(
+					&Event::pallet_balances(crate::Event::ReserveRepatriated(1, 2, 30, Status::Free))
+				));
+				assert!(events.contains(
+					&Event::pallet_balances(crate::Event::ReserveRepatriated(1, 2, 40, Status::Reserved))
+				));
+
+				// only one of the portions.
+				assert_eq!(Balances::repatriate_reserved_split(&1, &2, 10, 0), 0);
+				assert_eq!(Balances::repatriate_reserved_split(&1, &2, 0, 10), 0);
+				assert_eq!(Balances::free_balance(2), 41);
+				assert_eq!(Balances::reserved_balance(2), 50);
+				assert_eq!(<TotalIssuance<$test>>::get(), 111);
+			});
+		}
+
+		#[test]
+		fn repatriate_reserved_split_returns_shortfall() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 110);
+				let _ = Balances::deposit_creating(&2, 1);
+				assert_ok!(Balances::reserve(&1, 50));
+
+				// the free portion is covered first.
+				assert_eq!(Balances::repatriate_reserved_split(&1, &2, 30, 40), 20);
+				assert_eq!(Balances::reserved_balance(1), 0);
+				assert_eq!(Balances::free_balance(2), 31);
+				assert_eq!(Balances::reserved_balance(2), 20);
+
+				// repatriating to oneself unreserves the free portion only.
+				assert_ok!(Balances::reserve(&1, 50));
+				assert_eq!(Balances::repatriate_reserved_split(&1, &1, 30, 40), 20);
+				assert_eq!(Balances::free_balance(1), 40);
+				assert_eq!(Balances::reserved_balance(1), 20);
+			});
+		}
+
+		#[test]
+		fn repatriate_reserved_split_to_dead_account_respects_existential_deposit() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 110);
+				assert_ok!(Balances::reserve(&1, 100));
+
+				// too little would land on the new account, so nothing is moved.
+				assert_eq!(Balances::repatriate_reserved_split(&1, &2, 5, 0), 5);
+				assert_eq!(Balances::total_balance(&2), 0);
+				assert_eq!(Balances::reserved_balance(1), 100);
+
+				// a free portion below the existential deposit is fine as long as the total isn't.
+				assert_eq!(Balances::repatriate_reserved_split(&1, &2, 5, 10), 0);
+				assert_eq!(Balances::free_balance(2), 5);
+				assert_eq!(Balances::reserved_balance(2), 10);
+				assert_eq!(Balances::reserved_balance(1), 85);
+			});
+		}
+
+		#[test]
+		fn set_existential_deposit_works() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				assert_eq!(Balances::existential_deposit(), 10);
+				assert_noop!(Balances::set_existential_deposit(Some(1).into(), 20), BadOrigin);
+
+				assert_ok!(Balances::set_existential_deposit(RawOrigin::Root.into(), 20));
+				assert_eq!(Balances::existential_deposit(), 20);
+				assert_eq!(<Balances as Currency<_>>::minimum_balance(), 20);
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::ExistentialDepositChanged(10, 20)));
+
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_noop!(
+					<Balances as Currency<_>>::transfer(&1, &2, 15, AllowDeath),
+					Error::<$test, _>::ExistentialDeposit,
+				);
+			});
+		}
+
+		#[test]
+		fn raising_existential_deposit_reaps_on_next_mutation_only() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 15);
+				let _ = Balances::deposit_creating(&2, 100);
+				assert_ok!(Balances::set_existential_deposit(RawOrigin::Root.into(), 20));
+
+				// the account is now below the existential deposit, but survives...
+				assert_eq!(Balances::free_balance(1), 15);
+				assert_eq!(Balances::total_issuance(), 115);
+
+				// ...until it is next touched.
+				let _ = Balances::slash(&1, 1);
+				assert_eq!(Balances::free_balance(1), 0);
+				assert_eq!(Balances::total_issuance(), 100);
+			});
+		}
+
+		#[test]
+		fn mints_overflowing_total_issuance_are_rejected() {
+			<$ext_builder>::default().build().execute_with(|| {
+				Balances::make_free_balance_be(&1, u64::max_value() - 10);
+				assert_eq!(Balances::remaining_issuance_capacity(), 10);
+				Balances::make_free_balance_be(&2, 5);
+				assert_eq!(Balances::remaining_issuance_capacity(), 5);
+
+				assert_eq!(Balances::deposit_creating(&3, 6).peek(), 0);
+				assert_eq!(Balances::free_balance(3), 0);
+				assert_noop!(Balances::deposit_into_existing(&2, 6), Error::<$test, _>::IssuanceOverflow);
+				assert_eq!(Balances::issue(6).peek(), 0);
+				assert_eq!(Balances::total_issuance(), u64::max_value() - 5);
+
+				// right up to the maximum is fine.
+				let imbalance = Balances::issue(5);
+				assert_eq!(imbalance.peek(), 5);
+				assert_eq!(Balances::remaining_issuance_capacity(), 0);
+			});
+		}
+
+		#[test]
+		fn clamping_total_issuance_emits_event() {
+			<$ext_builder>::default().build().execute_with(|| {
+				Balances::make_free_balance_be(&1, u64::max_value() - 10);
+				Balances::make_free_balance_be(&2, 15);
+				assert_eq!(Balances::total_issuance(), u64::max_value());
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::IssuanceCapped(5)));
+			});
+		}
+
+		#[test]
+		fn ensure_reserved_reaches_target() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_ok!(Balances::reserve(&1, 30));
+				let _ = events();
+
+				// below the target.
+				assert_ok!(Balances::ensure_reserved(&1, 50));
+				assert_eq!(Balances::reserved_balance(1), 50);
+				assert_eq!(Balances::free_balance(1), 50);
+				assert_eq!(events(), [Event::pallet_balances(crate::Event::Reserved(1, 20))]);
+
+				// at the target.
+				assert_ok!(Balances::ensure_reserved(&1, 50));
+				assert_eq!(Balances::reserved_balance(1), 50);
+				assert_eq!(events(), []);
+
+				// above the target.
+				assert_ok!(Balances::ensure_reserved(&1, 10));
+				assert_eq!(Balances::reserved_balance(1), 10);
+				assert_eq!(Balances::free_balance(1), 90);
+				assert_eq!(events(), [Event::pallet_balances(crate::Event::Unreserved(1, 40))]);
+
+				assert_noop!(Balances::ensure_reserved(&1, 101), Error::<$test, _>::InsufficientBalance);
+			});
+		}
+
+		#[test]
+		fn frozen_balance_breakdown_matches_withdraw() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				Balances::set_lock(ID_1, &1, 30, WithdrawReasons::TRANSACTION_PAYMENT);
+				Balances::set_lock(ID_2, &1, 50, WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+				Balances::set_lock(*b"all     ", &1, 20, WithdrawReasons::all());
+
+				let (fee, misc) = Balances::frozen_balance_breakdown(&1);
+				assert_eq!((fee, misc), (30, 50));
+				let payment = WithdrawReasons::TRANSACTION_PAYMENT;
+				assert_ok!(Balances::ensure_can_withdraw(&1, 1, payment, fee));
+				assert!(Balances::ensure_can_withdraw(&1, 1, payment, fee - 1).is_err());
+				assert_ok!(Balances::ensure_can_withdraw(&1, 1, WithdrawReasons::TRANSFER, misc));
+				assert!(Balances::ensure_can_withdraw(&1, 1, WithdrawReasons::TRANSFER, misc - 1).is_err());
+				assert!(Balances::ensure_can_withdraw(&1, 1, WithdrawReasons::all(), fee.max(misc) - 1).is_err());
+			});
+		}
+
+		#[test]
+		fn transfer_if_exists_pays_an_existing_recipient() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				assert_ok!(Balances::transfer_if_exists(Some(1).into(), 2, 5));
+				assert_eq!(Balances::free_balance(1), 5);
+				assert_eq!(Balances::free_balance(2), 25);
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::Transfer(1, 2, 5)));
+			});
+		}
+
+		#[test]
+		fn transfer_if_exists_never_creates_the_recipient() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				assert_noop!(
+					Balances::transfer_if_exists(Some(1).into(), 5, 5),
+					Error::<$test, _>::RecipientDoesNotExist,
+				);
+				assert_eq!(Balances::free_balance(1), 10);
+				assert!(!System::account_exists(&5));
+			});
+		}
+
+		#[test]
+		fn can_reserve_and_can_withdraw_match_the_actual_outcome() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				use frame_support::storage::{with_transaction, TransactionOutcome};
+				let reserves = |value| with_transaction(||
+					TransactionOutcome::Rollback(Balances::reserve(&1, value).is_ok())
+				);
+				let withdraws = |value, reasons| with_transaction(||
+					TransactionOutcome::Rollback(Balances::withdraw(&1, value, reasons, AllowDeath).is_ok())
+				);
+				let check = || {
+					for value in [0, 20, 70, 71, 90, 95, 100, 101].iter().copied() {
+						assert_eq!(Balances::can_reserve(&1, value), reserves(value), "reserve {}", value);
+						for reasons in [
+							WithdrawReasons::TRANSFER,
+							WithdrawReasons::TRANSACTION_PAYMENT,
+							WithdrawReasons::all(),
+						].iter().copied() {
+							assert_eq!(
+								Balances::can_withdraw_for(&1, value, reasons),
+								withdraws(value, reasons),
+								"withdraw {} for {:?}", value, reasons,
+							);
+						}
+					}
+				};
+
+				// no account at all.
+				check();
+
+				// unrestricted; withdrawing below the existential deposit reaps the account.
+				let _ = Balances::deposit_creating(&1, 100);
+				check();
+				assert!(Balances::can_withdraw_for(&1, 95, WithdrawReasons::TRANSFER));
+
+				// a non-fee lock, whose consumer reference also keeps the account from being reaped.
+				Balances::set_lock(ID_1, &1, 30, WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+				check();
+				assert!(!Balances::can_reserve(&1, 71));
+				assert!(Balances::can_withdraw_for(&1, 90, WithdrawReasons::TRANSACTION_PAYMENT));
+				assert!(!Balances::can_withdraw_for(&1, 95, WithdrawReasons::TRANSACTION_PAYMENT));
+
+				// some of the free balance already reserved.
+				assert_ok!(Balances::reserve(&1, 20));
+				check();
+				assert!(Balances::can_withdraw_for(&1, 80, WithdrawReasons::TRANSACTION_PAYMENT));
+			});
+		}
+
+		#[test]
+		fn migrate_single_locks_wraps_single_locks_once() {
+			<$ext_builder>::default().build().execute_with(|| {
+				use frame_support::storage::unhashed;
+				for who in 1..=3 {
+					let _ = Balances::deposit_creating(&who, 100);
+				}
+				// single locks in the pre-expiry and in the current layout, frozen when they were set.
+				unhashed::put(&Locks::<$test>::hashed_key_for(1), &(ID_1, 50u64, Reasons::Misc));
+				let _ = Balances::mutate_account(&1, |a| a.misc_frozen = 50);
+				let single = BalanceLock { id: ID_2, amount: 20u64, reasons: Reasons::All, until: Some(9u64) };
+				unhashed::put(&Locks::<$test>::hashed_key_for(2), &single);
+				let _ = Balances::mutate_account(&2, |a| { a.misc_frozen = 20; a.fee_frozen = 20; });
+				Balances::set_lock(ID_1, &3, 30, WithdrawReasons::all());
+				let current = Balances::locks(&3);
+
+				StorageVersion::<$test>::put(Releases::V5_0_0);
+				assert_ok!(migrations::pre_upgrade::<$test, ()>());
+				<Balances as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+				assert_ok!(migrations::post_upgrade::<$test, ()>());
+
+				let expected = |who| match who {
+					1 => vec![BalanceLock { id: ID_1, amount: 50, reasons: Reasons::Misc, until: None }],
+					2 => vec![single.clone()],
+					_ => current.clone(),
+				};
+				for who in 1..=3 {
+					assert_eq!(Balances::locks(&who), expected(who));
+				}
+				assert_eq!(StorageVersion::<$test>::get(), Releases::V8_0_0);
+
+				// running it again changes nothing.
+				migrations::migrate_single_locks::<$test, ()>();
+				for who in 1..=3 {
+					assert_eq!(Balances::locks(&who), expected(who));
+				}
+			});
+		}
+
+		#[test]
+		fn reserved_transfer_funds_the_reserve_of_dest() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				// an existing recipient.
+				assert_ok!(Balances::reserved_transfer(Some(1).into(), 2, 4));
+				assert_eq!(Balances::free_balance(1), 6);
+				assert_eq!((Balances::free_balance(2), Balances::reserved_balance(2)), (20, 4));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::ReservedTransfer(1, 2, 4)),
+				);
+				assert_eq!(Balances::total_issuance(), 110);
+
+				// locks still apply to the sender.
+				Balances::set_lock(ID_1, &1, 5, WithdrawReasons::all());
+				assert_noop!(
+					Balances::reserved_transfer(Some(1).into(), 2, 2),
+					Error::<$test, _>::LiquidityRestrictions,
+				);
+				assert_noop!(
+					Balances::reserved_transfer(Some(1).into(), 2, 7),
+					Error::<$test, _>::InsufficientBalance,
+				);
+			});
+		}
+
+		#[test]
+		fn reserved_transfer_creates_dest_only_from_the_existential_deposit() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				assert_noop!(
+					Balances::reserved_transfer(Some(2).into(), 5, 9),
+					Error::<$test, _>::ExistentialDeposit,
+				);
+				assert_ok!(Balances::reserved_transfer(Some(2).into(), 5, 10));
+				assert_eq!((Balances::free_balance(5), Balances::reserved_balance(5)), (0, 10));
+				assert_eq!(Balances::total_balance(&5), 10);
+				assert_eq!(Balances::free_balance(2), 190);
+			});
+		}
+
+		#[test]
+		fn fees_fall_back_to_the_reserve_only_when_allowed() {
+			<$ext_builder>::default().existential_deposit(1).build().execute_with(|| {
+				let fee = WithdrawReasons::TRANSACTION_PAYMENT;
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_ok!(Balances::reserve(&1, 90));
+				assert_noop!(Balances::withdraw(&1, 15, fee, AllowDeath), Error::<$test, _>::InsufficientBalance);
+
+				assert_ok!(Balances::set_fee_from_reserved(Some(1).into(), true));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::FeeFromReservedSet(1, true)),
+				);
+
+				// the free balance suffices.
+				assert_ok!(Balances::withdraw(&1, 4, fee, AllowDeath));
+				assert_eq!((Balances::free_balance(1), Balances::reserved_balance(1)), (6, 90));
+
+				// only the shortfall comes from the reserve, and only for fees.
+				assert_noop!(
+					Balances::withdraw(&1, 15, WithdrawReasons::TRANSFER, AllowDeath),
+					Error::<$test, _>::InsufficientBalance,
+				);
+				assert_ok!(Balances::withdraw(&1, 15, fee, AllowDeath));
+				assert_eq!((Balances::free_balance(1), Balances::reserved_balance(1)), (0, 81));
+				assert_eq!(Balances::total_issuance(), 81);
+			});
+		}
+
+		#[test]
+		fn fees_from_the_reserve_respect_locks_and_named_reserves() {
+			<$ext_builder>::default().existential_deposit(1).build().execute_with(|| {
+				let fee = WithdrawReasons::TRANSACTION_PAYMENT;
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_ok!(Balances::reserve_named(&[1u8; 8], &1, 60));
+				assert_ok!(Balances::reserve(&1, 30));
+				Balances::set_lock(ID_1, &1, 6, WithdrawReasons::all());
+				assert_ok!(Balances::set_fee_from_reserved(Some(1).into(), true));
+
+				// the locked free balance stays, so all of the fee comes from the anonymous reserve.
+				assert!(Balances::can_withdraw_for(&1, 34, fee));
+				assert_ok!(Balances::withdraw(&1, 34, fee, AllowDeath));
+				assert_eq!((Balances::free_balance(1), Balances::reserved_balance(1)), (6, 60));
+
+				// the named reserve is never used to pay fees.
+				assert!(!Balances::can_withdraw_for(&1, 1, fee));
+				assert_noop!(Balances::withdraw(&1, 1, fee, AllowDeath), Error::<$test, _>::InsufficientBalance);
+
+				assert_ok!(Balances::set_fee_from_reserved(Some(1).into(), false));
+				assert!(!Balances::fee_from_reserved(&1));
+			});
+		}
+
+		#[test]
+		fn transfer_bounded_keeps_min_remaining() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				assert_noop!(
+					Balances::transfer_bounded(Some(2).into(), 1, 6, 15),
+					Error::<$test, _>::WouldGoBelowMinimum,
+				);
+				assert_noop!(
+					Balances::transfer_bounded(Some(2).into(), 1, 21, 0),
+					Error::<$test, _>::WouldGoBelowMinimum,
+				);
+				assert_ok!(Balances::transfer_bounded(Some(2).into(), 1, 5, 15));
+				assert_eq!(Balances::free_balance(2), 15);
+				assert_eq!(Balances::free_balance(1), 15);
+
+				// it is still a keep-alive transfer.
+				assert_noop!(
+					Balances::transfer_bounded(Some(2).into(), 1, 15, 0),
+					Error::<$test, _>::KeepAlive,
+				);
+			});
+		}
+
+		#[test]
+		fn set_balance_reports_why_an_account_was_not_reaped() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				// a lock blocks reaping, and nothing is changed.
+				Balances::set_lock(ID_1, &1, 5, WithdrawReasons::all());
+				assert_err!(
+					Balances::set_balance(RawOrigin::Root.into(), 1, 0, 0),
+					Error::<$test, _>::CannotReap,
+				);
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::ReapFailed(1, ReapBlocker::Locks)),
+				);
+				assert_eq!(Balances::free_balance(1), 10);
+				Balances::remove_lock(ID_1, &1);
+
+				// so does a consumer while this pallet is the only provider.
+				assert_ok!(System::inc_consumers(&1));
+				assert_err!(
+					Balances::set_balance(RawOrigin::Root.into(), 1, 0, 0),
+					Error::<$test, _>::CannotReap,
+				);
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::ReapFailed(1, ReapBlocker::ConsumerRefs)),
+				);
+				assert_eq!(Balances::free_balance(1), 10);
+				System::dec_consumers(&1);
+			});
+		}
+
+		#[test]
+		fn set_balance_to_zero_with_held_provider_ref_leaves_account_alive() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				// e.g. a staking bond keeps the account alive.
+				System::inc_providers(&1);
+				assert_ok!(System::inc_consumers(&1));
+
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 1, 0, 0));
+				assert_eq!(Balances::total_balance(&1), 0);
+				assert!(System::account_exists(&1));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::ReapFailed(1, ReapBlocker::ProviderRefs)),
+				);
+
+				// without the extra provider there is nothing to report.
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 2, 0, 0));
+				assert!(!System::events().iter().any(|record| record.event ==
+					Event::pallet_balances(crate::Event::ReapFailed(2, ReapBlocker::ProviderRefs))
+				));
+			});
+		}
+
+		#[test]
+		fn cached_frozen_balances_match_the_locks() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				let assert_cache_matches = || {
+					let account = Balances::account(&1);
+					assert_eq!(
+						(account.misc_frozen, account.fee_frozen),
+						Balances::frozen_by_locks(&Balances::locks(&1)),
+					);
+				};
+
+				Balances::set_lock(ID_1, &1, 40, WithdrawReasons::TRANSACTION_PAYMENT);
+				assert_cache_matches();
+				Balances::set_lock(ID_2, &1, 20, WithdrawReasons::TRANSFER);
+				assert_cache_matches();
+				Balances::extend_lock(ID_2, &1, 60, WithdrawReasons::TRANSACTION_PAYMENT);
+				assert_cache_matches();
+				assert_eq!(Balances::account(&1).misc_frozen, 60);
+				assert_eq!(Balances::account(&1).fee_frozen, 60);
+				Balances::remove_lock(ID_2, &1);
+				assert_cache_matches();
+				assert_eq!(Balances::account(&1).misc_frozen, 0);
+				assert_eq!(Balances::account(&1).fee_frozen, 40);
+
+				assert_ok!(Balances::try_set_lock_until(ID_2, &1, 70, WithdrawReasons::all(), 3));
+				assert_cache_matches();
+				System::set_block_number(3);
+				assert_ok!(Balances::prune_expired_locks(Some(2).into(), 1));
+				assert_cache_matches();
+				Balances::remove_lock(ID_1, &1);
+				assert_cache_matches();
+				assert_eq!(Balances::account(&1).fee_frozen, 0);
+			});
+		}
+
+		#[test]
+		fn reap_account_works_for_accounts_below_a_raised_existential_deposit() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				let issuance = Balances::total_issuance();
+				// still above the existential deposit.
+				assert_noop!(Balances::reap_account(Some(3).into(), 1), Error::<$test, _>::AccountNotReapable);
+
+				assert_ok!(Balances::set_existential_deposit(RawOrigin::Root.into(), 50));
+				let info = Balances::reap_account(Some(3).into(), 1).unwrap();
+				assert_eq!(info.pays_fee, Pays::No);
+				assert_eq!(Balances::total_balance(&1), 0);
+				assert!(!System::account_exists(&1));
+				assert_eq!(Balances::total_issuance(), issuance - 10);
+				assert!(events().ends_with(&[
+					Event::pallet_balances(crate::Event::DustLost(1, 10)),
+					Event::pallet_balances(crate::Event::Rescinded(10)),
+				]));
+
+				// a dead account can't be reaped again.
+				assert_noop!(Balances::reap_account(Some(3).into(), 1), Error::<$test, _>::AccountNotReapable);
+			});
+		}
+
+		#[test]
+		fn reap_account_rejects_accounts_in_use() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				Balances::set_lock(ID_1, &1, 5, WithdrawReasons::all());
+				assert_ok!(Balances::set_existential_deposit(RawOrigin::Root.into(), 50));
+				assert_noop!(Balances::reap_account(Some(3).into(), 1), Error::<$test, _>::AccountNotReapable);
+
+				Balances::remove_lock(ID_1, &1);
+				assert_ok!(System::inc_consumers(&2));
+				assert_noop!(Balances::reap_account(Some(3).into(), 2), Error::<$test, _>::AccountNotReapable);
+
+				assert_ok!(Balances::reap_account(Some(3).into(), 1));
+			});
+		}
+
+		#[test]
+		fn compact_reserves_removes_only_zero_named_reserves() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				let id_1 = [1u8; 8];
+				let id_2 = [2u8; 8];
+				let id_3 = [3u8; 8];
+				assert_ok!(Balances::reserve_named(&id_1, &1, 10));
+				assert_ok!(Balances::reserve_named(&id_2, &1, 20));
+				assert_ok!(Balances::reserve_named(&id_3, &1, 30));
+
+				// slashing a named reserve in full leaves a zero entry behind.
+				let _ = Balances::slash_reserved_named(&id_1, &1, 10);
+				let _ = Balances::slash_reserved_named(&id_3, &1, 30);
+				assert_eq!(Balances::reserves(&1).len(), 3);
+
+				let info = Balances::compact_reserves(Some(1).into()).unwrap();
+				assert_eq!(info.actual_weight, Some(<() as WeightInfo>::compact_reserves(2)));
+				assert_eq!(Balances::reserves(&1), vec![ReserveData { id: id_2, amount: 20 }]);
+				assert_eq!(Balances::reserved_balance(1), 20);
+
+				// the last zero entry removes the reserves altogether.
+				let _ = Balances::slash_reserved_named(&id_2, &1, 20);
+				assert_ok!(Balances::compact_reserves(Some(1).into()));
+				assert!(!Reserves::<$test>::contains_key(&1));
+
+				// nothing to compact is fine.
+				assert_ok!(Balances::compact_reserves(Some(2).into()));
+			});
+		}
+
+		#[test]
+		fn frozen_accounts_can_receive_but_not_send() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				assert_noop!(Balances::freeze(Some(2).into(), 1), BadOrigin);
+				assert_ok!(Balances::freeze(RawOrigin::Root.into(), 1));
+				assert!(Balances::is_frozen(&1));
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::AccountFrozen(1)));
+
+				assert_noop!(Balances::transfer(Some(1).into(), 2, 5), Error::<$test, _>::AccountFrozen);
+				assert_noop!(Balances::reserve(&1, 5), Error::<$test, _>::AccountFrozen);
+				assert_noop!(
+					Balances::withdraw(&1, 5, WithdrawReasons::TRANSACTION_PAYMENT, AllowDeath),
+					Error::<$test, _>::AccountFrozen,
+				);
+				assert!(!Balances::can_reserve(&1, 5));
+				assert!(!Balances::can_withdraw_for(&1, 5, WithdrawReasons::TRANSFER));
+
+				// incoming transfers still succeed.
+				assert_ok!(Balances::transfer(Some(2).into(), 1, 5));
+				assert_eq!(Balances::free_balance(1), 15);
+
+				// and root may still move funds off the account.
+				assert_ok!(Balances::force_transfer(RawOrigin::Root.into(), 1, 3, 10));
+				assert_eq!(Balances::free_balance(1), 5);
+				assert_eq!(Balances::free_balance(3), 40);
+
+				assert_ok!(Balances::thaw(RawOrigin::Root.into(), 1));
+				assert!(!Balances::is_frozen(&1));
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::AccountThawed(1)));
+				assert_ok!(Balances::transfer(Some(1).into(), 2, 5));
+			});
+		}
+
+		#[test]
+		fn try_deposit_returns_what_it_could_not_deposit() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				// a live account.
+				let imbalance = Balances::try_deposit(&1, 5).unwrap();
+				assert_eq!(imbalance.peek(), 5);
+				drop(imbalance);
+				assert_eq!(Balances::free_balance(1), 105);
+
+				// a reaped account.
+				assert_ok!(Balances::transfer(Some(1).into(), 2, 105));
+				assert!(!System::account_exists(&1));
+				assert_eq!(
+					Balances::try_deposit(&1, 50).map(|i| i.peek()),
+					Err((50, Error::<$test, _>::DeadAccount.into())),
+				);
+				assert_eq!(Balances::free_balance(1), 0);
+
+				// below the existential deposit into an account which never existed.
+				assert_eq!(
+					Balances::try_deposit(&5, 3).map(|i| i.peek()),
+					Err((3, Error::<$test, _>::DeadAccount.into())),
+				);
+				assert!(!System::account_exists(&5));
+			});
+		}
+
+		#[test]
+		fn on_idle_prunes_stale_locks_within_the_weight_budget() {
+			<$ext_builder>::default().build().execute_with(|| {
+				for who in 1..=5 {
+					let _ = Balances::deposit_creating(&who, 100);
+					assert_ok!(Balances::try_set_lock_until(ID_1, &who, 50, WithdrawReasons::all(), 3));
+					Balances::set_lock(ID_2, &who, 10, WithdrawReasons::all());
+				}
+				System::set_block_number(4);
+
+				let db_weight = <$test as frame_system::Config>::DbWeight::get();
+				let per_account = db_weight.reads(1)
+					+ <() as WeightInfo>::prune_expired_locks(<$test as Config>::MaxLocks::get());
+				let budget = db_weight.reads_writes(1, 1) + 2 * per_account;
+
+				// too tight for even a single account.
+				assert_eq!(Balances::on_idle(4, budget - per_account - 1), 0);
+				assert!((1..=5).all(|who| Balances::locks(&who).len() == 2));
+
+				let mut passes = 0;
+				while (1..=5).any(|who| Balances::locks(&who).len() > 1) {
+					assert!(Balances::on_idle(4, budget) <= budget);
+					passes += 1;
+				}
+				assert_eq!(passes, 3);
+				for who in 1..=5 {
+					assert_eq!(
+						Balances::locks(&who),
+						vec![BalanceLock { id: ID_2, amount: 10, reasons: Reasons::All, until: None }],
+					);
+				}
+			});
+		}
+
+		#[test]
+		fn is_reapable_tracks_consumer_refs() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_eq!(Balances::account_refs(&1), (1, 0, 0));
+				assert!(Balances::is_reapable(&1));
+
+				// e.g. staking bonding the account.
+				assert_ok!(System::inc_consumers(&1));
+				assert_eq!(Balances::account_refs(&1), (1, 1, 0));
+				assert!(!Balances::is_reapable(&1));
+
+				System::dec_consumers(&1);
+				assert_eq!(Balances::account_refs(&1), (1, 0, 0));
+				assert!(Balances::is_reapable(&1));
+			});
+		}
+
+		#[test]
+		fn slash_all_and_reap_takes_everything_and_reaps() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				assert_ok!(Balances::reserve(&1, 30));
+				Balances::set_lock(ID_1, &1, 50, WithdrawReasons::all());
+				assert_ok!(Balances::set_fee_from_reserved(Some(1).into(), true));
+				assert_ok!(Balances::freeze(RawOrigin::Root.into(), 1));
+				// e.g. staking still holding its ledger.
+				assert_ok!(System::inc_consumers(&1));
+
+				// only the reference of the locks is released, so staking must drop its own first.
+				assert_eq!(Balances::slash_all_and_reap(&1).err(), Some(Error::<$test, _>::CannotReap.into()));
+				assert_eq!(Balances::total_balance(&1), 100);
+				assert_eq!(Balances::locks(&1).len(), 1);
+				System::dec_consumers(&1);
+
+				let imbalance = Balances::slash_all_and_reap(&1).unwrap();
+				assert_eq!(imbalance.peek(), 100);
+				assert_eq!(Balances::total_balance(&1), 0);
+				assert!(Balances::locks(&1).is_empty());
+				assert!(!FrozenAccounts::<$test>::contains_key(1));
+				assert!(!FeeFromReserved::<$test>::contains_key(1));
+				assert!(!System::account_exists(&1));
+
+				drop(imbalance);
+				assert_eq!(Balances::total_issuance(), 0);
+			});
+		}
+
+		#[test]
+		fn slash_all_and_reap_reports_an_account_kept_alive_by_another_provider() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				System::inc_providers(&1);
+
+				assert_eq!(Balances::slash_all_and_reap(&1).unwrap().peek(), 100);
+				assert_eq!(Balances::total_balance(&1), 0);
+				assert!(System::account_exists(&1));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::ReapFailed(1, ReapBlocker::ProviderRefs)),
+				);
+			});
+		}
+
+		#[test]
+		fn genesis_sets_reserved_balances_and_locks() {
+			($existential_deposit).with(|v| *v.borrow_mut() = 1);
+			let mut t = frame_system::GenesisConfig::default().build_storage::<$test>().unwrap();
+			pallet_balances::GenesisConfig::<$test> {
+				balances: vec![(1, 100), (2, 50)],
+				reserved_balances: vec![(1, 30)],
+				locks: vec![(1, ID_1, 60, WithdrawReasons::all()), (2, ID_2, 50, WithdrawReasons::TRANSFER)],
+			}.assimilate_storage(&mut t).unwrap();
+
+			sp_io::TestExternalities::new(t).execute_with(|| {
+				assert_eq!(Balances::total_issuance(), 180);
+				assert_eq!(Balances::free_balance(&1), 100);
+				assert_eq!(Balances::reserved_balance(&1), 30);
+				assert_eq!(Balances::free_balance(&2), 50);
+				assert_eq!(Balances::reserved_balance(&2), 0);
+				assert_eq!(
+					Balances::locks(&1),
+					vec![BalanceLock { id: ID_1, amount: 60, reasons: Reasons::All, until: None }],
+				);
+				assert_eq!(
+					Balances::locks(&2),
+					vec![BalanceLock { id: ID_2, amount: 50, reasons: Reasons::Misc, until: None }],
+				);
+				assert_noop!(
+					<Balances as Currency<_>>::transfer(&1, &3, 41, AllowDeath),
+					Error::<$test, _>::LiquidityRestrictions
+				);
+			});
+		}
+
+		#[test]
+		#[should_panic = "locked balance exceeds the free balance in genesis."]
+		fn cannot_set_genesis_lock_above_free_balance() {
+			($existential_deposit).with(|v| *v.borrow_mut() = 1);
+			let mut t = frame_system::GenesisConfig::default().build_storage::<$test>().unwrap();
+			let _ = pallet_balances::GenesisConfig::<$test> {
+				balances: vec![(1, 10)],
+				reserved_balances: vec![(1, 20)],
+				locks: vec![(1, ID_1, 11, WithdrawReasons::all())],
+			}.assimilate_storage(&mut t).unwrap();
+		}
+
+		#[test]
+		#[should_panic = "free and reserved balances overflow in genesis."]
+		fn cannot_set_genesis_balances_overflowing() {
+			($existential_deposit).with(|v| *v.borrow_mut() = 1);
+			let mut t = frame_system::GenesisConfig::default().build_storage::<$test>().unwrap();
+			let _ = pallet_balances::GenesisConfig::<$test> {
+				balances: vec![(1, u64::max_value())],
+				reserved_balances: vec![(1, 1)],
+				locks: vec![],
+			}.assimilate_storage(&mut t).unwrap();
+		}
+
+		#[test]
+		fn slash_collateral_only_touches_its_named_reserve() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				let order = [1u8; 8];
+				let other = [2u8; 8];
+				assert_ok!(Balances::reserve_named(&order, &1, 30));
+				assert_ok!(Balances::reserve_named(&other, &1, 20));
+				assert_ok!(Balances::reserve(&1, 10));
+
+				// fully covered.
+				let (imbalance, shortfall) = Balances::slash_collateral(&1, &order, 10);
+				assert_eq!((imbalance.peek(), shortfall), (10, 0));
+				assert_eq!(Balances::reserved_balance_named(&order, &1), 20);
+
+				// partially covered.
+				let (imbalance, shortfall) = Balances::slash_collateral(&1, &order, 25);
+				assert_eq!((imbalance.peek(), shortfall), (20, 5));
+				assert_eq!(Balances::reserved_balance_named(&order, &1), 0);
+
+				// empty.
+				let (imbalance, shortfall) = Balances::slash_collateral(&1, &order, 5);
+				assert_eq!((imbalance.peek(), shortfall), (0, 5));
+
+				assert_eq!(Balances::free_balance(&1), 40);
+				assert_eq!(Balances::reserved_balance_named(&other, &1), 20);
+				assert_eq!(Balances::reserved_balance(&1), 30);
+			});
+		}
+
+		#[test]
+		fn simulate_transfer_reports_reaping_dust_and_creation() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				// 1 is left with 5, below the existential deposit.
+				assert_eq!(
+					Balances::simulate_transfer(&1, &2, 95),
+					SimulatedTransfer { sender_reaped: true, dust_lost: 5, recipient_created: false },
+				);
+				// everything is sent, so nothing is lost.
+				assert_eq!(
+					Balances::simulate_transfer(&1, &2, 100),
+					SimulatedTransfer { sender_reaped: true, dust_lost: 0, recipient_created: false },
+				);
+				assert_eq!(
+					Balances::simulate_transfer(&1, &5, 50),
+					SimulatedTransfer { sender_reaped: false, dust_lost: 0, recipient_created: true },
+				);
+				assert_eq!(Balances::simulate_transfer(&1, &2, 50), Default::default());
+
+				// nothing changed.
+				assert_eq!(Balances::free_balance(&1), 100);
+				assert_eq!(Balances::free_balance(&2), 200);
+				assert!(!System::account_exists(&5));
+			});
+		}
+
+		#[test]
+		fn force_set_lock_and_force_remove_lock_update_frozen_balances() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				assert_noop!(
+					Balances::force_set_lock(Some(1).into(), 1, ID_1, 5, WithdrawReasons::all()),
+					BadOrigin,
+				);
+
+				Balances::set_lock(ID_2, &1, 3, WithdrawReasons::TRANSACTION_PAYMENT);
+				assert_ok!(Balances::force_set_lock(RawOrigin::Root.into(), 1, ID_1, 5, WithdrawReasons::TRANSFER));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::LockForceSet(1, ID_1, 5)),
+				);
+				assert_eq!(
+					Balances::locks(&1),
+					vec![
+						BalanceLock { id: ID_2, amount: 3, reasons: Reasons::Fee, until: None },
+						BalanceLock { id: ID_1, amount: 5, reasons: Reasons::Misc, until: None },
+					],
+				);
+				assert_eq!(Balances::frozen_balance_breakdown(&1), (3, 5));
+
+				assert_ok!(Balances::force_remove_lock(RawOrigin::Root.into(), 1, ID_2));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::LockForceRemoved(1, ID_2)),
+				);
+				assert_eq!(
+					Balances::locks(&1),
+					vec![BalanceLock { id: ID_1, amount: 5, reasons: Reasons::Misc, until: None }],
+				);
+				assert_eq!(Balances::frozen_balance_breakdown(&1), (0, 5));
+			});
+		}
+
+		#[test]
+		fn transfer_all_with_nothing_to_transfer_is_refunded() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				Balances::set_lock(ID_1, &1, 10, WithdrawReasons::all());
+				let _ = events();
+
+				let post_info = Balances::transfer_all(Some(1).into(), 2, false).unwrap();
+				assert_eq!(
+					post_info.actual_weight,
+					Some(<$test as frame_system::Config>::DbWeight::get().reads(1)),
+				);
+				assert_eq!(events(), []);
+				assert_eq!(Balances::free_balance(1), 10);
+				assert_eq!(Balances::free_balance(2), 20);
+
+				// anything to transfer pays for the whole transfer.
+				let post_info = Balances::transfer_all(Some(3).into(), 5, false).unwrap();
+				assert_eq!(post_info.actual_weight, None);
+				assert_eq!(Balances::free_balance(5), 30);
+			});
+		}
+
+		#[test]
+		fn transfer_all_below_existential_deposit_still_fails() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 15);
+				assert_noop!(
+					Balances::transfer_all(Some(1).into(), 2, true),
+					Error::<$test, _>::ExistentialDeposit,
+				);
+			});
+		}
+
+		#[test]
+		fn last_active_tracks_balance_changes() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				assert_eq!(Balances::last_active(&1), Some(0));
+
+				System::set_block_number(5);
+				assert_ok!(Balances::transfer(Some(1).into(), 2, 5));
+				assert_eq!(Balances::last_active(&1), Some(5));
+				assert_eq!(Balances::last_active(&2), Some(5));
+
+				System::set_block_number(7);
+				assert_ok!(Balances::reserve(&3, 10));
+				assert_eq!(Balances::last_active(&3), Some(7));
+
+				// neither reads nor locks change it.
+				let _ = Balances::free_balance(&4);
+				let _ = Balances::simulate_transfer(&4, &1, 10);
+				Balances::set_lock(ID_1, &4, 10, WithdrawReasons::all());
+				assert_eq!(Balances::last_active(&4), Some(0));
+
+				// reaped accounts are forgotten.
+				assert_ok!(Balances::transfer_all(Some(1).into(), 2, false));
+				assert_eq!(Balances::last_active(&1), None);
+			});
+		}
+
+		#[test]
+		fn migration_initializes_last_active() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				LastActive::<$test>::remove_all();
+				StorageVersion::<$test>::put(Releases::V6_0_0);
+				System::set_block_number(9);
+
+				<Balances as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+
+				assert_eq!(StorageVersion::<$test>::get(), Releases::V8_0_0);
+				for who in &[1, 2, 3, 4, 12] {
+					assert_eq!(Balances::last_active(who), Some(9));
+				}
+				assert_eq!(Balances::last_active(&5), None);
+			});
+		}
+
+		#[test]
+		fn repatriate_reserved_weight_is_the_benchmarked_one() {
+			<$ext_builder>::default().build().execute_with(|| {
+				assert_eq!(
+					Balances::repatriate_reserved_weight(Status::Free),
+					<() as WeightInfo>::repatriate_reserved_free(),
+				);
+				assert_eq!(
+					Balances::repatriate_reserved_weight(Status::Reserved),
+					<() as WeightInfo>::repatriate_reserved(),
+				);
+			});
+		}
+
+		#[test]
+		fn adjust_balance_applies_deltas() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				let issuance = Balances::total_issuance();
+				assert_noop!(Balances::adjust_balance(Some(1).into(), 1, 5, 0), BadOrigin);
+
+				assert_ok!(Balances::adjust_balance(RawOrigin::Root.into(), 1, 5, 7));
+				assert_eq!(Balances::free_balance(&1), 15);
+				assert_eq!(Balances::reserved_balance(&1), 7);
+				assert_eq!(Balances::total_issuance(), issuance + 12);
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::BalanceSet(1, 15, 7)),
+				);
+
+				assert_ok!(Balances::adjust_balance(RawOrigin::Root.into(), 1, -10, -2));
+				assert_eq!(Balances::free_balance(&1), 5);
+				assert_eq!(Balances::reserved_balance(&1), 5);
+				assert_eq!(Balances::total_issuance(), issuance);
+
+				assert_noop!(
+					Balances::adjust_balance(RawOrigin::Root.into(), 1, -6, 0),
+					Error::<$test, _>::AdjustmentUnderflow,
+				);
+				assert_noop!(
+					Balances::adjust_balance(RawOrigin::Root.into(), 1, 0, i128::min_value()),
+					Error::<$test, _>::AdjustmentUnderflow,
+				);
+				assert_noop!(
+					Balances::adjust_balance(RawOrigin::Root.into(), 1, i128::max_value(), 0),
+					Error::<$test, _>::Overflow,
+				);
+			});
+		}
+
+		#[test]
+		fn claimable_transfers_stay_reserved_until_claimed() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				assert_ok!(Balances::transfer_claimable(Some(1).into(), 5, 6));
+				assert_ok!(Balances::transfer_claimable(Some(2).into(), 5, 4));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::TransferClaimable(2, 5, 4)),
+				);
+				assert_eq!(Balances::free_balance(&5), 0);
+				assert_eq!(Balances::reserved_balance(&5), 10);
+				assert_eq!(Balances::claimable(&5), 10);
+				let id = <$test as Config>::ClaimableReserveId::get();
+				assert_eq!(Balances::reserved_balance_named(&id, &5), 10);
+
+				// unclaimed funds can't be spent.
+				assert_noop!(
+					Balances::transfer(Some(5).into(), 1, 1),
+					Error::<$test, _>::InsufficientBalance,
+				);
+				assert_ok!(Balances::set_fee_from_reserved(Some(5).into(), true));
+				assert!(!Balances::can_withdraw_for(&5, 1, WithdrawReasons::TRANSACTION_PAYMENT));
+
+				assert_ok!(Balances::claim(Some(5).into()));
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::Claimed(5, 10)));
+				assert_eq!(Balances::free_balance(&5), 10);
+				assert_eq!(Balances::reserved_balance(&5), 0);
+				assert_eq!(Balances::claimable(&5), 0);
+				assert_ok!(Balances::transfer(Some(5).into(), 1, 1));
+
+				assert_noop!(Balances::claim(Some(5).into()), Error::<$test, _>::NothingToClaim);
+				assert_noop!(
+					Balances::transfer_claimable(Some(1).into(), 1, 1),
+					Error::<$test, _>::ClaimableToSelf,
+				);
+			});
+		}
+
+		#[test]
+		fn claimable_balance_follows_its_named_reserve() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				let id = <$test as Config>::ClaimableReserveId::get();
+				assert_ok!(Balances::transfer_claimable(Some(1).into(), 5, 10));
+
+				// what was slashed from the reserve is no longer claimable.
+				let (slashed, _) = Balances::slash_reserved_named(&id, &5, 4);
+				drop(slashed);
+				assert_ok!(Balances::claim(Some(5).into()));
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::Claimed(5, 6)));
+				assert_eq!(Balances::free_balance(&5), 6);
+				assert!(!Claimable::<$test>::contains_key(5));
+
+				// nor does it outlive its account.
+				assert_ok!(Balances::transfer_claimable(Some(1).into(), 6, 5));
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 6, 0, 0));
+				assert!(!Claimable::<$test>::contains_key(6));
+				assert_eq!(Balances::claimable(&6), 0);
+			});
+		}
+
+		#[test]
+		fn issuance_events_account_for_every_issuance_change() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				let issuance = Balances::total_issuance();
+				let _ = events();
+
+				// a mint, a burn and a slash reaping its account.
+				let _ = Balances::deposit_creating(&5, 50);
+				assert_ok!(Balances::burn(Some(2).into(), 15, true));
+				let _ = Balances::slash(&1, 10);
+				assert!(!System::account_exists(&1));
+
+				let net = events().into_iter().fold(0i128, |net, event| match event {
+					Event::pallet_balances(crate::Event::Issued(amount)) => net + amount as i128,
+					Event::pallet_balances(crate::Event::Rescinded(amount)) => net - amount as i128,
+					_ => net,
+				});
+				assert_eq!(Balances::total_issuance(), issuance + 50 - 15 - 10);
+				assert_eq!(net, Balances::total_issuance() as i128 - issuance as i128);
+			});
+		}
+
+		#[test]
+		fn transfer_to_subaccount_pays_the_derived_account() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				use sp_runtime::{ModuleId, traits::AccountIdConversion};
+				let sub = b"order-7".to_vec();
+				let dest: u64 = ModuleId(*b"py/markt").into_sub_account(&sub);
+				assert_eq!(Balances::sub_account(*b"py/markt", &sub), dest);
+
+				assert_ok!(Balances::transfer_to_subaccount(Some(1).into(), *b"py/markt", sub, 5));
+				assert_eq!(Balances::free_balance(1), 5);
+				assert_eq!(Balances::free_balance(dest), 5);
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::SubAccountTransfer(1, dest, 5)),
+				);
+
+				assert_noop!(
+					Balances::transfer_to_subaccount(Some(1).into(), *b"py/markt", vec![0; 17], 1),
+					Error::<$test, _>::SubAccountSeedTooLong,
+				);
+			});
+		}
+
+		#[test]
+		fn paused_transfers_fail_but_root_can_still_move_funds() {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+				assert_noop!(Balances::pause_transfers(Some(1).into()), BadOrigin);
+				assert_ok!(Balances::pause_transfers(RawOrigin::Root.into()));
+				assert!(Balances::transfers_paused());
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::TransfersPaused));
+
+				assert_noop!(Balances::transfer(Some(1).into(), 2, 5), Error::<$test, _>::TransfersPaused);
+				assert_noop!(
+					Balances::transfer_keep_alive(Some(1).into(), 2, 5),
+					Error::<$test, _>::TransfersPaused,
+				);
+				assert_noop!(Balances::transfer_all(Some(1).into(), 2, false), Error::<$test, _>::TransfersPaused);
+				assert_noop!(Balances::reserved_transfer(Some(1).into(), 2, 5), Error::<$test, _>::TransfersPaused);
+				assert_noop!(Balances::burn(Some(1).into(), 5, true), Error::<$test, _>::TransfersPaused);
+
+				// queries and root keep working.
+				assert_eq!(Balances::transferable_balance(&1), 9);
+				assert_ok!(Balances::force_transfer(RawOrigin::Root.into(), 1, 2, 5));
+				assert_eq!(Balances::free_balance(1), 5);
+				assert_eq!(Balances::free_balance(2), 25);
+
+				assert_ok!(Balances::resume_transfers(RawOrigin::Root.into()));
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::TransfersResumed));
+				assert_ok!(Balances::transfer(Some(1).into(), 2, 5));
+			});
+		}
+
+		#[test]
+		fn mint_tops_up_or_creates_accounts() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				let issuance = Balances::total_issuance();
+				assert_noop!(Balances::mint(Some(1).into(), 1, 5), BadOrigin);
+
+				// an existing account may get less than the existential deposit.
+				assert_ok!(Balances::mint(RawOrigin::Root.into(), 1, 5));
+				assert_eq!(Balances::free_balance(1), 105);
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::Minted(1, 5)));
+
+				assert_ok!(Balances::mint(RawOrigin::Root.into(), 5, 10));
+				assert_eq!(Balances::free_balance(5), 10);
+				assert!(System::account_exists(&5));
+				assert_eq!(Balances::total_issuance(), issuance + 15);
+
+				assert_noop!(
+					Balances::mint(RawOrigin::Root.into(), 6, 9),
+					Error::<$test, _>::ExistentialDeposit,
+				);
+				assert_eq!(Balances::total_issuance(), issuance + 15);
+			});
+		}
+
+		#[test]
+		fn vested_transfer_releases_after_the_cliff() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				let schedule = VestingSchedule { locked: 50, per_block: 5, starting_block: 1, cliff: 5 };
+				assert_noop!(
+					Balances::vested_transfer(Some(1).into(), 2, VestingSchedule { per_block: 0, ..schedule }),
+					Error::<$test, _>::InvalidVestingSchedule,
+				);
+				assert_ok!(Balances::vested_transfer(Some(1).into(), 2, schedule));
+				assert_eq!(Balances::free_balance(2), 250);
+				assert_eq!(Balances::vesting(2), Some(schedule));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::VestedTransfer(1, 2, 50)),
+				);
+				assert_noop!(
+					Balances::vested_transfer(Some(1).into(), 2, schedule),
+					Error::<$test, _>::ExistingVestingSchedule,
+				);
+				assert_noop!(Balances::claim_vested(Some(3).into()), Error::<$test, _>::NotVesting);
+
+				// before the cliff everything stays locked.
+				System::set_block_number(4);
+				assert_eq!(Balances::vested_balance(&2), 0);
+				assert_ok!(Balances::claim_vested(Some(2).into()));
+				assert_eq!(Balances::locks(2)[0].amount, 50);
+				assert_noop!(
+					<Balances as Currency<_>>::transfer(&2, &3, 201, AllowDeath),
+					Error::<$test, _>::LiquidityRestrictions,
+				);
+
+				// mid-schedule the release is counted from the starting block.
+				System::set_block_number(7);
+				assert_eq!(Balances::vested_balance(&2), 30);
+				assert_ok!(Balances::claim_vested(Some(2).into()));
+				assert_eq!(Balances::locks(2)[0].amount, 20);
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::VestingUpdated(2, 20)));
+				assert_ok!(<Balances as Currency<_>>::transfer(&2, &3, 230, AllowDeath));
+
+				// past the end the lock and the schedule are gone.
+				System::set_block_number(11);
+				assert_eq!(Balances::vested_balance(&2), 50);
+				assert_ok!(Balances::claim_vested(Some(2).into()));
+				assert!(Balances::locks(2).is_empty());
+				assert_eq!(Balances::vesting(2), None);
+				assert_eq!(Balances::vested_balance(&2), 0);
+				assert_ok!(<Balances as Currency<_>>::transfer(&2, &3, 20, AllowDeath));
+			});
+		}
+
+		#[test]
+		fn expired_reserves_are_released_on_access_or_on_request() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				let order = [1u8; 8];
+				let other = [2u8; 8];
+
+				assert_ok!(Balances::reserve_with_expiry(&order, &1, 30, 3));
+				assert_eq!(Balances::reserved_balance_named(&order, &1), 30);
+				assert_eq!(Balances::reserve_expiries(1), vec![(order, 3, 30)]);
+
+				// nothing is released before the expiry.
+				System::set_block_number(2);
+				assert_ok!(Balances::release_expired_reserves(Some(2).into(), 1));
+				assert_eq!(Balances::reserved_balance_named(&order, &1), 30);
+
+				// changing the named reserves releases the expired one on the way.
+				System::set_block_number(3);
+				assert_ok!(Balances::reserve_named(&other, &1, 10));
+				assert_eq!(Balances::reserved_balance_named(&order, &1), 0);
+				assert_eq!(Balances::reserved_balance(1), 10);
+				assert!(Balances::reserve_expiries(1).is_empty());
+				assert!(System::events().iter().any(|record| record.event ==
+					Event::pallet_balances(crate::Event::ReserveExpired(1, order, 30))
+				));
+
+				// or anyone can release it explicitly.
+				assert_ok!(Balances::reserve_with_expiry(&order, &1, 20, 5));
+				System::set_block_number(5);
+				assert_ok!(Balances::release_expired_reserves(Some(2).into(), 1));
+				assert_eq!(Balances::reserved_balance_named(&order, &1), 0);
+				assert_eq!(Balances::free_balance(1), 90);
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::ReserveExpired(1, order, 20)));
+			});
+		}
+
+		#[test]
+		fn balance_summary_is_consistent_with_transfers() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				let summary = |free, reserved, frozen, transferable| BalanceSummary {
+					free,
+					reserved,
+					frozen,
+					transferable,
+					total: free + reserved,
+				};
+
+				// an unlocked account keeps the existential deposit back.
+				assert_eq!(Balances::balance_summary(&1), summary(100, 0, 0, 90));
+				assert_eq!(Balances::balance_summary(&5), summary(0, 0, 0, 0));
+
+				// the reserve covers the existential deposit.
+				assert_ok!(Balances::reserve(&1, 20));
+				assert_eq!(Balances::balance_summary(&1), summary(80, 20, 0, 80));
+
+				// the most restrictive lock counts.
+				Balances::set_lock(ID_1, &1, 30, WithdrawReasons::all());
+				Balances::set_lock(ID_2, &1, 50, WithdrawReasons::all());
+				assert_eq!(Balances::balance_summary(&1), summary(80, 20, 50, 30));
+				assert_noop!(
+					<Balances as Currency<_>>::transfer(&1, &2, 31, AllowDeath),
+					Error::<$test, _>::LiquidityRestrictions,
+				);
+				assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 30, AllowDeath));
+				assert_eq!(Balances::balance_summary(&1), summary(50, 20, 50, 0));
+
+				// a fully locked account can't transfer anything.
+				assert_eq!(Balances::unreserve(&1, 20), 0);
+				Balances::set_lock(ID_1, &1, 100, WithdrawReasons::all());
+				assert_eq!(Balances::balance_summary(&1), summary(70, 0, 100, 0));
+			});
+		}
+
+		#[test]
+		fn try_state_catches_injected_drift() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				assert_ok!(Balances::do_try_state());
+				assert_ok!(Balances::transfer(Some(1).into(), 5, 50));
+				assert_ok!(Balances::reserve_named(&[1u8; 8], &2, 30));
+				Balances::set_lock(ID_1, &3, 500, WithdrawReasons::all());
+				let _ = Balances::slash(&4, 15);
+				assert_ok!(Balances::do_try_state());
+
+				TotalIssuance::<$test>::mutate(|issuance| *issuance += 1);
+				assert_eq!(Balances::do_try_state(), Err("the total issuance doesn't match the balances"));
+				TotalIssuance::<$test>::mutate(|issuance| *issuance -= 1);
+
+				Reserves::<$test>::mutate(&2, |reserves| reserves[0].amount = 31);
+				assert_eq!(Balances::do_try_state(), Err("the named reserves exceed the reserved balance"));
+			});
+		}
+
+		#[test]
+		fn dry_run_transfer_matches_the_real_outcome() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				let _ = events();
+				let check = |from, to, value| {
+					let issuance = Balances::total_issuance();
+					let (from_balance, to_balance) = (Balances::total_balance(&from), Balances::total_balance(&to));
+					let dry_run = Balances::dry_run_transfer(from, to, value);
+					assert_eq!(Balances::total_balance(&from), from_balance);
+					assert_eq!(Balances::total_balance(&to), to_balance);
+					assert_eq!(Balances::total_issuance(), issuance);
+					assert!(events().is_empty());
+					let real = Balances::transfer(Some(from).into(), to, value).map(|_| ()).map_err(|e| e.error);
+					assert_eq!(dry_run, real);
+					dry_run
+				};
+
+				assert_ok!(check(1, 2, 50));
+				assert_eq!(check(1, 2, 51), Err(Error::<$test, _>::InsufficientBalance.into()));
+				assert_eq!(check(2, 5, 9), Err(Error::<$test, _>::ExistentialDeposit.into()));
+				Balances::set_lock(ID_1, &3, 250, WithdrawReasons::all());
+				assert_eq!(check(3, 2, 51), Err(Error::<$test, _>::LiquidityRestrictions.into()));
+				assert_ok!(check(2, 5, 10));
+			});
+		}
+
+		#[test]
+		fn force_transfer_batch_reverts_failing_moves_only() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				assert_noop!(Balances::force_transfer_batch(Some(1).into(), vec![(1, 2, 10)]), BadOrigin);
+
+				assert_ok!(Balances::force_transfer_batch(
+					RawOrigin::Root.into(),
+					vec![(1, 5, 50), (2, 6, 201), (3, 6, 30), (4, 7, 5)],
+				));
+				assert_eq!(Balances::free_balance(1), 50);
+				assert_eq!(Balances::free_balance(5), 50);
+				assert_eq!(Balances::free_balance(2), 200);
+				assert_eq!(Balances::free_balance(3), 270);
+				assert_eq!(Balances::free_balance(6), 30);
+				assert_eq!(Balances::free_balance(4), 400);
+				assert!(!System::account_exists(&7));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::ForceTransferBatchCompleted(2, vec![
+						(1, Error::<$test, _>::InsufficientBalance.into()),
+						(3, Error::<$test, _>::ExistentialDeposit.into()),
+					])),
+				);
+			});
+		}
+
+		#[test]
+		fn reserve_and_unreserve_returning_give_the_reserved_balance() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				assert_eq!(Balances::reserve_returning(&1, 30), Ok(30));
+				assert_eq!(Balances::reserve_returning(&1, 20), Ok(50));
+				assert_eq!(Balances::reserved_balance(&1), 50);
+				assert_eq!(Balances::reserve_returning(&1, 0), Ok(50));
+				assert_noop!(
+					Balances::reserve_returning(&1, 51),
+					Error::<$test, _>::InsufficientBalance,
+				);
+
+				assert_eq!(Balances::unreserve_returning(&1, 15), 35);
+				assert_eq!(Balances::reserved_balance(&1), 35);
+				// unreserving more than is reserved empties it.
+				assert_eq!(Balances::unreserve_returning(&1, 100), 0);
+				assert_eq!(Balances::reserved_balance(&1), 0);
+				assert_eq!(Balances::free_balance(&1), 100);
+				assert_eq!(Balances::unreserve_returning(&99, 10), 0);
+			});
+		}
+
+		#[test]
+		fn inflation_is_minted_to_the_inflation_account_every_block() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				use frame_support::traits::{Get, OnInitialize};
+				let pot = <$test as Config>::InflationAccount::get();
+				let issuance = Balances::total_issuance();
+				assert_noop!(
+					Balances::set_inflation_per_block(Some(1).into(), 25),
+					BadOrigin,
+				);
+				assert_ok!(Balances::set_inflation_per_block(RawOrigin::Root.into(), 25));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::InflationPerBlockChanged(0, 25)),
+				);
+
+				for n in 2..6 {
+					System::set_block_number(n);
+					Balances::on_initialize(n);
+				}
+				assert_eq!(Balances::free_balance(&pot), 4 * 25);
+				assert_eq!(Balances::total_issuance(), issuance + 4 * 25);
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::InflationMinted(25)));
+
+				// the mint stops short of overflowing the total issuance.
+				TotalIssuance::<$test>::put(u64::max_value() - 10);
+				Balances::on_initialize(6);
+				assert_eq!(Balances::total_issuance(), u64::max_value());
+				assert_eq!(Balances::free_balance(&pot), 4 * 25 + 10);
+
+				assert_ok!(Balances::set_inflation_per_block(RawOrigin::Root.into(), 0));
+				Balances::on_initialize(7);
+				assert_eq!(Balances::free_balance(&pot), 4 * 25 + 10);
+			});
+		}
+
+		#[test]
+		fn amend_lock_enforces_the_reasons_of_both_subsystems() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				let fee = WithdrawReasons::TRANSACTION_PAYMENT;
+				Balances::set_lock(ID_1, &1, 30, WithdrawReasons::TRANSFER);
+				assert_ok!(Balances::amend_lock(ID_1, &1, 20, fee));
+
+				let lock = Balances::lock_by_id(&1, ID_1).unwrap();
+				assert_eq!(lock.amount, 30);
+				assert_eq!(lock.reasons, Reasons::All);
+				assert_noop!(
+					<Balances as Currency<_>>::transfer(&1, &2, 71, AllowDeath),
+					Error::<$test, _>::LiquidityRestrictions,
+				);
+				assert_noop!(
+					Balances::withdraw(&1, 71, fee, AllowDeath),
+					Error::<$test, _>::LiquidityRestrictions,
+				);
+
+				// `set_lock` would have replaced the reasons instead, leaving transfers free.
+				Balances::set_lock(ID_1, &1, 30, fee);
+				assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 71, AllowDeath));
+			});
+		}
+
+		#[test]
+		fn transfer_valid_until_is_rejected_after_its_block() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				System::set_block_number(5);
+				// included exactly at `valid_until`.
+				assert_ok!(Balances::transfer_valid_until(Some(1).into(), 2, 10, 5));
+				assert_eq!(Balances::free_balance(2), 210);
+
+				System::set_block_number(6);
+				assert_noop!(
+					Balances::transfer_valid_until(Some(1).into(), 2, 10, 5),
+					Error::<$test, _>::TransferExpired,
+				);
+				assert_ok!(Balances::transfer_valid_until(Some(1).into(), 2, 10, 6));
+				assert_eq!(Balances::free_balance(1), 80);
+			});
+		}
+
+		#[test]
+		fn holds_are_the_named_reserves() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				use fungible::{InspectHold, MutateHold};
+				let (market, staking) = (*b"market  ", *b"staking ");
+
+				assert!(<Balances as InspectHold<_>>::can_hold(&market, &1, 30));
+				assert_ok!(<Balances as MutateHold<_>>::hold(&market, &1, 30));
+				assert_ok!(<Balances as MutateHold<_>>::hold(&staking, &1, 20));
+				assert_ok!(Balances::reserve(&1, 5));
+				assert!(!<Balances as InspectHold<_>>::can_hold(&market, &1, 46));
+				assert_noop!(
+					<Balances as MutateHold<_>>::hold(&market, &1, 46),
+					Error::<$test, _>::InsufficientBalance,
+				);
+
+				assert_eq!(<Balances as InspectHold<_>>::balance_on_hold(&market, &1), 30);
+				assert_eq!(<Balances as InspectHold<_>>::balance_on_hold(&staking, &1), 20);
+				assert_eq!(<Balances as InspectHold<_>>::total_balance_on_hold(&1), 50);
+				// the reserved balance also holds the anonymous reserve.
+				assert_eq!(Balances::reserved_balance(&1), 55);
+
+				// releasing more than is held fails unless it is a best effort.
+				assert_noop!(
+					<Balances as MutateHold<_>>::release(&market, &1, 31, false),
+					Error::<$test, _>::InsufficientBalance,
+				);
+				assert_eq!(<Balances as MutateHold<_>>::release(&market, &1, 10, false), Ok(10));
+				assert_eq!(<Balances as MutateHold<_>>::release(&staking, &1, 25, true), Ok(20));
+				assert_eq!(Balances::free_balance(&1), 75);
+				assert_eq!(Balances::reserved_balance(&1), 25);
+
+				// transferring holds for the same reason on the other side, or frees it.
+				assert_eq!(
+					<Balances as MutateHold<_>>::transfer_held(&market, &1, &2, 15, false, true),
+					Ok(15),
+				);
+				assert_eq!(<Balances as InspectHold<_>>::balance_on_hold(&market, &2), 15);
+				assert_eq!(
+					<Balances as MutateHold<_>>::transfer_held(&market, &1, &2, 10, true, false),
+					Ok(5),
+				);
+				assert_eq!(Balances::free_balance(&2), 205);
+				assert_eq!(<Balances as InspectHold<_>>::total_balance_on_hold(&1), 0);
+				assert_eq!(Balances::reserved_balance(&1), 5);
+				assert_eq!(Balances::reserved_balance(&2), 15);
+			});
+		}
+
+		#[test]
+		fn withdraw_split_checks_fee_and_tip_together() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				let reasons = WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP;
+				Balances::set_lock(ID_1, &1, 60, reasons);
+
+				// each of the fee and the tip alone could be paid, but not both.
+				assert_ok!(Balances::ensure_can_withdraw(&1, 30, reasons, 70));
+				assert_noop!(
+					Balances::withdraw_split(&1, 30, 15, reasons),
+					Error::<$test, _>::LiquidityRestrictions,
+				);
+				assert_noop!(
+					Balances::withdraw_split(&1, u64::max_value(), 1, reasons),
+					Error::<$test, _>::Overflow,
+				);
+
+				let issuance = Balances::total_issuance();
+				let (fee, tip) = Balances::withdraw_split(&1, 30, 10, reasons).unwrap();
+				assert_eq!((fee.peek(), tip.peek()), (30, 10));
+				assert_eq!(Balances::free_balance(&1), 60);
+				drop((fee, tip));
+				assert_eq!(Balances::total_issuance(), issuance - 40);
+
+				// the fee would leave the account below the existential deposit.
+				Balances::remove_lock(ID_1, &1);
+				assert_noop!(Balances::withdraw_split(&1, 45, 10, reasons), Error::<$test, _>::KeepAlive);
+			});
+		}
+
+		#[test]
+		fn force_adjust_total_issuance_repairs_drift() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				let issuance = Balances::total_issuance();
+				TotalIssuance::<$test>::put(issuance - 20);
+				assert_eq!(Balances::do_try_state(), Err("the total issuance doesn't match the balances"));
+
+				assert_noop!(
+					Balances::force_adjust_total_issuance(Some(1).into(), AdjustmentDirection::Increase, 20),
+					BadOrigin,
+				);
+				assert_noop!(
+					Balances::force_adjust_total_issuance(
+						RawOrigin::Root.into(),
+						AdjustmentDirection::Decrease,
+						issuance,
+					),
+					Error::<$test, _>::AdjustmentUnderflow,
+				);
+				assert_ok!(Balances::force_adjust_total_issuance(
+					RawOrigin::Root.into(),
+					AdjustmentDirection::Increase,
+					20,
+				));
+				assert_eq!(Balances::total_issuance(), issuance);
+				assert_ok!(Balances::do_try_state());
+				let events = System::events();
+				let n = events.len();
+				assert_eq!(events[n - 2].event, Event::pallet_balances(crate::Event::Issued(20)));
+				assert_eq!(
+					events[n - 1].event,
+					Event::pallet_balances(crate::Event::TotalIssuanceForced(issuance - 20, issuance)),
+				);
+
+				// drifting the other way is repaired by lowering it.
+				TotalIssuance::<$test>::put(issuance + 5);
+				assert_ok!(Balances::force_adjust_total_issuance(
+					RawOrigin::Root.into(),
+					AdjustmentDirection::Decrease,
+					5,
+				));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::TotalIssuanceForced(issuance + 5, issuance)),
+				);
+				assert_ok!(Balances::do_try_state());
+
+				// `set_balance` reports its change of the total issuance the same way.
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 1, 110, 0));
+				let events = System::events();
+				let n = events.len();
+				assert_eq!(events[n - 2].event, Event::pallet_balances(crate::Event::Issued(10)));
+				assert_eq!(events[n - 1].event, Event::pallet_balances(crate::Event::BalanceSet(1, 110, 0)));
+				assert_ok!(Balances::do_try_state());
+			});
+		}
+
+		#[test]
+		fn account_tags_can_be_set_and_cleared() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				assert_eq!(Balances::account_tag(&1), None);
+				assert_noop!(Balances::set_account_tag(Some(1).into(), 1, b"kyc1".to_vec()), BadOrigin);
+				assert_noop!(
+					Balances::set_account_tag(RawOrigin::Root.into(), 1, b"kyc-1".to_vec()),
+					Error::<$test, _>::TagTooLong,
+				);
+
+				assert_ok!(Balances::set_account_tag(RawOrigin::Root.into(), 1, b"kyc1".to_vec()));
+				assert_eq!(Balances::account_tag(&1), Some(b"kyc1".to_vec()));
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::AccountTagSet(1, b"kyc1".to_vec())),
+				);
+				// accounts need not exist to be tagged.
+				assert_ok!(Balances::set_account_tag(RawOrigin::Root.into(), 50, vec![]));
+				assert_eq!(Balances::account_tag(&50), Some(vec![]));
+
+				assert_ok!(Balances::set_account_tag(RawOrigin::Root.into(), 1, b"kyc2".to_vec()));
+				assert_eq!(Balances::account_tag(&1), Some(b"kyc2".to_vec()));
+
+				assert_ok!(Balances::clear_account_tag(RawOrigin::Root.into(), 1));
+				assert_eq!(Balances::account_tag(&1), None);
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::AccountTagCleared(1)));
+				assert_noop!(
+					Balances::clear_account_tag(RawOrigin::Root.into(), 1),
+					Error::<$test, _>::NotTagged,
+				);
+			});
+		}
+
+		#[test]
+		fn transfer_fraction_resolves_against_the_live_balance() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				assert_ok!(Balances::transfer_fraction(Some(1).into(), 2, Perbill::from_percent(0), true));
+				assert_eq!(Balances::free_balance(&1), 100);
+
+				// half of what is transferable while staying alive, after the balance changed,
+				// rounded down.
+				assert_ok!(Balances::transfer(Some(3).into(), 1, 11));
+				let half = Perbill::from_percent(50);
+				assert_ok!(Balances::transfer_fraction(Some(1).into(), 2, half, true));
+				assert_eq!(Balances::free_balance(&1), 61);
+				assert_eq!(Balances::free_balance(&2), 250);
+				assert_eq!(
+					last_event(),
+					Event::pallet_balances(crate::Event::FractionTransferred(1, 2, half, 50)),
+				);
+
+				// everything but the existential deposit when keeping alive, otherwise everything.
+				assert_ok!(Balances::transfer_fraction(Some(1).into(), 2, Perbill::from_percent(100), true));
+				assert_eq!(Balances::free_balance(&1), 10);
+				assert_ok!(Balances::transfer_fraction(Some(1).into(), 2, Perbill::from_percent(100), false));
+				assert_eq!(Balances::free_balance(&1), 0);
+				assert!(!System::account_exists(&1));
+				assert_eq!(Balances::free_balance(&2), 311);
+			});
+		}
+
+		#[test]
+		fn slash_pro_rata_pays_out_by_weight() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				let issuance = Balances::total_issuance();
+				let id = [1u8; 8];
+				assert_ok!(Balances::reserve_named(&id, &1, 90));
+				assert_ok!(Balances::reserve(&1, 5));
+
+				// equal weights.
+				assert_eq!(
+					Balances::slash_pro_rata(&id, &1, 60, vec![(2, 1), (3, 1), (4, 1)]),
+					vec![(2, 20), (3, 20), (4, 20)],
+				);
+				assert_eq!(Balances::reserved_balance_named(&id, &1), 30);
+				assert_eq!(Balances::free_balance(&2), 220);
+
+				// unequal weights, rounded down by default, the remainder being burned.
+				assert_eq!(
+					Balances::slash_pro_rata(&id, &1, 10, vec![(2, 1), (3, 2)]),
+					vec![(2, 3), (3, 6)],
+				);
+
+				// a slash beyond the collateral shares out only what there is, leaving the other
+				// reserves alone.
+				assert_eq!(
+					Balances::slash_pro_rata(&id, &1, 100, vec![(2, 1), (3, 1)]),
+					vec![(2, 10), (3, 10)],
+				);
+				assert_eq!(Balances::reserved_balance_named(&id, &1), 0);
+				assert_eq!(Balances::reserved_balance(&1), 5);
+				assert_eq!(Balances::total_issuance(), issuance - 1);
+
+				// nothing is slashed without any weight.
+				assert_eq!(Balances::slash_pro_rata(&id, &3, 10, vec![(2, 0)]), vec![(2, 0)]);
+			});
+		}
+
+		#[test]
+		fn migration_dedups_locks() {
+			<$ext_builder>::default().existential_deposit(1).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 1_000);
+				let _ = Balances::deposit_creating(&2, 1_000);
+				Locks::<$test>::insert(1, vec![
+					BalanceLock { id: ID_1, amount: 30, reasons: Reasons::Misc, until: None },
+					BalanceLock { id: ID_2, amount: 10, reasons: Reasons::All, until: Some(4) },
+					BalanceLock { id: ID_1, amount: 50, reasons: Reasons::Fee, until: Some(9) },
+					BalanceLock { id: ID_2, amount: 5, reasons: Reasons::All, until: Some(6) },
+				]);
+				// the duplicates were double counted.
+				let _ = Balances::mutate_account(&1, |a| { a.misc_frozen = 90; a.fee_frozen = 65; });
+				Balances::set_lock(ID_1, &2, 20, WithdrawReasons::all());
+				let untouched = Balances::locks(&2);
+
+				StorageVersion::<$test>::put(Releases::V7_0_0);
+				assert_ok!(migrations::pre_upgrade::<$test, ()>());
+				<Balances as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+				assert_ok!(migrations::post_upgrade::<$test, ()>());
+
+				assert_eq!(StorageVersion::<$test>::get(), Releases::V8_0_0);
+				assert_eq!(Balances::locks(&1), vec![
+					BalanceLock { id: ID_1, amount: 50, reasons: Reasons::All, until: None },
+					BalanceLock { id: ID_2, amount: 10, reasons: Reasons::All, until: Some(6) },
+				]);
+				assert_eq!(Balances::account(&1).misc_frozen, 50);
+				assert_eq!(Balances::account(&1).fee_frozen, 50);
+				assert_eq!(Balances::locks(&2), untouched);
+			});
+		}
+
+		#[test]
+		fn unreserving_stops_at_the_reserve_floor() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				assert_ok!(Balances::reserve(&1, 50));
+				Balances::set_reserve_floor(&1, 20);
+				assert_eq!(Balances::reserve_floor(&1), 20);
+
+				// down to the floor is fine, below it is clamped.
+				assert_eq!(Balances::unreserve(&1, 30), 0);
+				assert_eq!(Balances::reserved_balance(&1), 20);
+				assert_eq!(Balances::unreserve(&1, 5), 5);
+				assert_eq!(Balances::reserved_balance(&1), 20);
+
+				// named reserves are held to it as well.
+				let id = [1u8; 8];
+				assert_ok!(Balances::reserve_named(&id, &1, 40));
+				Balances::set_reserve_floor(&1, 50);
+				assert_eq!(Balances::unreserve_named(&id, &1, 40), 30);
+				assert_eq!(Balances::reserved_balance_named(&id, &1), 30);
+				assert_eq!(Balances::reserved_balance(&1), 50);
+
+				// slashes aren't, and removing the floor frees the rest.
+				let _ = Balances::slash_reserved(&1, 10);
+				assert_eq!(Balances::reserved_balance(&1), 40);
+				Balances::set_reserve_floor(&1, 0);
+				assert_eq!(Balances::unreserve(&1, 40), 0);
+				assert_eq!(Balances::reserved_balance(&1), 0);
+				assert!(!ReserveFloor::<$test>::contains_key(&1));
+			});
+		}
+
+		#[test]
+		fn vested_transfer_reverts_if_the_lock_cannot_be_set() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				let max = <$test as Config>::MaxLocks::get();
+				for i in 0..max {
+					assert_ok!(Balances::try_set_lock([i as u8; 8], &2, 1, WithdrawReasons::all()));
+				}
+				let schedule = VestingSchedule { locked: 50, per_block: 5, starting_block: 1, cliff: 5 };
+				assert_noop!(
+					Balances::vested_transfer(Some(1).into(), 2, schedule),
+					Error::<$test, _>::TooManyLocks,
+				);
+				assert_eq!(Balances::free_balance(2), 200);
+				assert_eq!(Balances::vesting(2), None);
+			});
+		}
+
+		#[test]
+		fn vesting_schedules_go_with_their_lock() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				let schedule = VestingSchedule { locked: 50, per_block: 5, starting_block: 1, cliff: 5 };
+				assert_ok!(Balances::vested_transfer(Some(1).into(), 2, schedule));
+				assert_ok!(Balances::force_remove_lock(RawOrigin::Root.into(), 2, VESTING_ID));
+				assert_eq!(Balances::vesting(2), None);
+				assert_noop!(Balances::claim_vested(Some(2).into()), Error::<$test, _>::NotVesting);
+
+				// so a new schedule may be set up in its place.
+				assert_ok!(Balances::vested_transfer(Some(1).into(), 2, schedule));
+				assert_eq!(Balances::vesting(2), Some(schedule));
+				Balances::remove_lock(VESTING_ID, &2);
+				assert_eq!(Balances::vesting(2), None);
+
+				// other locks leave it be.
+				assert_ok!(Balances::vested_transfer(Some(1).into(), 3, schedule));
+				Balances::set_lock(ID_1, &3, 10, WithdrawReasons::all());
+				Balances::remove_lock(ID_1, &3);
+				assert_eq!(Balances::vesting(3), Some(schedule));
+			});
+		}
+
+		#[test]
+		fn only_unreserve_is_held_to_the_reserve_floor() {
+			<$ext_builder>::default().existential_deposit(10).monied(true).build().execute_with(|| {
+				assert_ok!(Balances::reserve(&1, 50));
+				Balances::set_reserve_floor(&1, 40);
+				assert_eq!(Balances::unreserve(&1, 50), 40);
+				assert_eq!(Balances::reserved_balance(&1), 40);
+
+				// root goes below the floor.
+				assert_ok!(Balances::force_unreserve(RawOrigin::Root.into(), 1, 30));
+				assert_eq!(Balances::reserved_balance(&1), 10);
+				assert_eq!(Balances::free_balance(&1), 90);
+				assert_eq!(last_event(), Event::pallet_balances(crate::Event::Unreserved(1, 30)));
+				assert_eq!(Balances::reserve_floor(&1), 40);
+			});
+		}
 	}
 }
@@ -12,11 +12,12 @@ use sp_runtime::{
 use sp_core::H256;
 use sp_io;
 use frame_support::parameter_types;
+use frame_support::traits::{OnUnbalanced, Contains};
 use frame_support::weights::{Weight, DispatchInfo, IdentityFee};
 use pallet_transaction_payment::CurrencyAdapter;
 use crate::{
 	self as pallet_balances,
-	Module, Config, decl_tests,
+	Module, Config, NegativeImbalance, decl_tests,
 };
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -72,25 +73,103 @@ impl pallet_transaction_payment::Config for Test {
 	type FeeMultiplierUpdate = ();
 }
 
+/// Sweeps dust into `BurnAccount` when enabled, otherwise sends it to `DustTreasury` when one
+/// is set, otherwise burns it.
+pub struct DustSink;
+impl OnUnbalanced<NegativeImbalance<Test>> for DustSink {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance<Test>) {
+		if SweepToBurnAccount::get() {
+			crate::SweepDust::<Test>::on_nonzero_unbalanced(amount);
+		} else if let Some(treasury) = DustTreasury::get() {
+			let _ = Balances::resolve_into_existing(&treasury, amount);
+		}
+	}
+}
+pub struct DustRemovalWhitelist;
+impl Contains<u64> for DustRemovalWhitelist {
+	fn sorted_members() -> Vec<u64> {
+		vec![]
+	}
+}
+parameter_types! {
+	pub const MaxLocks: u32 = 50;
+	pub const MaxBatchSize: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+	pub const MaxMemoLength: u32 = 32;
+	pub const MaxTagLength: u32 = 4;
+	pub const MaxSubAccountSeedLength: u32 = 16;
+	pub static DustTreasury: Option<u64> = None;
+	pub static SweepToBurnAccount: bool = false;
+	pub const BurnAccount: u64 = 255;
+	pub const BurnInterval: u64 = 5;
+}
 impl Config for Test {
 	type Balance = u64;
-	type DustRemoval = ();
+	type DustRemoval = DustSink;
+	type DustRemovalWhitelist = DustRemovalWhitelist;
 	type Event = Event;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = frame_system::Pallet<Test>;
-	type MaxLocks = ();
+	type MaxLocks = MaxLocks;
 	type WeightInfo = ();
+	type MaxBatchSize = MaxBatchSize;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type MaxMemoLength = MaxMemoLength;
+	type MaxSubAccountSeedLength = MaxSubAccountSeedLength;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SetEDOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type BurnAccount = BurnAccount;
+	type BurnInterval = BurnInterval;
+	type TransferFilter = crate::AllowAllTransfers;
+	type DestinationFilter = crate::AllowAllTransfers;
+	type SupportedAssets = crate::NativeAssetOnly;
+	type FeeOnlyAccounts = crate::NoAccounts;
+	type FeeOnlyMinimum = ();
+	type OffchainSignature = sp_runtime::testing::TestSignature;
+	type SigningPublicKey = sp_runtime::testing::UintAuthorityId;
+	type MergeableLocks = crate::NoMergeableLocks;
+	type MinTransfer = ();
+	type IssuanceHistoryDepth = ();
+	type TipToAuthor = ();
+	type Rounding = ();
+	type LockAggregation = ();
+	type ReserveQuota = crate::NoReserveQuota;
+	type SystemAccounts = ();
+	type SponsoredFeeRefund = ();
+	type FreezeOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type EmergencyOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type MintOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type ForceLockOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type AccountCreationFee = ();
+	type CreationFeeDestination = ();
+	type CheckpointFee = ();
+	type CheckpointFeeDestination = ();
+	type FeeReserve = ();
+	type InflationOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type InflationAccount = ();
+	type MaxIssuance = crate::UncappedIssuance;
+	type OnReservedChanged = ();
+	type TagOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type MaxTagLength = MaxTagLength;
+	type LockPruneWeight = ();
+	type ClaimableReserveId = ();
 }
 
 pub struct ExtBuilder {
 	existential_deposit: u64,
 	monied: bool,
+	dust_treasury: Option<u64>,
+	sweep_dust: bool,
 }
 impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
 			existential_deposit: 1,
 			monied: false,
+			dust_treasury: None,
+			sweep_dust: false,
 		}
 	}
 }
@@ -103,8 +182,18 @@ impl ExtBuilder {
 		self.monied = monied;
 		self
 	}
+	pub fn dust_treasury(mut self, dust_treasury: Option<u64>) -> Self {
+		self.dust_treasury = dust_treasury;
+		self
+	}
+	pub fn sweep_dust(mut self, sweep_dust: bool) -> Self {
+		self.sweep_dust = sweep_dust;
+		self
+	}
 	pub fn set_associated_consts(&self) {
 		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = self.existential_deposit);
+		DUST_TREASURY.with(|v| *v.borrow_mut() = self.dust_treasury);
+		SWEEP_TO_BURN_ACCOUNT.with(|v| *v.borrow_mut() = self.sweep_dust);
 	}
 	pub fn build(self) -> sp_io::TestExternalities {
 		self.set_associated_consts();
@@ -121,6 +210,8 @@ impl ExtBuilder {
 			} else {
 				vec![]
 			},
+			reserved_balances: vec![],
+			locks: vec![],
 		}.assimilate_storage(&mut t).unwrap();
 
 		let mut ext = sp_io::TestExternalities::new(t);
@@ -130,3 +221,105 @@ impl ExtBuilder {
 }
 
 decl_tests!{ Test, ExtBuilder, EXISTENTIAL_DEPOSIT }
+
+#[test]
+fn dust_is_burned_without_treasury() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			assert_eq!(Balances::total_issuance(), 1100);
+			assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 95, AllowDeath));
+
+			assert!(System::events().iter().any(|record| record.event ==
+				Event::pallet_balances(crate::Event::DustLost(1, 5))
+			));
+			assert_eq!(Balances::total_balance(&1), 0);
+			assert_eq!(Balances::free_balance(2), 295);
+			assert_eq!(Balances::total_issuance(), 1095);
+		});
+}
+
+#[test]
+fn dust_is_sent_to_treasury() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.monied(true)
+		.dust_treasury(Some(12))
+		.build()
+		.execute_with(|| {
+			assert_eq!(Balances::total_issuance(), 1100);
+			assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 95, AllowDeath));
+
+			assert!(System::events().iter().any(|record| record.event ==
+				Event::pallet_balances(crate::Event::DustLost(1, 5))
+			));
+			assert_eq!(Balances::total_balance(&1), 0);
+			assert_eq!(Balances::free_balance(2), 295);
+			assert_eq!(Balances::free_balance(12), 105);
+			assert_eq!(Balances::total_issuance(), 1100);
+		});
+}
+
+#[test]
+fn dust_is_swept_to_burn_account_and_burned_each_interval() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.monied(true)
+		.sweep_dust(true)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&255, 10);
+			assert_eq!(Balances::total_issuance(), 1110);
+
+			// Two reapings accumulate their dust without touching the issuance.
+			assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 95, AllowDeath));
+			assert_ok!(<Balances as Currency<_>>::transfer(&3, &4, 293, AllowDeath));
+			assert_eq!(Balances::total_balance(&1), 0);
+			assert_eq!(Balances::total_balance(&3), 0);
+			assert_eq!(Balances::pending_dust(), 12);
+			assert_eq!(Balances::free_balance(255), 22);
+			assert_eq!(Balances::total_issuance(), 1110);
+
+			// Nothing is burned outside `BurnInterval` boundaries.
+			<Balances as frame_support::traits::OnInitialize<u64>>::on_initialize(4);
+			assert_eq!(Balances::pending_dust(), 12);
+			assert_eq!(Balances::total_issuance(), 1110);
+
+			<Balances as frame_support::traits::OnInitialize<u64>>::on_initialize(5);
+			assert_eq!(Balances::pending_dust(), 0);
+			assert_eq!(Balances::free_balance(255), 10);
+			assert_eq!(Balances::total_issuance(), 1098);
+			let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+			assert!(events.ends_with(&[
+				Event::pallet_balances(crate::Event::DustBurned(12)),
+				Event::pallet_balances(crate::Event::Rescinded(12)),
+			]));
+		});
+}
+
+#[test]
+fn on_initialize_only_charges_for_work_to_do() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			use frame_support::traits::OnInitialize;
+
+			// Neither expiring locks nor a `BurnInterval` boundary: just the check for expiries.
+			let idle = <Balances as OnInitialize<u64>>::on_initialize(2);
+			assert_eq!(idle, <() as WeightInfo>::on_initialize_idle());
+
+			assert_ok!(Balances::try_set_lock_until(*b"expiring", &1, 50, WithdrawReasons::all(), 3));
+			System::set_block_number(3);
+			let expiring = <Balances as OnInitialize<u64>>::on_initialize(3);
+			assert_eq!(expiring, <() as WeightInfo>::on_initialize_expiring(1));
+			assert!(expiring > idle);
+			assert!(Balances::locks(1).is_empty());
+
+			let burning = <Balances as OnInitialize<u64>>::on_initialize(5);
+			assert_eq!(burning, idle + <() as WeightInfo>::on_initialize_burn());
+		});
+}
@@ -6,17 +6,21 @@
 #![cfg(test)]
 
 use sp_runtime::{
+	Perbill, ConsensusEngineId,
 	traits::IdentityLookup,
-	testing::Header,
+	testing::{Header, TestSignature, UintAuthorityId},
 };
 use sp_core::H256;
 use sp_io;
 use frame_support::parameter_types;
-use frame_support::traits::StorageMapShim;
+use frame_support::traits::{
+	StorageMapShim, Contains, OnNewAccount, OnKilledAccount, OnUnbalanced, FindAuthor,
+};
 use frame_support::weights::{Weight, DispatchInfo, IdentityFee};
 use crate::{
 	self as pallet_balances,
-	Module, Config, decl_tests,
+	Module, Config, NegativeImbalance, RoundingMode, LockAggregation, AssetId, DustDestination,
+	RouteDust, TaxedCurrency, MAX_ACCOUNTS_ABOVE, MAX_ACCOUNTS_SCANNED, decl_tests,
 };
 use pallet_transaction_payment::CurrencyAdapter;
 
@@ -75,10 +79,131 @@ impl pallet_transaction_payment::Config for Test {
 }
 parameter_types! {
 	pub const MaxLocks: u32 = 50;
+	pub const MaxBatchSize: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+	pub const MaxMemoLength: u32 = 32;
+	pub const MaxTagLength: u32 = 4;
+	pub const MaxSubAccountSeedLength: u32 = 16;
+	pub static NewAccounts: u32 = 0;
+	pub static KilledAccounts: u32 = 0;
+	pub static TransferLimit: u64 = u64::max_value();
+	pub static MinTransfer: u64 = 0;
+	pub const IssuanceHistoryDepth: u32 = 3;
+	pub static FeeBurnRatio: Perbill = Perbill::from_percent(80);
+	pub static TransferTax: Perbill = Perbill::from_percent(0);
+	pub static SlashBurnRatio: Perbill = Perbill::from_percent(25);
+	pub static FeeRounding: RoundingMode = RoundingMode::Floor;
+	pub static LockPolicy: LockAggregation = LockAggregation::Max;
+	pub static ReserveQuota: Perbill = Perbill::from_percent(100);
+	pub static CreationFee: u64 = 0;
+	pub static CheckpointFee: u64 = 0;
+	pub static DustTarget: DustDestination = DustDestination::Burn;
+	pub static BlockAuthor: Option<u64> = None;
+	pub static FeeReserve: u64 = 0;
+	pub static DeniedDestination: Option<(u64, u64)> = None;
+	pub static MaxIssuance: u64 = u64::max_value();
+	pub static ReservedChanges: Vec<(u64, u64, u64)> = vec![];
+	pub const FeeTreasury: u64 = 7;
+	pub SystemAccounts: Vec<u64> = vec![20, 21];
+	pub const SponsoredFeeRefund: u64 = 2;
+	pub const ClaimableReserveId: [u8; 8] = *b"claimabl";
+	pub const FeeOnlyMinimum: u64 = 2;
+	pub static LockPruneWeight: Weight = 0;
+}
+/// Account `99` is never reaped.
+pub struct DustRemovalWhitelist;
+impl Contains<u64> for DustRemovalWhitelist {
+	fn sorted_members() -> Vec<u64> {
+		vec![99]
+	}
+}
+/// Blocks any transfer above `TransferLimit`.
+pub struct TransferLimitFilter;
+impl Contains<(u64, u64)> for TransferLimitFilter {
+	fn contains((_who, value): &(u64, u64)) -> bool {
+		*value <= TransferLimit::get()
+	}
+	fn sorted_members() -> Vec<(u64, u64)> {
+		vec![]
+	}
+}
+/// Blocks the signed transfers of the `DeniedDestination` pair, if any.
+pub struct DenyDestination;
+impl Contains<(u64, u64)> for DenyDestination {
+	fn contains(pair: &(u64, u64)) -> bool {
+		DeniedDestination::get() != Some(*pair)
+	}
+	fn sorted_members() -> Vec<(u64, u64)> {
+		vec![]
+	}
+}
+/// Records every change of a reserved balance in `ReservedChanges`.
+pub struct RecordReservedChanges;
+impl primitives::traits::OnReservedChanged<u64, u64> for RecordReservedChanges {
+	fn on_reserved_changed(who: &u64, old: u64, new: u64) {
+		RESERVED_CHANGES.with(|v| v.borrow_mut().push((*who, old, new)));
+	}
+}
+/// Only `ID_1` locks can be moved by `merge_accounts`.
+pub struct MergeableLocks;
+impl Contains<LockIdentifier> for MergeableLocks {
+	fn sorted_members() -> Vec<LockIdentifier> {
+		vec![ID_1]
+	}
+}
+/// The native currency and `Token(1)`, which has no backing pallet yet.
+pub struct SupportedAssets;
+impl Contains<AssetId> for SupportedAssets {
+	fn sorted_members() -> Vec<AssetId> {
+		vec![AssetId::Native, AssetId::Token(1)]
+	}
+}
+/// Account `55` only pays fees, and is held to `FeeOnlyMinimum`.
+pub struct FeeOnlyAccounts;
+impl Contains<u64> for FeeOnlyAccounts {
+	fn sorted_members() -> Vec<u64> {
+		vec![55]
+	}
+}
+/// Finds `BlockAuthor` as the author of every block.
+pub struct StaticAuthor;
+impl FindAuthor<u64> for StaticAuthor {
+	fn find_author<'a, I>(_digests: I) -> Option<u64> where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>
+	{
+		BlockAuthor::get()
+	}
+}
+/// Pays tips to account `9`, standing in for the block author.
+pub struct TipToAuthor;
+impl OnUnbalanced<NegativeImbalance<Test>> for TipToAuthor {
+	fn on_nonzero_unbalanced(tip: NegativeImbalance<Test>) {
+		Balances::resolve_creating(&9, tip);
+	}
+}
+/// Pays account creation and checkpoint fees to `FeeTreasury`.
+pub struct CreationFeeToTreasury;
+impl OnUnbalanced<NegativeImbalance<Test>> for CreationFeeToTreasury {
+	fn on_nonzero_unbalanced(fee: NegativeImbalance<Test>) {
+		Balances::resolve_creating(&FeeTreasury::get(), fee);
+	}
+}
+/// Counts the accounts created and reaped by the pallet.
+pub struct CountingHooks;
+impl OnNewAccount<u64> for CountingHooks {
+	fn on_new_account(_who: &u64) {
+		NEW_ACCOUNTS.with(|v| *v.borrow_mut() += 1);
+	}
+}
+impl OnKilledAccount<u64> for CountingHooks {
+	fn on_killed_account(_who: &u64) {
+		KILLED_ACCOUNTS.with(|v| *v.borrow_mut() += 1);
+	}
 }
 impl Config for Test {
 	type Balance = u64;
-	type DustRemoval = ();
+	type DustRemoval = RouteDust<Test, DustTarget, StaticAuthor>;
+	type DustRemovalWhitelist = DustRemovalWhitelist;
 	type Event = Event;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = StorageMapShim<
@@ -89,6 +214,49 @@ impl Config for Test {
 	>;
 	type MaxLocks = MaxLocks;
 	type WeightInfo = ();
+	type MaxBatchSize = MaxBatchSize;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type MaxMemoLength = MaxMemoLength;
+	type MaxSubAccountSeedLength = MaxSubAccountSeedLength;
+	type OnNewAccount = CountingHooks;
+	type OnKilledAccount = CountingHooks;
+	type SetEDOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type BurnAccount = ();
+	type BurnInterval = ();
+	type TransferFilter = TransferLimitFilter;
+	type DestinationFilter = DenyDestination;
+	type SupportedAssets = SupportedAssets;
+	type FeeOnlyAccounts = FeeOnlyAccounts;
+	type FeeOnlyMinimum = FeeOnlyMinimum;
+	type OffchainSignature = TestSignature;
+	type SigningPublicKey = UintAuthorityId;
+	type MergeableLocks = MergeableLocks;
+	type MinTransfer = MinTransfer;
+	type IssuanceHistoryDepth = IssuanceHistoryDepth;
+	type TipToAuthor = TipToAuthor;
+	type Rounding = FeeRounding;
+	type LockAggregation = LockPolicy;
+	type ReserveQuota = ReserveQuota;
+	type SystemAccounts = SystemAccounts;
+	type SponsoredFeeRefund = SponsoredFeeRefund;
+	type FreezeOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type EmergencyOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type MintOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type ForceLockOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type AccountCreationFee = CreationFee;
+	type CreationFeeDestination = CreationFeeToTreasury;
+	type CheckpointFee = CheckpointFee;
+	type CheckpointFeeDestination = CreationFeeToTreasury;
+	type FeeReserve = FeeReserve;
+	type InflationOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type InflationAccount = ();
+	type MaxIssuance = MaxIssuance;
+	type OnReservedChanged = RecordReservedChanges;
+	type TagOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type MaxTagLength = MaxTagLength;
+	type LockPruneWeight = LockPruneWeight;
+	type ClaimableReserveId = ClaimableReserveId;
 }
 
 pub struct ExtBuilder {
@@ -133,6 +301,8 @@ impl ExtBuilder {
 			} else {
 				vec![]
 			},
+			reserved_balances: vec![],
+			locks: vec![],
 		}.assimilate_storage(&mut t).unwrap();
 
 		let mut ext = sp_io::TestExternalities::new(t);
@@ -154,16 +324,17 @@ fn emit_events_with_no_existential_deposit_suicide_with_dust() {
 			assert_eq!(
 				events(),
 				[
+					Event::pallet_balances(crate::Event::Issued(100)),
 					Event::frame_system(system::Event::NewAccount(1)),
-					Event::pallet_balances(crate::Event::Endowed(1, 100)),
+					Event::pallet_balances(crate::Event::Endowed(1, 100, None)),
 					Event::pallet_balances(crate::Event::BalanceSet(1, 100, 0)),
 				]
 			);
 
 			let _ = Balances::slash(&1, 98);
 
-			// no events
-			assert_eq!(events(), []);
+			// only the issuance change
+			assert_eq!(events(), [Event::pallet_balances(crate::Event::Rescinded(98))]);
 
 			let _ = Balances::slash(&1, 1);
 
@@ -172,7 +343,1373 @@ fn emit_events_with_no_existential_deposit_suicide_with_dust() {
 				[
 					Event::frame_system(system::Event::KilledAccount(1)),
 					Event::pallet_balances(crate::Event::DustLost(1, 1)),
+					Event::pallet_balances(crate::Event::Rescinded(1)),
+					Event::pallet_balances(crate::Event::Rescinded(1)),
+				]
+			);
+		});
+}
+
+#[test]
+fn whitelisted_account_is_not_reaped() {
+	<ExtBuilder>::default()
+		.existential_deposit(10)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&99, 100);
+			let _ = Balances::deposit_creating(&1, 100);
+			assert_eq!(Balances::total_issuance(), 200);
+
+			// the whitelisted account keeps its sub-ED balance.
+			let _ = Balances::slash(&99, 99);
+			assert_eq!(Balances::free_balance(99), 1);
+			assert!(Account::<Test>::contains_key(99));
+
+			assert_ok!(Balances::transfer(Some(1).into(), 99, 10));
+			assert_ok!(Balances::transfer(Some(99).into(), 2, 10));
+			assert_eq!(Balances::free_balance(99), 1);
+
+			// a non-whitelisted account dropping to the same balance is reaped.
+			let _ = Balances::slash(&1, 89);
+			assert_eq!(Balances::free_balance(1), 0);
+			assert!(!Account::<Test>::contains_key(1));
+			assert!(events().ends_with(&[
+				Event::pallet_balances(crate::Event::DustLost(1, 1)),
+				Event::pallet_balances(crate::Event::Rescinded(1)),
+				Event::pallet_balances(crate::Event::Rescinded(89)),
+			]));
+			assert_eq!(Balances::total_issuance(), 11);
+		});
+}
+
+#[test]
+fn account_hooks_fire_once_per_transition() {
+	<ExtBuilder>::default()
+		.existential_deposit(10)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&1, 100);
+			assert_eq!((NewAccounts::get(), KilledAccounts::get()), (1, 0));
+
+			// mutations which keep the account alive don't fire anything.
+			let _ = Balances::slash(&1, 50);
+			let _ = Balances::deposit_creating(&1, 20);
+			assert_eq!((NewAccounts::get(), KilledAccounts::get()), (1, 0));
+
+			// neither does a failed attempt to create an account.
+			assert_noop!(
+				Balances::transfer(Some(1).into(), 2, 5),
+				Error::<Test, _>::ExistentialDeposit,
+			);
+			assert_eq!((NewAccounts::get(), KilledAccounts::get()), (1, 0));
+
+			let _ = Balances::slash(&1, 70);
+			assert_eq!((NewAccounts::get(), KilledAccounts::get()), (1, 1));
+
+			// a whitelisted account oscillating around the existential deposit is never reaped.
+			let _ = Balances::deposit_creating(&99, 100);
+			let _ = Balances::slash(&99, 95);
+			let _ = Balances::deposit_creating(&99, 20);
+			let _ = Balances::slash(&99, 20);
+			assert_eq!((NewAccounts::get(), KilledAccounts::get()), (2, 1));
+		});
+}
+
+#[test]
+fn transfer_filter_blocks_signed_transfers_but_not_force_transfer() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			TRANSFER_LIMIT.with(|v| *v.borrow_mut() = 5);
+
+			assert_ok!(Balances::transfer(Some(1).into(), 2, 5));
+			assert_noop!(Balances::transfer(Some(1).into(), 2, 6), Error::<Test, _>::TransferBlocked);
+			assert_noop!(
+				Balances::transfer_keep_alive(Some(2).into(), 1, 6),
+				Error::<Test, _>::TransferBlocked,
+			);
+			assert_noop!(Balances::transfer_all(Some(2).into(), 1, false), Error::<Test, _>::TransferBlocked);
+			assert_noop!(
+				Balances::transfer_batch(Some(1).into(), vec![(2, 5), (3, 6)]),
+				Error::<Test, _>::TransferBlocked,
+			);
+			assert_noop!(
+				Balances::transfer_with_memo(Some(1).into(), 2, 6, vec![]),
+				Error::<Test, _>::TransferBlocked,
+			);
+			assert_noop!(Balances::transfer_if_exists(Some(1).into(), 2, 6), Error::<Test, _>::TransferBlocked);
+			assert_noop!(Balances::reserved_transfer(Some(1).into(), 2, 6), Error::<Test, _>::TransferBlocked);
+
+			assert_ok!(Balances::force_transfer(RawOrigin::Root.into(), 2, 1, 20));
+			assert_eq!(Balances::free_balance(1), 25);
+			assert_eq!(Balances::free_balance(2), 5);
+		});
+}
+
+fn merge_signature(who: u64, other: u64) -> TestSignature {
+	TestSignature(other, Balances::merge_accounts_payload(&who, &other))
+}
+
+#[test]
+fn merge_accounts_moves_free_balance_and_reaps_other() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			// the signature must be by `other`, over the payload for this pair.
+			let signed_by_who = TestSignature(1, Balances::merge_accounts_payload(&1, &2));
+			assert_noop!(
+				Balances::merge_accounts(Some(1).into(), 2, signed_by_who),
+				Error::<Test, _>::InvalidMergeSignature,
+			);
+			assert_noop!(
+				Balances::merge_accounts(Some(1).into(), 2, merge_signature(3, 2)),
+				Error::<Test, _>::InvalidMergeSignature,
+			);
+
+			assert_ok!(Balances::merge_accounts(Some(1).into(), 2, merge_signature(1, 2)));
+			assert_eq!(Balances::free_balance(1), 30);
+			assert!(!Account::<Test>::contains_key(2));
+			assert_eq!(Balances::total_issuance(), 110);
+			assert_eq!(events().last(), Some(&Event::pallet_balances(crate::Event::AccountsMerged(2, 1))));
+		});
+}
+
+#[test]
+fn merge_accounts_moves_reserved_balance_and_mergeable_locks() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Balances::reserve(&2, 5));
+			Balances::set_lock(ID_1, &2, 10, WithdrawReasons::all());
+			Balances::set_lock(ID_1, &1, 4, WithdrawReasons::TRANSFER);
+
+			assert_ok!(Balances::merge_accounts(Some(1).into(), 2, merge_signature(1, 2)));
+			assert_eq!(Balances::free_balance(1), 25);
+			assert_eq!(Balances::reserved_balance(1), 5);
+			assert!(!Account::<Test>::contains_key(2));
+			assert!(Balances::locks(2).is_empty());
+			assert_eq!(
+				Balances::locks(1),
+				vec![BalanceLock { id: ID_1, amount: 14, reasons: Reasons::All, until: None }],
+			);
+			assert_eq!(Balances::total_issuance(), 110);
+		});
+}
+
+#[test]
+fn merge_accounts_is_blocked_by_obligations_it_cannot_move() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			Balances::set_lock(ID_2, &2, 10, WithdrawReasons::all());
+			assert_noop!(
+				Balances::merge_accounts(Some(1).into(), 2, merge_signature(1, 2)),
+				Error::<Test, _>::MergeNotPermitted,
+			);
+			Balances::remove_lock(ID_2, &2);
+
+			assert_ok!(Balances::reserve_named(&[1u8; 8], &2, 5));
+			assert_noop!(
+				Balances::merge_accounts(Some(1).into(), 2, merge_signature(1, 2)),
+				Error::<Test, _>::MergeNotPermitted,
+			);
+			assert_noop!(
+				Balances::merge_accounts(Some(1).into(), 1, merge_signature(1, 1)),
+				Error::<Test, _>::MergeNotPermitted,
+			);
+			assert_eq!(Balances::free_balance(2), 15);
+		});
+}
+
+#[test]
+fn merge_signature_cannot_be_replayed_once_other_is_funded_again() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			let signature = merge_signature(1, 2);
+			assert_ok!(Balances::merge_accounts(Some(1).into(), 2, signature.clone()));
+			assert_eq!(Balances::merge_nonce(2), 1);
+
+			// `other` starts over with a zero system nonce, but its merge nonce is kept.
+			assert_ok!(Balances::transfer(Some(3).into(), 2, 10));
+			assert_noop!(
+				Balances::merge_accounts(Some(1).into(), 2, signature),
+				Error::<Test, _>::InvalidMergeSignature,
+			);
+			assert_ok!(Balances::merge_accounts(Some(1).into(), 2, merge_signature(1, 2)));
+			assert_eq!(Balances::free_balance(1), 40);
+
+			// the consent is bound to the chain it was signed for.
+			let payload = Balances::merge_accounts_payload(&1, &3);
+			frame_system::BlockHash::<Test>::insert(0, H256::repeat_byte(1));
+			assert_noop!(
+				Balances::merge_accounts(Some(1).into(), 3, TestSignature(3, payload)),
+				Error::<Test, _>::InvalidMergeSignature,
+			);
+		});
+}
+
+#[test]
+fn transfers_below_min_transfer_are_rejected() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			MIN_TRANSFER.with(|v| *v.borrow_mut() = 5);
+
+			assert_noop!(Balances::transfer(Some(1).into(), 2, 4), Error::<Test, _>::TransferTooSmall);
+			assert_noop!(Balances::transfer_keep_alive(Some(1).into(), 2, 4), Error::<Test, _>::TransferTooSmall);
+			assert_ok!(Balances::transfer(Some(1).into(), 2, 5));
+			assert_ok!(Balances::transfer_keep_alive(Some(2).into(), 1, 6));
+			assert_eq!(Balances::free_balance(1), 11);
+			assert_eq!(Balances::free_balance(2), 19);
+
+			// every other signed transfer is held to it, down to each leg of a batch.
+			assert_noop!(
+				Balances::transfer_batch(Some(1).into(), vec![(2, 5), (3, 4)]),
+				Error::<Test, _>::TransferTooSmall,
+			);
+			assert_noop!(
+				Balances::transfer_with_memo(Some(1).into(), 2, 4, vec![]),
+				Error::<Test, _>::TransferTooSmall,
+			);
+			assert_noop!(Balances::transfer_if_exists(Some(1).into(), 2, 4), Error::<Test, _>::TransferTooSmall);
+			assert_noop!(Balances::reserved_transfer(Some(1).into(), 2, 4), Error::<Test, _>::TransferTooSmall);
+			assert_noop!(
+				Balances::transfer_fraction(Some(1).into(), 2, Perbill::from_percent(10), false),
+				Error::<Test, _>::TransferTooSmall,
+			);
+
+			// `force_transfer` is exempt.
+			assert_ok!(Balances::force_transfer(RawOrigin::Root.into(), 1, 2, 1));
+			assert_ok!(Balances::transfer(Some(3).into(), 4, 27));
+			assert_noop!(Balances::transfer_all(Some(3).into(), 4, false), Error::<Test, _>::TransferTooSmall);
+			assert_eq!(Balances::free_balance(3), 3);
+			assert_eq!(Balances::free_balance(4), 67);
+		});
+}
+
+#[test]
+fn issuance_history_keeps_the_last_eras() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			use primitives::traits::OnEraBoundary;
+			for era in 0..5 {
+				let _ = Balances::deposit_creating(&1, 10);
+				Balances::on_era_boundary(era);
+			}
+
+			assert_eq!(Balances::issuance_at_era(0), None);
+			assert_eq!(Balances::issuance_at_era(1), None);
+			assert_eq!(Balances::issuance_at_era(2), Some(140));
+			assert_eq!(Balances::issuance_at_era(3), Some(150));
+			assert_eq!(Balances::issuance_at_era(4), Some(160));
+			assert_eq!(IssuanceHistory::<Test>::iter().count(), 3);
+		});
+}
+
+#[test]
+fn transaction_tip_is_paid_to_the_author_or_burned() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			let issuance = Balances::total_issuance();
+			let charge_tip = |tip, tip_destination| {
+				<ChargeTransactionTip<Test> as SignedExtension>::pre_dispatch(
+					ChargeTransactionTip::from(tip, tip_destination),
+					&1,
+					CALL,
+					&info_from_weight(1),
+					1,
+				)
+			};
+
+			assert_ok!(charge_tip(3, TipDestination::Author));
+			assert_eq!(Balances::free_balance(1), 7);
+			assert_eq!(Balances::free_balance(9), 3);
+			assert_eq!(Balances::total_issuance(), issuance);
+
+			assert_ok!(charge_tip(2, TipDestination::Burn));
+			assert_eq!(Balances::free_balance(1), 5);
+			assert_eq!(Balances::free_balance(9), 3);
+			assert_eq!(Balances::total_issuance(), issuance - 2);
+
+			// the tip may not kill the sender.
+			assert!(charge_tip(5, TipDestination::Burn).is_err());
+			assert!(<ChargeTransactionTip<Test> as SignedExtension>::validate(
+				&ChargeTransactionTip::from(5, TipDestination::Burn),
+				&1,
+				CALL,
+				&info_from_weight(1),
+				1,
+			).is_err());
+			assert_eq!(Balances::free_balance(1), 5);
+
+			// nor be paid out of the reserved balance, however much of it there is.
+			assert_ok!(Balances::reserve(&2, 15));
+			assert!(<ChargeTransactionTip<Test> as SignedExtension>::validate(
+				&ChargeTransactionTip::from(8, TipDestination::Burn),
+				&2,
+				CALL,
+				&info_from_weight(1),
+				1,
+			).is_err());
+		});
+}
+
+#[test]
+fn fee_split_burns_its_ratio_and_pays_the_rest_to_the_treasury() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			type Split = crate::FeeSplit<Test, FeeBurnRatio, FeeTreasury>;
+			let charge_fee = |fee| {
+				let issuance = Balances::total_issuance();
+				let treasury = Balances::free_balance(FeeTreasury::get());
+				let imbalance = Balances::withdraw(&4, fee, WithdrawReasons::TRANSACTION_PAYMENT, AllowDeath)
+					.unwrap();
+				Split::on_unbalanced(imbalance);
+				(issuance - Balances::total_issuance(), Balances::free_balance(FeeTreasury::get()) - treasury)
+			};
+
+			// 80% burned, 20% to the treasury.
+			assert_eq!(charge_fee(10), (8, 2));
+			// the rounding remainder is burned.
+			assert_eq!(charge_fee(9), (8, 1));
+
+			FEE_BURN_RATIO.with(|v| *v.borrow_mut() = Perbill::from_percent(0));
+			assert_eq!(charge_fee(7), (0, 7));
+			FEE_BURN_RATIO.with(|v| *v.borrow_mut() = Perbill::from_percent(100));
+			assert_eq!(charge_fee(7), (7, 0));
+			FEE_BURN_RATIO.with(|v| *v.borrow_mut() = Perbill::from_percent(33));
+			assert_eq!(charge_fee(3), (1, 2));
+		});
+}
+
+#[test]
+fn circulating_supply_excludes_system_accounts() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			assert_eq!(Balances::circulating_supply(), 110);
+
+			let _ = Balances::deposit_creating(&20, 50);
+			let _ = Balances::deposit_creating(&21, 30);
+			assert_ok!(Balances::reserve(&21, 10));
+			assert_eq!(Balances::total_issuance(), 190);
+			assert_eq!(Balances::circulating_supply(), 110);
+
+			// moving funds out of a system account puts them in circulation.
+			assert_ok!(Balances::transfer(Some(20).into(), 1, 15));
+			assert_eq!(Balances::circulating_supply(), 125);
+		});
+}
+
+/// A signature over the payload as of now, i.e. for a transfer dispatched at the current nonce.
+fn sponsor_signature(sponsor: u64, who: u64, dest: u64, value: u64) -> TestSignature {
+	TestSignature(sponsor, Balances::sponsored_transfer_payload(&who, &dest, value))
+}
+
+#[test]
+fn sponsored_transfer_has_the_sponsor_pay_back_the_fee() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Balances::reserve(&3, 5));
+			// as `CheckNonce` bumps the nonce before dispatch.
+			System::inc_account_nonce(&1);
+			let signature = sponsor_signature(3, 1, 2, 4);
+
+			assert_ok!(Balances::sponsored_transfer(Some(1).into(), 3, signature.clone(), 2, 4));
+			assert_eq!(Balances::free_balance(1), 8);
+			assert_eq!(Balances::free_balance(2), 24);
+			assert_eq!(Balances::reserved_balance(3), 3);
+			assert_eq!(
+				last_event(),
+				Event::pallet_balances(crate::Event::TransferSponsored(3, 1, 2)),
+			);
+
+			// the signature doesn't sponsor another transaction.
+			System::inc_account_nonce(&1);
+			assert_noop!(
+				Balances::sponsored_transfer(Some(1).into(), 3, signature, 2, 4),
+				Error::<Test, _>::InvalidSponsorSignature,
+			);
+
+			// nor one on another chain.
+			let signature = sponsor_signature(3, 1, 2, 4);
+			frame_system::BlockHash::<Test>::insert(0, H256::repeat_byte(1));
+			assert_noop!(
+				Balances::sponsored_transfer(Some(1).into(), 3, signature, 2, 4),
+				Error::<Test, _>::InvalidSponsorSignature,
+			);
+		});
+}
+
+#[test]
+fn sponsored_transfer_needs_a_sponsor_signature_and_funds() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			// signed by someone else.
+			assert_noop!(
+				Balances::sponsored_transfer(Some(1).into(), 3, sponsor_signature(4, 1, 2, 4), 2, 4),
+				Error::<Test, _>::InvalidSponsorSignature,
+			);
+			// signed for another transfer.
+			assert_noop!(
+				Balances::sponsored_transfer(Some(1).into(), 3, sponsor_signature(3, 1, 2, 5), 2, 4),
+				Error::<Test, _>::InvalidSponsorSignature,
+			);
+
+			// a sponsor without enough anonymous reserve reverts the whole call.
+			assert_ok!(Balances::reserve(&3, 1));
+			assert_ok!(Balances::reserve_named(&[1u8; 8], &3, 5));
+			assert_noop!(
+				Balances::sponsored_transfer(Some(1).into(), 3, sponsor_signature(3, 1, 2, 4), 2, 4),
+				Error::<Test, _>::SponsorInsufficient,
+			);
+			assert_eq!(Balances::free_balance(1), 10);
+		});
+}
+
+#[test]
+fn rounding_modes_round_fractions() {
+	let half = Perbill::from_percent(50);
+	assert_eq!(RoundingMode::Floor.apply(half, 3u64), 1);
+	assert_eq!(RoundingMode::Ceil.apply(half, 3u64), 2);
+	// halves go to the even unit.
+	assert_eq!(RoundingMode::NearestBankers.apply(half, 3u64), 2);
+	assert_eq!(RoundingMode::NearestBankers.apply(half, 5u64), 2);
+	assert_eq!(RoundingMode::NearestBankers.apply(Perbill::from_percent(33), 2u64), 1);
+	assert_eq!(RoundingMode::NearestBankers.apply(Perbill::from_percent(33), 1u64), 0);
+	// exact parts aren't rounded at all.
+	for mode in &[RoundingMode::Floor, RoundingMode::Ceil, RoundingMode::NearestBankers] {
+		assert_eq!(mode.apply(half, 4u64), 2);
+		assert_eq!(mode.apply(Perbill::from_percent(100), u64::max_value()), u64::max_value());
+		assert_eq!(mode.apply_rational(u128::max_value(), 3, 3), u128::max_value());
+	}
+	assert_eq!(RoundingMode::Floor.apply_rational(10, 2, 3), 6);
+	assert_eq!(RoundingMode::Ceil.apply_rational(10, 1, 3), 4);
+	assert_eq!(RoundingMode::NearestBankers.apply_rational(10, 2, 3), 7);
+	assert_eq!(RoundingMode::NearestBankers.apply_rational(5, 1, 2), 2);
+	assert_eq!(RoundingMode::NearestBankers.apply_rational(7, 1, 2), 4);
+}
+
+#[test]
+fn fee_split_rounding_stays_within_bounds_and_conserves_units() {
+	for mode in &[RoundingMode::Floor, RoundingMode::Ceil, RoundingMode::NearestBankers] {
+		FEE_ROUNDING.with(|v| *v.borrow_mut() = *mode);
+		<ExtBuilder>::default()
+			.existential_deposit(1)
+			.monied(true)
+			.build()
+			.execute_with(|| {
+				type Split = crate::FeeSplit<Test, FeeBurnRatio, FeeTreasury>;
+				// 33% burned, 67% to the treasury.
+				FEE_BURN_RATIO.with(|v| *v.borrow_mut() = Perbill::from_percent(33));
+				let _ = Balances::deposit_creating(&5, 10_000);
+				let issuance = Balances::total_issuance();
+				let treasury = Balances::free_balance(FeeTreasury::get());
+
+				let splits = 300;
+				let fees: u64 = (0..splits).map(|k| {
+					let fee = k % 10 + 1;
+					let imbalance = Balances::withdraw(&5, fee, WithdrawReasons::TRANSACTION_PAYMENT, AllowDeath)
+						.unwrap();
+					Split::on_unbalanced(imbalance);
+					fee
+				}).sum();
+
+				let burned = issuance - Balances::total_issuance();
+				let to_treasury = Balances::free_balance(FeeTreasury::get()) - treasury;
+				assert_eq!(burned + to_treasury, fees);
+
+				// the error against the exact treasury part, in hundredths of a unit.
+				let error = to_treasury as i128 * 100 - fees as i128 * 67;
+				let splits = splits as i128;
+				match mode {
+					RoundingMode::Floor => assert!(error <= 0 && -error < splits * 100),
+					RoundingMode::Ceil => assert!(error >= 0 && error < splits * 100),
+					RoundingMode::NearestBankers => assert!(error.abs() <= splits * 50),
+				}
+			});
+	}
+}
+
+#[test]
+fn transfer_asset_routes_native_and_rejects_other_assets() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Balances::transfer_asset(Some(1).into(), AssetId::Native, 2, 5));
+			assert_eq!(Balances::free_balance(1), 5);
+			assert_eq!(Balances::free_balance(2), 25);
+			assert_eq!(
+				last_event(),
+				Event::pallet_balances(crate::Event::AssetTransfer(AssetId::Native, 1, 2, 5)),
+			);
+
+			// supported, but no pallet backs it yet.
+			assert_noop!(
+				Balances::transfer_asset(Some(1).into(), AssetId::Token(1), 2, 5),
+				Error::<Test, _>::UnsupportedAsset,
+			);
+			assert_noop!(
+				Balances::transfer_asset(Some(1).into(), AssetId::Token(2), 2, 5),
+				Error::<Test, _>::UnsupportedAsset,
+			);
+		});
+}
+
+#[test]
+fn reap_and_create_transfer_events_come_in_order() {
+	<ExtBuilder>::default()
+		.existential_deposit(10)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&1, 100);
+			let _ = events();
+
+			assert_ok!(Balances::force_transfer(RawOrigin::Root.into(), 1, 2, 95));
+			assert_eq!(
+				events(),
+				[
+					Event::frame_system(system::Event::NewAccount(2)),
+					Event::pallet_balances(crate::Event::Endowed(2, 95, Some(1))),
+					Event::pallet_balances(crate::Event::Transfer(1, 2, 95)),
+					Event::frame_system(system::Event::KilledAccount(1)),
+					Event::pallet_balances(crate::Event::DustLost(1, 5)),
+					Event::pallet_balances(crate::Event::Rescinded(5)),
+				]
+			);
+		});
+}
+
+#[test]
+fn failed_reap_and_create_transfer_leaves_no_events() {
+	<ExtBuilder>::default()
+		.existential_deposit(10)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&1, 100);
+			Balances::set_lock(ID_1, &1, 50, WithdrawReasons::all());
+			let _ = events();
+
+			assert_noop!(
+				Balances::force_transfer(RawOrigin::Root.into(), 1, 2, 95),
+				Error::<Test, _>::LiquidityRestrictions,
+			);
+			assert_eq!(events(), []);
+			assert!(!System::account_exists(&2));
+		});
+}
+
+#[test]
+fn fee_only_account_survives_below_existential_deposit() {
+	<ExtBuilder>::default()
+		.existential_deposit(10)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&1, 100);
+			assert_eq!(Balances::existential_deposit_for(&55), 2);
+			assert_eq!(Balances::existential_deposit_for(&1), 10);
+
+			// other accounts still need the full existential deposit.
+			assert_noop!(
+				Balances::transfer(Some(1).into(), 2, 5),
+				Error::<Test, _>::ExistentialDeposit,
+			);
+			assert_ok!(Balances::transfer(Some(1).into(), 55, 5));
+			assert_eq!(Balances::free_balance(&55), 5);
+
+			assert_ok!(Balances::transfer_keep_alive(Some(55).into(), 1, 3));
+			assert_eq!(Balances::free_balance(&55), 2);
+			assert!(System::account_exists(&55));
+			assert_noop!(
+				Balances::transfer_keep_alive(Some(55).into(), 1, 1),
+				Error::<Test, _>::KeepAlive,
+			);
+		});
+}
+
+#[test]
+fn fee_only_account_is_reaped_below_reduced_minimum() {
+	<ExtBuilder>::default()
+		.existential_deposit(10)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&1, 100);
+			assert_ok!(Balances::transfer(Some(1).into(), 55, 5));
+			let _ = events();
+
+			assert_ok!(Balances::transfer(Some(55).into(), 1, 4));
+			assert!(!System::account_exists(&55));
+			assert_eq!(Balances::free_balance(&55), 0);
+			assert_eq!(
+				events(),
+				[
+					Event::pallet_balances(crate::Event::Transfer(55, 1, 4)),
+					Event::frame_system(system::Event::KilledAccount(55)),
+					Event::pallet_balances(crate::Event::DustLost(55, 1)),
+					Event::pallet_balances(crate::Event::Rescinded(1)),
 				]
 			);
 		});
 }
+
+#[test]
+fn overlapping_misc_locks_freeze_the_largest_by_default() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&1, 100);
+			Balances::set_lock(ID_1, &1, 30, WithdrawReasons::TRANSFER);
+			Balances::set_lock(ID_2, &1, 20, WithdrawReasons::TRANSFER);
+			assert_eq!(Balances::frozen_balance_breakdown(&1), (0, 30));
+			assert_eq!(Balances::transferable_balance(&1), 70);
+			assert_ok!(Balances::transfer(Some(1).into(), 2, 70));
+		});
+}
+
+#[test]
+fn overlapping_misc_locks_add_up_when_summed() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			LOCK_POLICY.with(|v| *v.borrow_mut() = LockAggregation::Sum);
+			let _ = Balances::deposit_creating(&1, 100);
+			Balances::set_lock(ID_1, &1, 30, WithdrawReasons::TRANSFER);
+			Balances::set_lock(ID_2, &1, 20, WithdrawReasons::TRANSFER);
+			assert_eq!(Balances::frozen_balance_breakdown(&1), (0, 50));
+			assert_eq!(Balances::transferable_balance(&1), 50);
+			assert_noop!(
+				Balances::transfer(Some(1).into(), 2, 51),
+				Error::<Test, _>::LiquidityRestrictions,
+			);
+			assert_ok!(Balances::transfer(Some(1).into(), 2, 50));
+
+			// a fee lock adds to the fee part only.
+			Balances::set_lock(*b"fee     ", &1, 10, WithdrawReasons::TRANSACTION_PAYMENT);
+			assert_eq!(Balances::frozen_balance_breakdown(&1), (10, 50));
+		});
+}
+
+#[test]
+fn reserves_are_capped_by_reserve_quota() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			RESERVE_QUOTA.with(|v| *v.borrow_mut() = Perbill::from_percent(50));
+			let _ = Balances::deposit_creating(&1, 100);
+
+			assert_ok!(Balances::reserve(&1, 30));
+			assert_ok!(Balances::reserve_named(&[1u8; 8], &1, 20));
+			assert_eq!(Balances::reserved_balance(&1), 50);
+
+			// one unit over the quota.
+			assert!(!Balances::can_reserve(&1, 1));
+			assert_noop!(Balances::reserve(&1, 1), Error::<Test, _>::ReserveQuotaExceeded);
+			assert_noop!(
+				Balances::reserve_named(&[1u8; 8], &1, 1),
+				Error::<Test, _>::ReserveQuotaExceeded,
+			);
+			assert_eq!(Balances::reserved_balance_named(&[1u8; 8], &1), 20);
+		});
+}
+
+#[test]
+fn force_operations_ignore_reserve_quota() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			RESERVE_QUOTA.with(|v| *v.borrow_mut() = Perbill::from_percent(50));
+			let _ = Balances::deposit_creating(&1, 100);
+			assert_ok!(Balances::reserve(&1, 50));
+
+			assert_noop!(Balances::force_reserve(Some(1).into(), 1, 10), BadOrigin);
+			assert_ok!(Balances::force_reserve(RawOrigin::Root.into(), 1, 10));
+			assert_eq!(Balances::reserved_balance(&1), 60);
+			assert_eq!(last_event(), Event::pallet_balances(crate::Event::Reserved(1, 10)));
+
+			assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 2, 10, 90));
+			assert_eq!(Balances::reserved_balance(&2), 90);
+		});
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "balance arithmetic overflowed")]
+fn unreserving_into_an_overflowing_free_balance_panics_in_debug_builds() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&1, 100);
+			assert_ok!(Balances::reserve(&1, 10));
+			// corrupt the account, which release builds would silently saturate.
+			Account::<Test>::mutate(&1, |account| account.free = u64::max_value() - 5);
+			let _ = Balances::unreserve(&1, 10);
+		});
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "balance arithmetic overflowed")]
+fn issuance_underflow_panics_in_debug_builds() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&1, 100);
+			// the total issuance no longer covers the balance of `1`.
+			TotalIssuance::<Test>::put(10);
+			let _ = Balances::set_balance_batch(RawOrigin::Root.into(), vec![(1, 0, 0)]);
+		});
+}
+
+#[test]
+fn creating_transfers_pay_the_account_creation_fee() {
+	<ExtBuilder>::default()
+		.existential_deposit(10)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			CREATION_FEE.with(|v| *v.borrow_mut() = 5);
+			let _ = Balances::deposit_creating(&FeeTreasury::get(), 10);
+
+			// existing recipients incur no fee.
+			assert_ok!(Balances::transfer(Some(1).into(), 2, 50));
+			assert_eq!(Balances::free_balance(1), 50);
+			assert_eq!(Balances::free_balance(2), 250);
+			assert_eq!(Balances::free_balance(FeeTreasury::get()), 10);
+
+			assert_ok!(Balances::transfer(Some(1).into(), 5, 30));
+			assert_eq!(Balances::free_balance(1), 15);
+			assert_eq!(Balances::free_balance(5), 30);
+			assert_eq!(Balances::free_balance(FeeTreasury::get()), 15);
+
+			assert_noop!(
+				Balances::transfer(Some(2).into(), 8, 250),
+				Error::<Test, _>::CannotPayCreationFee,
+			);
+
+			assert_ok!(Balances::force_transfer(RawOrigin::Root.into(), 3, 6, 20));
+			assert_eq!(Balances::free_balance(3), 275);
+			assert_eq!(Balances::free_balance(6), 20);
+			assert_eq!(Balances::free_balance(FeeTreasury::get()), 20);
+		});
+}
+
+#[test]
+fn root_moves_funds_off_a_frozen_account_to_a_new_one() {
+	<ExtBuilder>::default()
+		.existential_deposit(10)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			CREATION_FEE.with(|v| *v.borrow_mut() = 5);
+			let _ = Balances::deposit_creating(&FeeTreasury::get(), 10);
+			assert_ok!(Balances::freeze(RawOrigin::Root.into(), 3));
+			assert_noop!(Balances::transfer(Some(3).into(), 6, 20), Error::<Test, _>::AccountFrozen);
+
+			// the creation fee is charged all the same.
+			assert_ok!(Balances::force_transfer(RawOrigin::Root.into(), 3, 6, 20));
+			assert_eq!(Balances::free_balance(3), 275);
+			assert_eq!(Balances::free_balance(6), 20);
+			assert_eq!(Balances::free_balance(FeeTreasury::get()), 15);
+
+			assert_ok!(Balances::force_transfer_batch(RawOrigin::Root.into(), vec![(3, 8, 20)]));
+			assert_eq!(
+				events().last(),
+				Some(&Event::pallet_balances(crate::Event::ForceTransferBatchCompleted(1, vec![]))),
+			);
+			assert_eq!(Balances::free_balance(3), 250);
+			assert_eq!(Balances::free_balance(8), 20);
+			assert_eq!(Balances::free_balance(FeeTreasury::get()), 20);
+		});
+}
+
+#[test]
+fn every_signed_call_creating_an_account_pays_the_creation_fee() {
+	<ExtBuilder>::default()
+		.existential_deposit(10)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			CREATION_FEE.with(|v| *v.borrow_mut() = 5);
+			let _ = Balances::deposit_creating(&FeeTreasury::get(), 10);
+			let treasury = || Balances::free_balance(FeeTreasury::get());
+
+			assert_ok!(Balances::transfer_keep_alive(Some(4).into(), 10, 20));
+			assert_eq!(Balances::free_balance(4), 375);
+			assert_eq!(treasury(), 15);
+			assert_ok!(Balances::transfer_asset(Some(4).into(), AssetId::Native, 11, 20));
+			assert_eq!(Balances::free_balance(4), 350);
+			assert_eq!(treasury(), 20);
+			assert_ok!(Balances::transfer_with_memo(Some(4).into(), 12, 20, b"hi".to_vec()));
+			assert_ok!(Balances::reserved_transfer(Some(4).into(), 13, 20));
+			assert_ok!(Balances::transfer_claimable(Some(4).into(), 14, 20));
+			assert_ok!(Balances::transfer_batch(Some(4).into(), vec![(15, 20), (10, 20)]));
+			assert_eq!(Balances::free_balance(4), 350 - 4 * 25 - 20);
+			assert_eq!(treasury(), 40);
+
+			// the fee is set aside from what `transfer_all` moves.
+			assert_ok!(Balances::transfer_all(Some(2).into(), 16, false));
+			assert_eq!(Balances::free_balance(16), 195);
+			assert!(!System::account_exists(&2));
+			assert_eq!(treasury(), 45);
+
+			// a transfer which fails charges no fee either.
+			assert_noop!(
+				Balances::transfer_keep_alive(Some(1).into(), 17, 90),
+				Error::<Test, _>::KeepAlive,
+			);
+		});
+}
+
+#[test]
+fn locks_leave_the_fee_reserve_free() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			FEE_RESERVE.with(|v| *v.borrow_mut() = 5);
+			let _ = Balances::deposit_creating(&1, 100);
+			let id = *b"feesafe ";
+
+			// up to the safe limit.
+			assert_ok!(Balances::try_set_lock(id, &1, 95, WithdrawReasons::all()));
+			assert_noop!(
+				Balances::try_set_lock(id, &1, 96, WithdrawReasons::all()),
+				Error::<Test, _>::WouldLockOutFees,
+			);
+			assert_noop!(
+				Balances::try_extend_lock(id, &1, 100, WithdrawReasons::all()),
+				Error::<Test, _>::WouldLockOutFees,
+			);
+			// the trait drops the change, keeping the lock as it was.
+			Balances::extend_lock(id, &1, 100, WithdrawReasons::all());
+			assert_eq!(Balances::lock_by_id(&1, id).map(|l| l.amount), Some(95));
+			assert_ok!(Balances::withdraw(&1, 5, WithdrawReasons::TRANSACTION_PAYMENT, AllowDeath));
+
+			// locks which don't freeze fees as well as other withdrawals may take everything.
+			let _ = Balances::deposit_creating(&2, 100);
+			assert_ok!(Balances::try_set_lock(id, &2, 100, WithdrawReasons::TRANSFER));
+			assert_ok!(Balances::try_set_lock(*b"feeonly ", &2, 100, WithdrawReasons::TRANSACTION_PAYMENT));
+		});
+}
+
+#[test]
+fn destination_filter_rejects_denied_pairs_only() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			DENIED_DESTINATION.with(|v| *v.borrow_mut() = Some((1, 3)));
+
+			assert_noop!(
+				Balances::transfer(Some(1).into(), 3, 10),
+				Error::<Test, _>::DestinationNotAllowed,
+			);
+			assert_noop!(
+				Balances::transfer_keep_alive(Some(1).into(), 3, 10),
+				Error::<Test, _>::DestinationNotAllowed,
+			);
+			assert_noop!(
+				Balances::transfer_batch(Some(1).into(), vec![(2, 10), (3, 10)]),
+				Error::<Test, _>::DestinationNotAllowed,
+			);
+
+			// other pairs, the same recipient included, are unaffected.
+			assert_ok!(Balances::transfer(Some(1).into(), 2, 10));
+			assert_ok!(Balances::transfer(Some(2).into(), 3, 10));
+			assert_ok!(Balances::transfer(Some(3).into(), 1, 10));
+
+			// root bypasses the filter.
+			assert_ok!(Balances::force_transfer(RawOrigin::Root.into(), 1, 3, 10));
+			assert_eq!(Balances::free_balance(&3), 310);
+		});
+}
+
+#[test]
+fn minting_stops_at_max_issuance() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			use frame_support::traits::OnInitialize;
+			MAX_ISSUANCE.with(|v| *v.borrow_mut() = 1000);
+
+			// right up to the cap.
+			assert_ok!(Balances::mint(RawOrigin::Root.into(), 1, 999));
+			assert_eq!(Balances::remaining_issuance_capacity(), 1);
+			assert_noop!(Balances::mint(RawOrigin::Root.into(), 2, 2), Error::<Test, _>::IssuanceOverflow);
+			assert_eq!(Balances::issue(2).peek(), 0);
+
+			// crediting an imbalance which already exists creates nothing, so the cap doesn't apply.
+			let fee = Balances::withdraw(&1, 10, WithdrawReasons::TRANSACTION_PAYMENT, AllowDeath).unwrap();
+			let (to_2, refund) = fee.split(6);
+			Balances::resolve_creating(&2, to_2);
+			assert_ok!(Balances::resolve_into_existing(&1, refund));
+			assert_eq!(Balances::free_balance(&1), 993);
+			assert_eq!(Balances::free_balance(&2), 6);
+			assert_eq!(Balances::total_issuance(), 999);
+
+			// the inflation one unit over is clamped.
+			assert_ok!(Balances::set_inflation_per_block(RawOrigin::Root.into(), 2));
+			Balances::on_initialize(2);
+			assert!(System::events().iter().any(|record| {
+				record.event == Event::pallet_balances(crate::Event::IssuanceCapReached(1))
+			}));
+			assert_eq!(Balances::free_balance(&0), 1);
+			assert_eq!(Balances::total_issuance(), 1000);
+			Balances::on_initialize(3);
+			assert_eq!(Balances::total_issuance(), 1000);
+
+			// burning frees headroom to mint again.
+			assert_ok!(Balances::burn(Some(1).into(), 100, false));
+			assert_eq!(Balances::remaining_issuance_capacity(), 100);
+			assert_ok!(Balances::set_inflation_per_block(RawOrigin::Root.into(), 0));
+			assert_ok!(Balances::mint(RawOrigin::Root.into(), 2, 100));
+			assert_eq!(Balances::total_issuance(), 1000);
+
+			// a deposit beyond the cap is refused before anything is credited.
+			assert_eq!(Balances::deposit_creating(&3, 2).peek(), 0);
+			assert_eq!(Balances::free_balance(&3), 0);
+			assert_noop!(Balances::deposit_into_existing(&2, 2), Error::<Test, _>::IssuanceOverflow);
+			assert_noop!(
+				<Balances as fungible::Mutate<_>>::mint_into(&2, 2),
+				Error::<Test, _>::IssuanceOverflow,
+			);
+			assert_eq!(Balances::total_issuance(), 1000);
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::pallet_balances(crate::Event::IssuanceCapReached(2)),
+			);
+		});
+}
+
+#[test]
+fn deposits_at_max_issuance_keep_the_issuance_matching_the_balances() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			MAX_ISSUANCE.with(|v| *v.borrow_mut() = Balances::total_issuance() + 5);
+
+			drop(Balances::deposit_creating(&5, 6));
+			assert_ok!(Balances::do_try_state());
+			drop(Balances::deposit_creating(&5, 5));
+			assert_eq!(Balances::free_balance(&5), 5);
+			assert_eq!(Balances::remaining_issuance_capacity(), 0);
+			drop(Balances::deposit_creating(&1, 1));
+			assert!(Balances::deposit_into_existing(&1, 1).is_err());
+			assert_eq!(Balances::free_balance(&1), 10);
+			assert_ok!(Balances::do_try_state());
+		});
+}
+
+#[test]
+fn accounts_above_pages_through_the_accounts_in_key_order() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			for who in 1..=10 {
+				let _ = Balances::deposit_creating(&who, who * 10);
+			}
+			// reserved balance counts too.
+			assert_ok!(Balances::reserve(&5, 20));
+			let expected: Vec<(u64, u64)> = frame_system::Account::<Test>::iter()
+				.map(|(who, _)| (who, who * 10))
+				.filter(|(_, total)| *total > 40)
+				.collect();
+			assert_eq!(expected.len(), 6);
+
+			let mut pages = Vec::new();
+			let mut start_key = None;
+			loop {
+				let (page, next) = Balances::accounts_above(40, start_key, 4);
+				assert!(page.len() <= 4);
+				pages.extend(page);
+				start_key = next;
+				if start_key.is_none() { break }
+			}
+			assert_eq!(pages, expected);
+
+			// the continuation key survives the account being reaped.
+			let (first, next) = Balances::accounts_above(40, None, 1);
+			let _ = Balances::slash(&next.unwrap(), u64::max_value());
+			assert_eq!(Balances::total_balance(&next.unwrap()), 0);
+			let (second, _) = Balances::accounts_above(40, next, 1);
+			assert_eq!(first.len(), 1);
+			assert_eq!(second[0], expected[1]);
+
+			assert_eq!(Balances::accounts_above(40, None, 0), (Vec::new(), None));
+		});
+}
+
+#[test]
+fn accounts_above_stops_after_looking_at_max_accounts_scanned() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			let accounts = MAX_ACCOUNTS_SCANNED as u64 + 10;
+			for who in 1..=accounts {
+				let _ = Balances::deposit_creating(&who, if who % 1000 == 0 { 100 } else { 1 });
+			}
+			let expected: Vec<(u64, u64)> = frame_system::Account::<Test>::iter()
+				.filter(|(who, _)| who % 1000 == 0)
+				.map(|(who, _)| (who, 100))
+				.collect();
+
+			// the first call gives up before it has looked at every account, with a page to spare.
+			let (first, next) = Balances::accounts_above(50, None, MAX_ACCOUNTS_ABOVE);
+			assert!(first.len() <= expected.len());
+			assert!(next.is_some());
+
+			let mut pages = first;
+			let mut start_key = next;
+			while start_key.is_some() {
+				let (page, next) = Balances::accounts_above(50, start_key, MAX_ACCOUNTS_ABOVE);
+				pages.extend(page);
+				start_key = next;
+			}
+			assert_eq!(pages, expected);
+		});
+}
+
+#[test]
+fn slash_split_burns_its_ratio_and_pays_the_rest_to_the_treasury() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			type Split = crate::SlashSplit<Test, SlashBurnRatio, FeeTreasury>;
+			let slash = |amount| {
+				let issuance = Balances::total_issuance();
+				let treasury = Balances::free_balance(FeeTreasury::get());
+				let (imbalance, _) = Balances::slash(&4, amount);
+				Split::on_unbalanced(imbalance);
+				(issuance - Balances::total_issuance(), Balances::free_balance(FeeTreasury::get()) - treasury)
+			};
+
+			// 25% burned, 75% to the treasury.
+			assert_eq!(slash(100), (25, 75));
+			assert_eq!(slash(40), (10, 30));
+			// the rounding remainder is burned, the same way every time.
+			assert_eq!(slash(10), (3, 7));
+			assert_eq!(slash(10), (3, 7));
+			assert_eq!(slash(1), (1, 0));
+
+			SLASH_BURN_RATIO.with(|v| *v.borrow_mut() = Perbill::from_percent(100));
+			assert_eq!(slash(20), (20, 0));
+		});
+}
+
+#[test]
+fn reserved_balance_changes_are_reported() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Balances::reserve(&1, 50));
+			assert_eq!(Balances::unreserve(&1, 20), 0);
+			let _ = Balances::slash_reserved(&1, 10);
+			assert_ok!(Balances::repatriate_reserved(&1, &2, 5, Status::Reserved));
+			assert_ok!(Balances::repatriate_reserved(&1, &2, 5, Status::Free));
+			// mutations leaving the reserved balance as it is aren't reported.
+			assert_ok!(Balances::transfer(Some(1).into(), 3, 10));
+
+			assert_eq!(ReservedChanges::get(), vec![
+				(1, 0, 50),
+				(1, 50, 30),
+				(1, 30, 20),
+				(1, 20, 15),
+				(2, 0, 5),
+				(1, 15, 10),
+			]);
+		});
+}
+
+#[test]
+fn balance_checkpoint_reports_current_balances_and_charges_fee() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			CHECKPOINT_FEE.with(|v| *v.borrow_mut() = 3);
+			assert_ok!(Balances::reserve(&1, 20));
+			Balances::set_lock(ID_1, &1, 30, WithdrawReasons::all());
+			System::set_block_number(5);
+
+			assert_ok!(Balances::emit_balance_checkpoint(Some(2).into(), 1));
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::pallet_balances(crate::Event::BalanceCheckpoint(1, 80, 20, 30, 5)),
+			);
+			assert_eq!(Balances::free_balance(&2), 197);
+			assert_eq!(Balances::free_balance(FeeTreasury::get()), 3);
+
+			// a checkpoint of the caller itself reports the balance left after the fee.
+			assert_ok!(Balances::emit_balance_checkpoint(Some(2).into(), 2));
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::pallet_balances(crate::Event::BalanceCheckpoint(2, 194, 0, 0, 5)),
+			);
+
+			// the fee may not reap the caller.
+			assert_noop!(
+				Balances::emit_balance_checkpoint(Some(FeeTreasury::get()).into(), 1),
+				Error::<Test, _>::KeepAlive,
+			);
+		});
+}
+
+#[test]
+fn dust_can_reward_the_block_author() {
+	<ExtBuilder>::default()
+		.existential_deposit(10)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			DUST_TARGET.with(|v| *v.borrow_mut() = DustDestination::Author);
+			BLOCK_AUTHOR.with(|v| *v.borrow_mut() = Some(2));
+			let issuance = Balances::total_issuance();
+
+			// reaping 1 leaves 5 of dust, which the author gets.
+			assert_ok!(Balances::transfer(Some(1).into(), 3, 95));
+			assert!(!System::account_exists(&1));
+			assert_eq!(Balances::free_balance(&2), 205);
+			assert_eq!(Balances::total_issuance(), issuance);
+
+			// without an author, the dust is burned.
+			BLOCK_AUTHOR.with(|v| *v.borrow_mut() = None);
+			assert_ok!(Balances::transfer(Some(12).into(), 3, 95));
+			assert_eq!(Balances::free_balance(&2), 205);
+			assert_eq!(Balances::total_issuance(), issuance - 5);
+		});
+}
+
+#[test]
+fn taxed_currency_charges_the_sender_on_top() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			type Taxed = TaxedCurrency<Test, TransferTax, FeeTreasury>;
+			let transfer = |from: u64, to: u64, value: u64| Taxed::transfer(&from, &to, value, AllowDeath);
+
+			// no tax by default.
+			assert_ok!(transfer(1, 2, 50));
+			assert_eq!(Balances::free_balance(&1), 50);
+			assert_eq!(Balances::free_balance(FeeTreasury::get()), 0);
+
+			TRANSFER_TAX.with(|v| *v.borrow_mut() = Perbill::from_percent(10));
+			assert_ok!(transfer(2, 3, 50));
+			assert_eq!(Balances::free_balance(&2), 195);
+			assert_eq!(Balances::free_balance(&3), 350);
+			assert_eq!(Balances::free_balance(FeeTreasury::get()), 5);
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::pallet_balances(crate::Event::TransferTaxed(2, 50, 5)),
+			);
+
+			TRANSFER_TAX.with(|v| *v.borrow_mut() = Perbill::from_percent(2));
+			assert_ok!(transfer(3, 4, 50));
+			assert_eq!(Balances::free_balance(&3), 299);
+			assert_eq!(Balances::free_balance(FeeTreasury::get()), 6);
+
+			// a sender which can't pay the tax on top pays neither.
+			assert_noop!(transfer(1, 4, 50), Error::<Test, _>::InsufficientBalance);
+
+			// moves to the system accounts are internal.
+			assert_ok!(transfer(4, 20, 100));
+			assert_eq!(Balances::free_balance(&4), 350);
+			assert_eq!(Balances::free_balance(FeeTreasury::get()), 6);
+		});
+}
+
+#[test]
+fn lock_pruning_reserves_its_budget_in_on_initialize() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			use frame_support::traits::{OnInitialize, OnFinalize};
+			assert_ok!(Balances::try_set_lock_until(ID_1, &1, 5, WithdrawReasons::all(), 3));
+			System::set_block_number(4);
+			let idle = Balances::on_initialize(4);
+
+			// a fixed budget is reserved up front, however full the block ends up.
+			let db_weight = <Test as frame_system::Config>::DbWeight::get();
+			let per_account = db_weight.reads(1) + <() as WeightInfo>::prune_expired_locks(MaxLocks::get());
+			let budget = db_weight.reads_writes(1, 1) + 5 * per_account;
+			LOCK_PRUNE_WEIGHT.with(|v| *v.borrow_mut() = budget);
+			assert_eq!(Balances::on_initialize(4), idle + budget);
+
+			Balances::on_finalize(4);
+			assert!(Balances::locks(&1).is_empty());
+
+			// without a budget nothing is pruned.
+			LOCK_PRUNE_WEIGHT.with(|v| *v.borrow_mut() = 0);
+			assert_ok!(Balances::try_set_lock_until(ID_1, &2, 5, WithdrawReasons::all(), 5));
+			System::set_block_number(6);
+			Balances::on_finalize(6);
+			assert_eq!(Balances::locks(&2).len(), 1);
+		});
+}
+
+#[test]
+fn compact_account_store_keeps_the_free_balance_alone_when_nothing_is_reserved_or_frozen() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			use codec::Encode;
+			use frame_support::traits::StoredMap;
+			use crate::{AccountData, CompactAccountStore, FreeBalance};
+			type Store = CompactAccountStore<Test, system::Provider<Test>>;
+
+			let compact = AccountData { free: 50, ..Default::default() };
+			assert_ok!(Store::insert(&9, compact.clone()));
+			assert_eq!(Store::get(&9), compact);
+			assert_eq!(System::providers(&9), 1);
+			assert!(!Account::<Test>::contains_key(9));
+			let key = FreeBalance::<Test>::hashed_key_for(&9);
+			assert_eq!(sp_io::storage::get(&key).map(|v| v.len()), Some(50u64.encode().len()));
+
+			// reserving moves the account to the full form, and unreserving back.
+			let full = AccountData { free: 40, reserved: 10, ..Default::default() };
+			assert_ok!(Store::insert(&9, full.clone()));
+			assert_eq!(Store::get(&9), full);
+			assert!(!FreeBalance::<Test>::contains_key(9));
+			let key = Account::<Test>::hashed_key_for(&9);
+			assert_eq!(sp_io::storage::get(&key).map(|v| v.len()), Some(full.encode().len()));
+			assert_ok!(Store::insert(&9, compact.clone()));
+			assert_eq!(Store::get(&9), compact);
+			assert!(!Account::<Test>::contains_key(9));
+
+			// a frozen balance needs the full form too.
+			let frozen = AccountData { free: 50, fee_frozen: 5, ..Default::default() };
+			assert_ok!(Store::insert(&9, frozen.clone()));
+			assert_eq!(Store::get(&9), frozen);
+
+			assert_ok!(Store::remove(&9));
+			assert_eq!(Store::get(&9), AccountData::default());
+			assert!(!Account::<Test>::contains_key(9) && !FreeBalance::<Test>::contains_key(9));
+			assert_eq!(System::providers(&9), 0);
+		});
+}
+
+#[test]
+fn migration_moves_free_only_accounts_to_the_compact_store() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build()
+		.execute_with(|| {
+			use frame_support::traits::StoredMap;
+			use crate::{CompactAccountStore, FreeBalance};
+			type Store = CompactAccountStore<Test, system::Provider<Test>>;
+			assert_ok!(Balances::reserve(&1, 4));
+			let before = (1..=4).map(|who| Balances::account(&who)).collect::<Vec<_>>();
+
+			crate::migrations::migrate_to_compact_account_store::<Test, ()>();
+
+			assert!(Account::<Test>::contains_key(1));
+			for who in 2..=4 {
+				assert!(!Account::<Test>::contains_key(who));
+				assert_eq!(FreeBalance::<Test>::get(who), Some(who * 10));
+			}
+			assert_eq!((1..=4).map(|who| Store::get(&who)).collect::<Vec<_>>(), before);
+
+			// running it again moves nothing.
+			crate::migrations::migrate_to_compact_account_store::<Test, ()>();
+			assert_eq!((1..=4).map(|who| Store::get(&who)).collect::<Vec<_>>(), before);
+		});
+}
+
+#[test]
+fn slash_pro_rata_rounding_stays_within_bounds_and_conserves_units() {
+	for mode in &[RoundingMode::Floor, RoundingMode::Ceil, RoundingMode::NearestBankers] {
+		FEE_ROUNDING.with(|v| *v.borrow_mut() = *mode);
+		<ExtBuilder>::default()
+			.existential_deposit(1)
+			.monied(true)
+			.build()
+			.execute_with(|| {
+				use frame_support::traits::NamedReservableCurrency;
+				let id = *b"orders  ";
+				let _ = Balances::deposit_creating(&5, 100_000);
+				assert_ok!(Balances::reserve_named(&id, &5, 100_000));
+				let issuance = Balances::total_issuance();
+
+				let slashes = 300;
+				let (mut paid, mut slashed) = (0u64, 0u64);
+				for k in 0..slashes {
+					let total_slash = k % 17 + 1;
+					let shares = vec![(1, k as u32 % 5 + 1), (2, 3), (3, k as u32 % 7 + 1)];
+					let total_weight = shares.iter().map(|(_, w)| *w as u64).sum::<u64>();
+					let exact = |weight: u32| (total_slash * weight as u64) as i128 * 100 / total_weight as i128;
+					let payouts = Balances::slash_pro_rata(&id, &5, total_slash, shares.clone());
+					for ((_, weight), (_, amount)) in shares.into_iter().zip(payouts) {
+						// within a unit of the exact share, in hundredths of a unit.
+						let error = amount as i128 * 100 - exact(weight);
+						match mode {
+							RoundingMode::Floor => assert!(error <= 0 && -error < 100),
+							RoundingMode::Ceil => assert!(error < 100),
+							RoundingMode::NearestBankers => assert!(error.abs() <= 50),
+						}
+						paid += amount;
+					}
+					slashed += total_slash;
+				}
+
+				// whatever wasn't paid out was burned, and no more than was slashed was paid.
+				assert_eq!(Balances::reserved_balance_named(&id, &5), 100_000 - slashed);
+				assert_eq!(issuance - Balances::total_issuance(), slashed - paid);
+				assert!(paid <= slashed);
+				assert_ok!(Balances::do_try_state());
+			});
+	}
+}
@@ -12,7 +12,7 @@ use sp_runtime::{
 use sp_core::H256;
 use sp_io;
 use frame_support::parameter_types;
-use frame_support::traits::StorageMapShim;
+use frame_support::traits::{StorageMapShim, Contains};
 use frame_support::weights::{IdentityFee};
 use crate::{
 	self as pallet_balances,
@@ -93,12 +93,24 @@ impl OnUnbalanced<NegativeImbalance<Test>> for OnDustRemoval {
 		let _ = Balances::resolve_into_existing(&1, amount);
 	}
 }
+pub struct DustRemovalWhitelist;
+impl Contains<u64> for DustRemovalWhitelist {
+	fn sorted_members() -> Vec<u64> {
+		vec![]
+	}
+}
 parameter_types! {
 	pub const MaxLocks: u32 = 50;
+	pub const MaxBatchSize: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+	pub const MaxMemoLength: u32 = 32;
+	pub const MaxTagLength: u32 = 4;
+	pub const MaxSubAccountSeedLength: u32 = 16;
 }
 impl Config for Test {
 	type Balance = u64;
 	type DustRemoval = OnDustRemoval;
+	type DustRemovalWhitelist = DustRemovalWhitelist;
 	type Event = Event;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = StorageMapShim<
@@ -109,6 +121,49 @@ impl Config for Test {
 	>;
 	type MaxLocks = MaxLocks;
 	type WeightInfo = ();
+	type MaxBatchSize = MaxBatchSize;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type MaxMemoLength = MaxMemoLength;
+	type MaxSubAccountSeedLength = MaxSubAccountSeedLength;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SetEDOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type BurnAccount = ();
+	type BurnInterval = ();
+	type TransferFilter = crate::AllowAllTransfers;
+	type DestinationFilter = crate::AllowAllTransfers;
+	type SupportedAssets = crate::NativeAssetOnly;
+	type FeeOnlyAccounts = crate::NoAccounts;
+	type FeeOnlyMinimum = ();
+	type OffchainSignature = sp_runtime::testing::TestSignature;
+	type SigningPublicKey = sp_runtime::testing::UintAuthorityId;
+	type MergeableLocks = crate::NoMergeableLocks;
+	type MinTransfer = ();
+	type IssuanceHistoryDepth = ();
+	type TipToAuthor = ();
+	type Rounding = ();
+	type LockAggregation = ();
+	type ReserveQuota = crate::NoReserveQuota;
+	type SystemAccounts = ();
+	type SponsoredFeeRefund = ();
+	type FreezeOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type EmergencyOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type MintOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type ForceLockOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type AccountCreationFee = ();
+	type CreationFeeDestination = ();
+	type CheckpointFee = ();
+	type CheckpointFeeDestination = ();
+	type FeeReserve = ();
+	type InflationOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type InflationAccount = ();
+	type MaxIssuance = crate::UncappedIssuance;
+	type OnReservedChanged = ();
+	type TagOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type MaxTagLength = MaxTagLength;
+	type LockPruneWeight = ();
+	type ClaimableReserveId = ();
 }
 
 pub struct ExtBuilder {
@@ -136,6 +191,8 @@ impl ExtBuilder {
 		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 		pallet_balances::GenesisConfig::<Test> {
 			balances: vec![],
+			reserved_balances: vec![],
+			locks: vec![],
 		}.assimilate_storage(&mut t).unwrap();
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
@@ -171,7 +228,7 @@ fn transfer_dust_removal_tst1_should_work() {
 
 			// Verify the events
 			// Number of events expected is 8
-			assert_eq!(System::events().len(), 11);
+			assert_eq!(System::events().len(), 13);
 
 			assert!(
 				System::events().iter().any(
@@ -218,7 +275,7 @@ fn transfer_dust_removal_tst2_should_work() {
 
 			// Verify the events
 			// Number of events expected is 8
-			assert_eq!(System::events().len(), 9);
+			assert_eq!(System::events().len(), 11);
 
 			assert!(
 				System::events().iter().any(
@@ -273,8 +330,8 @@ fn repatriating_reserved_balance_dust_removal_should_work() {
 			assert_eq!(Balances::free_balance(1), 1500);
 
 			// Verify the events
-			// Number of events expected is 10
-			assert_eq!(System::events().len(), 10);
+			// Number of events expected is 12
+			assert_eq!(System::events().len(), 12);
 
 			assert!(
 				System::events().iter().any(
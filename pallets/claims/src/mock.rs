@@ -41,16 +41,68 @@ impl frame_system::Config for Test {
 
 parameter_types! {
     pub const ExistentialDeposit: u64 = 1;
+    pub const MaxLocks: u32 = 50;
+}
+
+pub struct DustRemovalWhitelist;
+impl frame_support::traits::Contains<u64> for DustRemovalWhitelist {
+    fn sorted_members() -> Vec<u64> {
+        vec![]
+    }
 }
 
 impl balances::Config for Test {
     type Balance = u64;
     type DustRemoval = ();
+    type DustRemovalWhitelist = DustRemovalWhitelist;
     type Event = ();
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
-    type MaxLocks = ();
+    type MaxLocks = MaxLocks;
+    type MaxBatchSize = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type MaxMemoLength = ();
+    type MaxSubAccountSeedLength = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SetEDOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type BurnAccount = ();
+    type BurnInterval = ();
+    type TransferFilter = balances::AllowAllTransfers;
+    type DestinationFilter = balances::AllowAllTransfers;
+    type SupportedAssets = balances::NativeAssetOnly;
+    type OffchainSignature = sp_runtime::testing::TestSignature;
+    type SigningPublicKey = sp_runtime::testing::UintAuthorityId;
+    type MergeableLocks = balances::NoMergeableLocks;
+    type MinTransfer = ();
+    type IssuanceHistoryDepth = ();
+    type TipToAuthor = ();
+    type Rounding = ();
+    type LockAggregation = ();
+    type FreezeOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type EmergencyOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MintOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type ForceLockOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type SystemAccounts = ();
+    type SponsoredFeeRefund = ();
+    type FeeOnlyAccounts = balances::NoAccounts;
+    type FeeOnlyMinimum = ();
+    type ReserveQuota = balances::NoReserveQuota;
+    type AccountCreationFee = ();
+    type CreationFeeDestination = ();
+    type FeeReserve = ();
+    type InflationOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type InflationAccount = ();
+    type MaxIssuance = balances::UncappedIssuance;
+    type OnReservedChanged = ();
+    type CheckpointFee = ();
+    type CheckpointFeeDestination = ();
+    type TagOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MaxTagLength = ();
+    type LockPruneWeight = ();
+    type ClaimableReserveId = ();
 }
 
 parameter_types!{
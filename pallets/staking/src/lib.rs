@@ -50,7 +50,7 @@ pub mod weight;
 use tars;
 use primitives::{
     constants::{currency::*, time::*},
-    traits::{UsableCurrency, MurphyInterface}
+    traits::{UsableCurrency, ReservedFirstSlash, MurphyInterface, OnEraBoundary}
 };
 
 const DEFAULT_MINIMUM_VALIDATOR_COUNT: u32 = 4;
@@ -378,7 +378,8 @@ pub trait Config: frame_system::Config {
     /// The staking's module id, used for staking pot
     type ModuleId: Get<ModuleId>;
     /// The staking balance.
-    type Currency: UsableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+    type Currency: UsableCurrency<Self::AccountId, Moment = Self::BlockNumber>
+        + ReservedFirstSlash<Self::AccountId>;
 
     /// Time used for computing era duration.
     ///
@@ -402,6 +403,9 @@ pub trait Config: frame_system::Config {
     /// Handler for the unbalanced reduction when slashing a staker.
     type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+    /// Whether slashes take from the reserved balance of a staker before its free balance.
+    type SlashReservedFirst: Get<bool>;
+
     /// Handler for the unbalanced increment when rewarding a staker.
     type Reward: OnUnbalanced<PositiveImbalanceOf<Self>>;
 
@@ -443,6 +447,9 @@ pub trait Config: frame_system::Config {
     /// Authoring and Staking ratio for murphy staking pot
     type AuthoringAndStakingRatio: Get<Perbill>;
 
+    /// Handler told about the start of every era.
+    type OnEraBoundary: OnEraBoundary;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
@@ -1846,6 +1853,7 @@ impl<T: Config> Module<T> {
             "💸 Start the era {:?}",
             active_era,
         );
+        T::OnEraBoundary::on_era_boundary(active_era);
         let bonding_duration = T::BondingDuration::get();
 
         BondedEras::mutate(|bonded| {
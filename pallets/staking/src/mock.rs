@@ -145,15 +145,84 @@ impl frame_system::Config for Test {
 parameter_types! {
     pub const TransferFee: Balance = 0;
     pub const CreationFee: Balance = 0;
+    pub const MaxLocks: u32 = 50;
 }
+pub struct DustRemovalWhitelist;
+impl frame_support::traits::Contains<AccountId> for DustRemovalWhitelist {
+    fn sorted_members() -> Vec<AccountId> {
+        vec![]
+    }
+}
+
+/// A signature which is valid for any message of the account it names.
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug)]
+pub struct TestSignature(pub AccountId);
+pub struct TestSigner(pub AccountId);
+impl sp_runtime::traits::IdentifyAccount for TestSigner {
+    type AccountId = AccountId;
+    fn into_account(self) -> AccountId {
+        self.0
+    }
+}
+impl sp_runtime::traits::Verify for TestSignature {
+    type Signer = TestSigner;
+    fn verify<L: sp_runtime::traits::Lazy<[u8]>>(&self, _msg: L, signer: &AccountId) -> bool {
+        &self.0 == signer
+    }
+}
+
 impl balances::Config for Test {
     type Balance = Balance;
     type DustRemoval = ();
+    type DustRemovalWhitelist = DustRemovalWhitelist;
     type Event = ();
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
-    type MaxLocks = ();
+    type MaxLocks = MaxLocks;
+    type MaxBatchSize = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type MaxMemoLength = ();
+    type MaxSubAccountSeedLength = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SetEDOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type BurnAccount = ();
+    type BurnInterval = ();
+    type TransferFilter = balances::AllowAllTransfers;
+    type DestinationFilter = balances::AllowAllTransfers;
+    type SupportedAssets = balances::NativeAssetOnly;
+    type OffchainSignature = TestSignature;
+    type SigningPublicKey = TestSigner;
+    type MergeableLocks = balances::NoMergeableLocks;
+    type MinTransfer = ();
+    type IssuanceHistoryDepth = ();
+    type TipToAuthor = ();
+    type Rounding = ();
+    type LockAggregation = ();
+    type FreezeOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type EmergencyOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MintOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type ForceLockOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type SystemAccounts = ();
+    type SponsoredFeeRefund = ();
+    type FeeOnlyAccounts = balances::NoAccounts;
+    type FeeOnlyMinimum = ();
+    type ReserveQuota = balances::NoReserveQuota;
+    type AccountCreationFee = ();
+    type CreationFeeDestination = ();
+    type FeeReserve = ();
+    type InflationOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type InflationAccount = ();
+    type MaxIssuance = balances::UncappedIssuance;
+    type OnReservedChanged = ();
+    type CheckpointFee = ();
+    type CheckpointFeeDestination = ();
+    type TagOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MaxTagLength = ();
+    type LockPruneWeight = ();
+    type ClaimableReserveId = ();
 }
 parameter_types! {
     pub const Period: BlockNumber = 1;
@@ -254,6 +323,7 @@ impl Config for Test {
     type RewardRemainder = ();
     type Event = ();
     type Slash = ();
+    type SlashReservedFirst = ();
     type Reward = ();
     type Randomness = TestRandomness;
     type SessionsPerEra = SessionsPerEra;
@@ -266,6 +336,7 @@ impl Config for Test {
     type MurphyStakingPot = TestStaking;
     type MurphyStakingPotDuration = MurphyStakingPotDuration;
     type AuthoringAndStakingRatio = AuthoringAndStakingRatio;
+    type OnEraBoundary = ();
     type WeightInfo = weight::WeightInfo;
 }
 
@@ -429,6 +500,8 @@ impl ExtBuilder {
                 (999, 1_000_000_000_000),
                 (Staking::staking_pot(), self.staking_pot)
             ],
+            reserved_balances: vec![],
+            locks: vec![],
         }.assimilate_storage(&mut storage);
 
         let stake_21 = if self.fair { 1000 } else { 2000 };
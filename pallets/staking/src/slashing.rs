@@ -585,7 +585,11 @@ pub fn do_slash<T: Config>(
     let value = ledger.slash(value, T::Currency::minimum_balance());
 
     if !value.is_zero() {
-        let (imbalance, missing) = T::Currency::slash(stash, value);
+        let (imbalance, missing) = if T::SlashReservedFirst::get() {
+            T::Currency::slash_reserved_first(stash, value)
+        } else {
+            T::Currency::slash(stash, value)
+        };
         slashed_imbalance.subsume(imbalance);
 
         if !missing.is_zero() {
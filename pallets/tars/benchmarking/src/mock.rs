@@ -64,14 +64,69 @@ impl system::Config for Test {
     type SS58Prefix = ();
 }
 
+parameter_types! {
+    pub const MaxLocks: u32 = 50;
+}
+
+pub struct DustRemovalWhitelist;
+impl frame_support::traits::Contains<AccountId> for DustRemovalWhitelist {
+    fn sorted_members() -> Vec<AccountId> {
+        vec![]
+    }
+}
+
 impl balances::Config for Test {
     type Balance = Balance;
     type DustRemoval = ();
+    type DustRemovalWhitelist = DustRemovalWhitelist;
     type Event = ();
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
-    type MaxLocks = ();
+    type MaxLocks = MaxLocks;
+    type MaxBatchSize = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type MaxMemoLength = ();
+    type MaxSubAccountSeedLength = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SetEDOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type BurnAccount = ();
+    type BurnInterval = ();
+    type TransferFilter = balances::AllowAllTransfers;
+    type DestinationFilter = balances::AllowAllTransfers;
+    type SupportedAssets = balances::NativeAssetOnly;
+    type OffchainSignature = sp_runtime::MultiSignature;
+    type SigningPublicKey = sp_runtime::MultiSigner;
+    type MergeableLocks = balances::NoMergeableLocks;
+    type MinTransfer = ();
+    type IssuanceHistoryDepth = ();
+    type TipToAuthor = ();
+    type Rounding = ();
+    type LockAggregation = ();
+    type FreezeOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type EmergencyOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MintOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type ForceLockOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type SystemAccounts = ();
+    type SponsoredFeeRefund = ();
+    type FeeOnlyAccounts = balances::NoAccounts;
+    type FeeOnlyMinimum = ();
+    type ReserveQuota = balances::NoReserveQuota;
+    type AccountCreationFee = ();
+    type CreationFeeDestination = ();
+    type FeeReserve = ();
+    type InflationOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type InflationAccount = ();
+    type MaxIssuance = balances::UncappedIssuance;
+    type OnReservedChanged = ();
+    type CheckpointFee = ();
+    type CheckpointFeeDestination = ();
+    type TagOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MaxTagLength = ();
+    type LockPruneWeight = ();
+    type ClaimableReserveId = ();
 }
 
 parameter_types! {
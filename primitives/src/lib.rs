@@ -34,6 +34,9 @@ pub type Moment = u64;
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
+/// Counter for the number of eras that have passed.
+pub type EraIndex = u32;
+
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
@@ -1,8 +1,9 @@
 // Copyright (C) 2019-2021 Calcu Network Technologies Ltd.
 // This file is part of Calcu.
 
-use frame_support::traits::LockableCurrency;
-use crate::{TarsAnchor, MerkleRoot, BlockNumber};
+use frame_support::traits::{Currency, LockableCurrency, ReservableCurrency, BalanceStatus};
+use sp_runtime::{DispatchResult, DispatchError};
+use crate::{TarsAnchor, MerkleRoot, BlockNumber, EraIndex};
 use sp_std::collections::btree_set::BTreeSet;
 
 /// A currency whose accounts can have liquidity restrictions.
@@ -10,6 +11,242 @@ pub trait UsableCurrency<AccountId>: LockableCurrency<AccountId> {
 	fn usable_balance(who: &AccountId) -> Self::Balance;
 }
 
+/// A currency which can slash the reserved balance of an account before its free balance.
+pub trait ReservedFirstSlash<AccountId>: Currency<AccountId> {
+	/// Deducts up to `value` from the balance of `who`, exhausting the reserved balance before
+	/// the free balance is touched. This function cannot fail.
+	///
+	/// As much funds up to `value` will be deducted as possible. If this is less than `value`,
+	/// then a non-zero second item will be returned.
+	fn slash_reserved_first(who: &AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance);
+}
+
+/// A currency where funds can be reserved from the user under a named identifier, so that
+/// different subsystems can never touch each other's reserves.
+pub trait NamedReservableCurrency<AccountId>: ReservableCurrency<AccountId> {
+	/// An identifier for a reserve. Used for disambiguating different reserves so that
+	/// they can be individually replaced or removed.
+	type ReserveIdentifier;
+
+	/// Deducts up to `value` from reserved balance of `who` under the reserve `id`. This function
+	/// cannot fail.
+	///
+	/// As much funds up to `value` will be deducted as possible. If the reserve balance of `who`
+	/// is less than `value`, then a non-zero second item will be returned.
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		who: &AccountId,
+		value: Self::Balance
+	) -> (Self::NegativeImbalance, Self::Balance);
+
+	/// The amount of the balance of a given account that is reserved under the reserve `id`.
+	fn reserved_balance_named(id: &Self::ReserveIdentifier, who: &AccountId) -> Self::Balance;
+
+	/// Moves `value` from balance to reserved balance under the reserve `id`.
+	///
+	/// If the free balance is lower than `value`, then no funds will be moved and an `Err` will
+	/// be returned to notify of this. This is different behavior than `unreserve_named`.
+	fn reserve_named(id: &Self::ReserveIdentifier, who: &AccountId, value: Self::Balance) -> DispatchResult;
+
+	/// Moves up to `value` from reserved balance under the reserve `id` to free balance. This
+	/// function cannot fail.
+	///
+	/// As much funds up to `value` will be moved as possible. If the reserve balance of `who`
+	/// under `id` is less than `value`, then the remaining amount will be returned.
+	fn unreserve_named(id: &Self::ReserveIdentifier, who: &AccountId, value: Self::Balance) -> Self::Balance;
+
+	/// Moves up to `value` from reserved balance of account `slashed` under the reserve `id` to
+	/// balance of account `beneficiary`. `beneficiary` must exist for this to succeed. If it does
+	/// not, `Err` will be returned. Funds will be placed in either the `free` balance or the
+	/// `reserved` balance under the same `id`, depending on the `status`.
+	///
+	/// As much funds up to `value` will be deducted as possible. If this is less than `value`,
+	/// then `Ok(non_zero)` will be returned.
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> Result<Self::Balance, DispatchError>;
+
+	/// Ensure the reserved balance under the reserve `id` is equal to `value`.
+	///
+	/// This will reserve extra amount of current reserved balance is less than `value`.
+	/// And unreserve if current reserved balance is greater than `value`.
+	fn ensure_reserved_named(id: &Self::ReserveIdentifier, who: &AccountId, value: Self::Balance) -> DispatchResult {
+		let current = Self::reserved_balance_named(id, who);
+		if current > value {
+			// we always have enough balance to unreserve here
+			Self::unreserve_named(id, who, current - value);
+			Ok(())
+		} else if value > current {
+			// we checked value > current
+			Self::reserve_named(id, who, value - current)
+		} else { // current == value
+			Ok(())
+		}
+	}
+
+	/// Unreserve all the named reserved balances, returning the unreserved amount.
+	///
+	/// Is a no-op if the value to be unreserved is zero.
+	fn unreserve_all_named(id: &Self::ReserveIdentifier, who: &AccountId) -> Self::Balance {
+		let value = Self::reserved_balance_named(id, who);
+		Self::unreserve_named(id, who, value);
+		value
+	}
+
+	/// Slash all the reserved balance under the reserve `id`, returning the negative imbalance
+	/// created.
+	///
+	/// Is a no-op if the value to be slashed is zero.
+	fn slash_all_reserved_named(id: &Self::ReserveIdentifier, who: &AccountId) -> Self::NegativeImbalance {
+		let value = Self::reserved_balance_named(id, who);
+		Self::slash_reserved_named(id, who, value).0
+	}
+
+	/// Move all the named reserved balance of one account into the balance of another, according
+	/// to `status`. If `status` is `Reserved`, the balance will be reserved with the same `id`.
+	///
+	/// Is a no-op if:
+	/// - the value to be moved is zero; or
+	/// - the `slashed` id equal to `beneficiary` and the `status` is `Reserved`.
+	fn repatriate_all_reserved_named(
+		id: &Self::ReserveIdentifier,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		status: BalanceStatus,
+	) -> DispatchResult {
+		let value = Self::reserved_balance_named(id, slashed);
+		Self::repatriate_reserved_named(id, slashed, beneficiary, value, status).map(|_| ())
+	}
+}
+
+/// The `fungible` family of token traits, mirroring the interface of newer Substrate releases
+/// so that pallets written against it can be integrated before the runtime is upgraded.
+pub mod fungible {
+	use sp_runtime::{RuntimeDebug, DispatchResult, DispatchError};
+
+	/// One of a number of consequences of depositing a fungible into an account.
+	#[derive(Copy, Clone, RuntimeDebug, Eq, PartialEq)]
+	pub enum DepositConsequence {
+		/// Deposit couldn't happen due to the amount being too low. This is usually because the
+		/// account doesn't yet exist and the deposit wouldn't bring it to at least the minimum
+		/// needed for existence.
+		BelowMinimum,
+		/// An overflow would occur. This is practically unexpected, but could happen in test
+		/// systems with extremely small balance types or balances that approach the max value
+		/// of the balance type.
+		Overflow,
+		/// Account continued in existence.
+		Success,
+	}
+
+	/// One of a number of consequences of withdrawing a fungible from an account.
+	#[derive(Copy, Clone, RuntimeDebug, Eq, PartialEq)]
+	pub enum WithdrawConsequence<Balance> {
+		/// Withdraw could not happen since the amount to be withdrawn is less than the total
+		/// funds in the account.
+		NoFunds,
+		/// The withdraw would mean the account dying when it needs to exist (usually because
+		/// it is a provider and there are consumer references on it).
+		WouldDie,
+		/// There has been an underflow in the system. This is indicative of a corrupt state and
+		/// likely unrecoverable.
+		Underflow,
+		/// Not enough of the funds in the account are available for withdrawal.
+		Frozen,
+		/// Account balance would reduce to zero, potentially destroying it. The parameter is the
+		/// amount of balance which is destroyed.
+		ReducedToZero(Balance),
+		/// Account continued in existence.
+		Success,
+	}
+
+	/// Trait for providing balance-inspection access to a fungible asset.
+	pub trait Inspect<AccountId> {
+		/// Scalar type for representing balance of an account.
+		type Balance;
+
+		/// The total amount of issuance in the system.
+		fn total_issuance() -> Self::Balance;
+
+		/// The minimum balance any single account may have.
+		fn minimum_balance() -> Self::Balance;
+
+		/// Get the balance of `who`.
+		fn balance(who: &AccountId) -> Self::Balance;
+
+		/// Get the maximum amount that `who` can withdraw/transfer successfully.
+		fn reducible_balance(who: &AccountId, keep_alive: bool) -> Self::Balance;
+
+		/// Returns `Success` if the balance of `who` may be increased by `amount`, otherwise the
+		/// reason why it may not.
+		fn can_deposit(who: &AccountId, amount: Self::Balance) -> DepositConsequence;
+
+		/// Returns the consequence of decreasing the balance of `who` by `amount`.
+		fn can_withdraw(who: &AccountId, amount: Self::Balance) -> WithdrawConsequence<Self::Balance>;
+	}
+
+	/// Trait for providing an ERC-20 style fungible asset.
+	pub trait Mutate<AccountId>: Inspect<AccountId> {
+		/// Increase the balance of `who` by exactly `amount`, minting new tokens. If that isn't
+		/// possible then an `Err` is returned and nothing is changed.
+		fn mint_into(who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+		/// Decrease the balance of `who` by at least `amount`, possibly slightly more in the case
+		/// of minimum_balance requirements, burning the tokens. If that isn't possible then an
+		/// `Err` is returned and nothing is changed. If successful, the amount of tokens reduced
+		/// is returned.
+		fn burn_from(who: &AccountId, amount: Self::Balance) -> Result<Self::Balance, DispatchError>;
+	}
+
+	/// Trait for inspecting a fungible asset whose balances can be held for a reason.
+	pub trait InspectHold<AccountId>: Inspect<AccountId> {
+		/// The reason for which some balance is held.
+		type Reason;
+
+		/// Get the balance of `who` held for `reason`.
+		fn balance_on_hold(reason: &Self::Reason, who: &AccountId) -> Self::Balance;
+
+		/// Get the balance of `who` held for any reason.
+		fn total_balance_on_hold(who: &AccountId) -> Self::Balance;
+
+		/// Returns `true` if `amount` more of the balance of `who` could be held for `reason`.
+		fn can_hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> bool;
+	}
+
+	/// Trait for holding and releasing the balances of a fungible asset.
+	pub trait MutateHold<AccountId>: InspectHold<AccountId> {
+		/// Hold `amount` of the balance of `who` for `reason`. If that isn't possible then an
+		/// `Err` is returned and nothing is changed.
+		fn hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+		/// Release up to `amount` of the balance of `who` held for `reason`, returning the amount
+		/// released. Unless `best_effort`, an `Err` is returned and nothing is changed if less
+		/// than `amount` is held.
+		fn release(
+			reason: &Self::Reason,
+			who: &AccountId,
+			amount: Self::Balance,
+			best_effort: bool,
+		) -> Result<Self::Balance, DispatchError>;
+
+		/// Move up to `amount` of the balance of `source` held for `reason` to `dest`, returning
+		/// the amount moved. It is held for the same `reason` in `dest` if `on_hold`, and is
+		/// free otherwise. `best_effort` is as for `release`.
+		fn transfer_held(
+			reason: &Self::Reason,
+			source: &AccountId,
+			dest: &AccountId,
+			amount: Self::Balance,
+			best_effort: bool,
+			on_hold: bool,
+		) -> Result<Self::Balance, DispatchError>;
+	}
+}
+
 /// Means for interacting with a specialized version of the `tars` trait.
 pub trait TarsInterface<AccountId> {
 	// Check whether work report was reported in the last report slot according to given block number
@@ -33,3 +270,23 @@ pub trait MurphyInterface<AccountId, Balance> {
 	// used for distribute murphy staking payout
 	fn withdraw_staking_pot() -> Balance;
 }
+
+/// Something to be told about the start of every staking era.
+pub trait OnEraBoundary {
+	/// Era `era` just started.
+	fn on_era_boundary(era: EraIndex);
+}
+
+impl OnEraBoundary for () {
+	fn on_era_boundary(_era: EraIndex) {}
+}
+
+/// Something to be told when the reserved balance of an account changes, however it changed.
+pub trait OnReservedChanged<AccountId, Balance> {
+	/// The reserved balance of `who` went from `old` to `new`.
+	fn on_reserved_changed(who: &AccountId, old: Balance, new: Balance);
+}
+
+impl<AccountId, Balance> OnReservedChanged<AccountId, Balance> for () {
+	fn on_reserved_changed(_who: &AccountId, _old: Balance, _new: Balance) {}
+}
@@ -81,6 +81,7 @@ pub fn create_full<C, P, SC, B>(deps: FullDeps<C, P, SC, B>) -> RpcExtension whe
     C: Send + Sync + 'static,
     C::Api: frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: balances_rpc::BalancesRuntimeApi<Block, AccountId, Balance>,
     C::Api: BabeApi<Block>,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + Sync + Send + 'static,
@@ -90,6 +91,7 @@ pub fn create_full<C, P, SC, B>(deps: FullDeps<C, P, SC, B>) -> RpcExtension whe
 {
     use frame_rpc_system::{FullSystem, SystemApi};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+    use balances_rpc::{Balances, BalancesApi};
     use sc_finality_grandpa_rpc::{GrandpaApi, GrandpaRpcHandler};
     use sc_consensus_babe_rpc::BabeRpcHandler;
 
@@ -121,6 +123,9 @@ pub fn create_full<C, P, SC, B>(deps: FullDeps<C, P, SC, B>) -> RpcExtension whe
     io.extend_with(
         TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
     );
+    io.extend_with(
+        BalancesApi::to_delegate(Balances::new(client.clone()))
+    );
     io.extend_with(
         sc_consensus_babe_rpc::BabeApi::to_delegate(
             BabeRpcHandler::new(
@@ -1,9 +1,12 @@
 // Copyright (C) 2019-2021 Calcu Network Technologies Ltd.
 // This file is part of Calcu.
 
-use sp_runtime::traits::{Convert, SaturatedConversion};
-use frame_support::traits::{OnUnbalanced, Currency};
-use crate::{Balances, Authorship, NegativeImbalance};
+use sp_std::prelude::*;
+use sp_runtime::traits::{Convert, SaturatedConversion, AccountIdConversion};
+use frame_support::traits::{OnUnbalanced, Currency, Contains, Get};
+use crate::{
+    Balances, Authorship, NegativeImbalance, AccountId, StakingModuleId, TreasuryModuleId, DustBurnModuleId,
+};
 
 /// Logic for the author to get a portion of fees.
 pub struct Author;
@@ -13,6 +16,48 @@ impl OnUnbalanced<NegativeImbalance> for Author {
     }
 }
 
+/// Pallet accounts which must never be reaped, even below the existential deposit.
+pub struct DustRemovalWhitelist;
+impl Contains<AccountId> for DustRemovalWhitelist {
+    fn sorted_members() -> Vec<AccountId> {
+        let mut members: Vec<AccountId> = vec![
+            StakingModuleId::get().into_account(),
+            TreasuryModuleId::get().into_account(),
+            DustBurnModuleId::get().into_account(),
+        ];
+        members.sort();
+        members
+    }
+}
+
+/// The pallet account collecting the swept dust until it is burned.
+pub struct DustBurnAccount;
+impl Get<AccountId> for DustBurnAccount {
+    fn get() -> AccountId {
+        DustBurnModuleId::get().into_account()
+    }
+}
+
+/// The treasury account, which the per-block inflation of the balances pallet is minted to.
+pub struct TreasuryAccount;
+impl Get<AccountId> for TreasuryAccount {
+    fn get() -> AccountId {
+        TreasuryModuleId::get().into_account()
+    }
+}
+
+/// Pallet accounts whose balance doesn't circulate.
+pub struct SystemAccounts;
+impl Get<Vec<AccountId>> for SystemAccounts {
+    fn get() -> Vec<AccountId> {
+        vec![
+            StakingModuleId::get().into_account(),
+            TreasuryModuleId::get().into_account(),
+            DustBurnModuleId::get().into_account(),
+        ]
+    }
+}
+
 /// Simple structure that exposes how u64 currency can be represented as... u64.
 pub struct CurrencyToVoteHandler;
 
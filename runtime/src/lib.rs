@@ -49,6 +49,7 @@ pub use frame_support::{
     },
     StorageValue,
 };
+use frame_support::traits::{ReservableCurrency, WithdrawReasons};
 use frame_system::limits::{BlockLength, BlockWeights};
 use frame_system::{EnsureRoot, EnsureOneOf};
 pub use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
@@ -60,7 +61,9 @@ use pallet_session::{historical as session_historical};
 pub use pallet_timestamp::Call as TimestampCall;
 
 /// Implementations of some helper traits passed into runtime modules as associated types.
-use impls::{CurrencyToVoteHandler, Author};
+use impls::{
+    CurrencyToVoteHandler, Author, DustRemovalWhitelist, DustBurnAccount, SystemAccounts, TreasuryAccount,
+};
 
 /// Calcu primitives
 use primitives::{
@@ -102,10 +105,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("calcu"),
     impl_name: create_runtime_str!("calcuio-calcu"),
     authoring_version: 1,
-    spec_version: 1,
+    spec_version: 2,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1
+    transaction_version: 2
 };
 
 /// The version information used to identify this runtime when compiled natively.
@@ -406,6 +409,10 @@ parameter_types! {
     pub const MurphyStakingPotDuration: u32 = 60;
     // Authoring and Staking reward ratio
     pub const AuthoringAndStakingRatio: Perbill = Perbill::from_percent(20);
+    // Half of each staking slash is burned, the rest goes to the treasury
+    pub const SlashBurnRatio: Perbill = Perbill::from_percent(50);
+    // Slashes take from free balance first.
+    pub const SlashReservedFirst: bool = false;
 }
 
 impl staking::Config for Runtime {
@@ -416,7 +423,8 @@ impl staking::Config for Runtime {
     type CurrencyToVote = CurrencyToVoteHandler;
     type RewardRemainder = ();
     type Event = Event;
-    type Slash = Treasury;
+    type Slash = balances::SlashSplit<Runtime, SlashBurnRatio, TreasuryAccount>;
+    type SlashReservedFirst = SlashReservedFirst;
     type Reward = ();
     type Randomness = RandomnessCollectiveFlip;
     type SessionsPerEra = SessionsPerEra;
@@ -431,6 +439,7 @@ impl staking::Config for Runtime {
     type MurphyStakingPot = Murphy;
     type MurphyStakingPotDuration = MurphyStakingPotDuration;
     type AuthoringAndStakingRatio = AuthoringAndStakingRatio;
+    type OnEraBoundary = Balances;
     type WeightInfo = staking::weight::WeightInfo;
 }
 
@@ -448,6 +457,22 @@ impl pallet_offences::Config for Runtime {
 parameter_types! {
     pub const ExistentialDeposit: u128 = 1 * CENTS;
     pub const MaxLocks: u32 = 50;
+    pub const MaxBatchSize: u32 = 128;
+    pub const MaxReserves: u32 = 50;
+    pub const MaxMemoLength: u32 = 256;
+    pub const MaxTagLength: u32 = 32;
+    pub const MaxSubAccountSeedLength: u32 = 16;
+    pub const MinTransfer: Balance = 1 * CENTS;
+    pub const SponsoredFeeRefund: Balance = 10 * MILLICENTS;
+    pub const ClaimableReserveId: [u8; 8] = *b"claimabl";
+    pub const AccountCreationFee: Balance = 0;
+    pub const CheckpointFee: Balance = 1 * CENTS;
+    pub const FeeReserve: Balance = 0;
+    pub const IssuanceHistoryDepth: u32 = 84;
+    pub const DustBurnModuleId: ModuleId = ModuleId(*b"cdustbrn");
+    pub const DustBurnInterval: BlockNumber = 1 * DAYS;
+    pub const DustTarget: balances::DustDestination = balances::DustDestination::Sweep;
+    pub LockPruneWeight: Weight = Perbill::from_percent(1) * RuntimeBlockWeights::get().max_block;
 }
 
 parameter_types! {
@@ -678,12 +703,60 @@ impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 
 impl balances::Config for Runtime {
     type Balance = Balance;
-    type DustRemoval = ();
+    type DustRemoval = balances::RouteDust<
+        Runtime,
+        DustTarget,
+        pallet_session::FindAccountFromAuthorIndex<Self, Babe>,
+    >;
+    type DustRemovalWhitelist = DustRemovalWhitelist;
     type Event = Event;
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = weights::pallet_balances::WeightInfo<Runtime>;
     type MaxLocks = MaxLocks;
+    type MaxBatchSize = MaxBatchSize;
+    type MaxReserves = MaxReserves;
+    type ReserveIdentifier = [u8; 8];
+    type MaxMemoLength = MaxMemoLength;
+    type MaxSubAccountSeedLength = MaxSubAccountSeedLength;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SetEDOrigin = MoreThanHalfCouncil;
+    type BurnAccount = DustBurnAccount;
+    type BurnInterval = DustBurnInterval;
+    type TransferFilter = balances::AllowAllTransfers;
+    type DestinationFilter = balances::AllowAllTransfers;
+    type SupportedAssets = balances::NativeAssetOnly;
+    type OffchainSignature = Signature;
+    type SigningPublicKey = <Signature as sp_runtime::traits::Verify>::Signer;
+    type MergeableLocks = balances::NoMergeableLocks;
+    type MinTransfer = MinTransfer;
+    type IssuanceHistoryDepth = IssuanceHistoryDepth;
+    type TipToAuthor = Author;
+    type Rounding = ();
+    type LockAggregation = ();
+    type FreezeOrigin = MoreThanHalfCouncil;
+    type EmergencyOrigin = MoreThanHalfCouncil;
+    type MintOrigin = MoreThanHalfCouncil;
+    type InflationOrigin = MoreThanHalfCouncil;
+    type InflationAccount = TreasuryAccount;
+    type ForceLockOrigin = MoreThanHalfCouncil;
+    type SystemAccounts = SystemAccounts;
+    type SponsoredFeeRefund = SponsoredFeeRefund;
+    type FeeOnlyAccounts = balances::NoAccounts;
+    type FeeOnlyMinimum = ExistentialDeposit;
+    type ReserveQuota = balances::NoReserveQuota;
+    type AccountCreationFee = AccountCreationFee;
+    type CreationFeeDestination = Treasury;
+    type FeeReserve = FeeReserve;
+    type MaxIssuance = balances::UncappedIssuance;
+    type OnReservedChanged = ();
+    type CheckpointFee = CheckpointFee;
+    type CheckpointFeeDestination = Treasury;
+    type TagOrigin = MoreThanHalfCouncil;
+    type MaxTagLength = MaxTagLength;
+    type LockPruneWeight = LockPruneWeight;
+    type ClaimableReserveId = ClaimableReserveId;
 }
 
 parameter_types! {
@@ -850,7 +923,10 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
+    // The tip of `ChargeTransactionPayment` is handled like the fee; wallets should leave it at
+    // zero and tip through `ChargeTransactionTip`, which lets the sender burn it instead.
     pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    balances::ChargeTransactionTip<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -1049,6 +1125,81 @@ impl_runtime_apis! {
 		}
     }
 
+    impl balances_rpc_runtime_api::BalancesApi<Block, AccountId, Balance> for Runtime {
+        fn transferable_balance(account: AccountId) -> Balance {
+            Balances::transferable_balance(&account)
+        }
+        fn lock_by_id(
+            account: AccountId,
+            id: LockIdentifier,
+        ) -> Option<balances_rpc_runtime_api::BalanceLock<Balance, BlockNumber>> {
+            Balances::lock_by_id(&account, id)
+        }
+        fn usable_balance(account: AccountId) -> Balance {
+            Balances::usable_balance(&account)
+        }
+        fn usable_balance_for_fees(account: AccountId) -> Balance {
+            Balances::usable_balance_for_fees(&account)
+        }
+        fn account_data(account: AccountId) -> balances_rpc_runtime_api::AccountData<Balance> {
+            Balances::account_data(&account)
+        }
+        fn frozen_balance_breakdown(account: AccountId) -> (Balance, Balance) {
+            Balances::frozen_balance_breakdown(&account)
+        }
+        fn can_reserve(account: AccountId, value: Balance) -> bool {
+            <Balances as ReservableCurrency<_>>::can_reserve(&account, value)
+        }
+        fn can_withdraw(account: AccountId, value: Balance, reasons: WithdrawReasons) -> bool {
+            Balances::can_withdraw_for(&account, value, reasons)
+        }
+        fn issuance_at_era(era: EraIndex) -> Option<Balance> {
+            Balances::issuance_at_era(era)
+        }
+        fn circulating_supply() -> Balance {
+            Balances::circulating_supply()
+        }
+        fn account_refs(account: AccountId) -> (u32, u32, u32) {
+            Balances::account_refs(&account)
+        }
+        fn is_reapable(account: AccountId) -> bool {
+            Balances::is_reapable(&account)
+        }
+        fn simulate_transfer(
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> balances_rpc_runtime_api::SimulatedTransfer<Balance> {
+            Balances::simulate_transfer(&from, &to, value)
+        }
+        fn last_active(account: AccountId) -> Option<BlockNumber> {
+            Balances::last_active(&account)
+        }
+        fn vested_balance(account: AccountId) -> Balance {
+            Balances::vested_balance(&account)
+        }
+        fn balance_summary(account: AccountId) -> balances_rpc_runtime_api::BalanceSummary<Balance> {
+            Balances::balance_summary(&account)
+        }
+        fn dry_run_transfer(
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), sp_runtime::DispatchError> {
+            Balances::dry_run_transfer(from, to, value)
+        }
+        fn accounts_above(
+            threshold: Balance,
+            start_key: Option<AccountId>,
+            limit: u32,
+        ) -> (Vec<(AccountId, Balance)>, Option<AccountId>) {
+            Balances::accounts_above(threshold, start_key, limit)
+        }
+        fn account_tag(account: AccountId) -> Option<Vec<u8>> {
+            Balances::account_tag(&account)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn dispatch_benchmark(